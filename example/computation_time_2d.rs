@@ -1,9 +1,8 @@
 use anyhow::Result;
-use env_logger;
 use rand::Rng;
 use std::time::Instant;
 
-use simple_delaunay_lib::delaunay_2d::delaunay_struct_2d::DelaunayStructure2D;
+use simple_delaunay_lib::delaunay_2d::delaunay_struct_2d::{DelaunayStructure2D, PredicateMode};
 
 fn generate_random_vertices(nb_vert: usize) -> Vec<[f64; 2]> {
     let mut rng = rand::thread_rng();
@@ -33,5 +32,71 @@ fn main() -> Result<()> {
         println!("{} vertices: {}ms", nb_vert, milli);
     }
 
+    // single insertions at random locations, with and without spatial hint
+    let nb_single = 10000;
+    for use_hint in [false, true] {
+        let mut del_struct = DelaunayStructure2D::new();
+        del_struct.insert_vertices(&generate_random_vertices(100000), true)?;
+        if use_hint {
+            del_struct.set_spatial_hint(Some(256))?;
+        }
+        let to_insert = generate_random_vertices(nb_single);
+
+        let now = Instant::now();
+        for &vert in to_insert.iter() {
+            del_struct.insert_vertex(vert, None)?;
+        }
+        let duration = now.elapsed();
+        let milli = duration.as_millis();
+
+        println!(
+            "{} single insertions into 100000 vertices (spatial hint: {}): {}ms",
+            nb_single, use_hint, milli
+        );
+    }
+
+    // full mesh traversal, before and after spatial reordering of triangles
+    let mut del_struct = DelaunayStructure2D::new();
+    del_struct.insert_vertices(&generate_random_vertices(500000), false)?;
+    let nb_triangles = del_struct.get_simplicial().get_nb_triangles();
+    for reordered in [false, true] {
+        if reordered {
+            let mut permutation = Vec::new();
+            del_struct.spatial_reorder(&mut permutation)?;
+        }
+
+        let now = Instant::now();
+        let mut sum_area = 0.;
+        for ind_triangle in 0..nb_triangles {
+            if let Some([pt0, pt1, pt2]) = del_struct.triangle_coords(ind_triangle)? {
+                sum_area +=
+                    (pt1[0] - pt0[0]) * (pt2[1] - pt0[1]) - (pt1[1] - pt0[1]) * (pt2[0] - pt0[0]);
+            }
+        }
+        let duration = now.elapsed();
+        let milli = duration.as_millis();
+
+        println!(
+            "Traversal of {} triangles (reordered: {}, area {:.3}): {}ms",
+            nb_triangles,
+            reordered,
+            sum_area * 0.5,
+            milli
+        );
+    }
+
+    // in-circle predicates on the same random points
+    let vec_pts = generate_random_vertices(1000000);
+    for mode in [PredicateMode::Fast, PredicateMode::Robust] {
+        let now = Instant::now();
+        let mut del_struct = DelaunayStructure2D::new();
+        del_struct.set_predicate_mode(mode);
+        del_struct.insert_vertices(&vec_pts, true)?;
+        let duration = now.elapsed();
+        let milli = duration.as_millis();
+
+        println!("1000000 vertices ({:?} predicates): {}ms", mode, milli);
+    }
+
     Ok(())
 }
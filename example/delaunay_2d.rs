@@ -1,6 +1,4 @@
 use anyhow::Result;
-use env_logger;
-use log;
 use nalgebra::base::*;
 use rand::Rng;
 use std::time::Instant;
@@ -10,7 +8,6 @@ use svg::node::element::path::Data;
 use svg::Document;
 
 use simple_delaunay_lib::delaunay_2d::delaunay_struct_2d::{DelaunayStructure2D, ExtendedTriangle};
-use simple_delaunay_lib::delaunay_2d::simplicial_struct_2d::Node;
 
 fn circle_center_and_radius(
     pt1: &Vector2<f64>,
@@ -111,19 +108,7 @@ fn draw_svg(delaunay: &DelaunayStructure2D, name: String, draw_circles: bool) ->
     document = document.add(rect);
 
     for ind_triangle in 0..delaunay.get_simplicial().get_nb_triangles() {
-        let tri = delaunay.get_simplicial().get_triangle(ind_triangle)?;
-
-        let [h1, h2, h3] = tri.halfedges();
-        let ind_pt1 = h1.first_node();
-        let ind_pt2 = h2.first_node();
-        let ind_pt3 = h3.first_node();
-
-        if let (Node::Value(val1), Node::Value(val2), Node::Value(val3)) =
-            (ind_pt1, ind_pt2, ind_pt3)
-        {
-            let pt1 = delaunay.get_vertices()[val1];
-            let pt2 = delaunay.get_vertices()[val2];
-            let pt3 = delaunay.get_vertices()[val3];
+        if let Some([pt1, pt2, pt3]) = delaunay.triangle_coords(ind_triangle)? {
             document = draw_triangle(
                 document,
                 &[
@@ -0,0 +1,39 @@
+use anyhow::Result;
+use rand::Rng;
+use std::time::Instant;
+
+use simple_delaunay_lib::delaunay_2d::delaunay_struct_2d::DelaunayStructure2D;
+
+fn main() -> Result<()> {
+    env_logger::init();
+
+    let nb_vert = 5000000;
+    let mut rng = rand::thread_rng();
+    let vec_pts: Vec<[f64; 2]> = (0..nb_vert).map(|_| rng.gen()).collect();
+
+    let mut del_struct = DelaunayStructure2D::new();
+    del_struct.insert_vertices(&vec_pts, true)?;
+    let nb_triangles = del_struct.get_simplicial().get_nb_triangles();
+
+    let single_thread = rayon::ThreadPoolBuilder::new().num_threads(1).build()?;
+    let now = Instant::now();
+    let valid = single_thread.install(|| del_struct.is_valid_parallel())?;
+    let milli_single = now.elapsed().as_millis();
+    println!(
+        "{} triangles, 1 thread: {}ms (valid: {})",
+        nb_triangles, milli_single, valid
+    );
+
+    let now = Instant::now();
+    let valid = del_struct.is_valid_parallel()?;
+    let milli = now.elapsed().as_millis();
+    println!(
+        "{} triangles, {} threads: {}ms (valid: {})",
+        nb_triangles,
+        rayon::current_num_threads(),
+        milli,
+        valid
+    );
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use anyhow::Result;
+use std::format;
+use std::io::{self, Read, Write};
+
+/// Current version of the binary format, version 1 lacking the fixed vertices
+pub(crate) const BINARY_VERSION: u8 = 2;
+
+/// Value encoding the node at infinity
+pub(crate) const BINARY_INFINITY: u64 = u64::MAX;
+
+pub(crate) fn write_header<W: Write>(w: &mut W, magic: &[u8; 4]) -> io::Result<()> {
+    w.write_all(magic)?;
+    w.write_all(&[BINARY_VERSION])
+}
+
+/// Reads header, returns the format version
+pub(crate) fn read_header<R: Read>(r: &mut R, magic: &[u8; 4]) -> Result<u8> {
+    let mut magic_read = [0u8; 4];
+    r.read_exact(&mut magic_read)?;
+    if &magic_read != magic {
+        return Err(anyhow::Error::msg("Wrong binary file header"));
+    }
+    let mut version = [0u8; 1];
+    r.read_exact(&mut version)?;
+    if version[0] == 0 || version[0] > BINARY_VERSION {
+        return Err(anyhow::Error::msg(format!(
+            "Unsupported binary format version {} (expected at most {})",
+            version[0], BINARY_VERSION
+        )));
+    }
+    Ok(version[0])
+}
+
+pub(crate) fn write_u64<W: Write>(w: &mut W, val: u64) -> io::Result<()> {
+    w.write_all(&val.to_le_bytes())
+}
+
+pub(crate) fn write_f64<W: Write>(w: &mut W, val: f64) -> io::Result<()> {
+    w.write_all(&val.to_le_bytes())
+}
+
+pub(crate) fn read_u64<R: Read>(r: &mut R) -> io::Result<u64> {
+    let mut buf = [0u8; 8];
+    r.read_exact(&mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
+
+pub(crate) fn read_f64<R: Read>(r: &mut R) -> io::Result<f64> {
+    let mut buf = [0u8; 8];
+    r.read_exact(&mut buf)?;
+    Ok(f64::from_le_bytes(buf))
+}
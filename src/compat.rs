@@ -0,0 +1,105 @@
+//! Helpers abstracting over std and alloc only builds
+
+#[cfg(feature = "std")]
+pub(crate) use std::collections::{HashMap, HashSet};
+
+#[cfg(not(feature = "std"))]
+pub(crate) use alloc::collections::{BTreeMap as HashMap, BTreeSet as HashSet};
+
+/// Square root, from libm without std
+#[inline]
+pub(crate) fn sqrt(val: f64) -> f64 {
+    #[cfg(feature = "std")]
+    {
+        val.sqrt()
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        libm::sqrt(val)
+    }
+}
+
+/// Arc cosine, from libm without std
+#[inline]
+pub(crate) fn acos(val: f64) -> f64 {
+    #[cfg(feature = "std")]
+    {
+        val.acos()
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        libm::acos(val)
+    }
+}
+
+/// Sine and cosine, from libm without std
+#[inline]
+pub(crate) fn sin_cos(val: f64) -> (f64, f64) {
+    #[cfg(feature = "std")]
+    {
+        val.sin_cos()
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        libm::sincos(val)
+    }
+}
+
+/// Elapsed time measurement, always zero without std
+pub(crate) struct Timer {
+    #[cfg(feature = "std")]
+    start: std::time::Instant,
+}
+
+impl Timer {
+    /// Starts measurement
+    pub(crate) fn start() -> Timer {
+        Timer {
+            #[cfg(feature = "std")]
+            start: std::time::Instant::now(),
+        }
+    }
+
+    /// Gets elapsed time since start, in nanoseconds
+    pub(crate) fn elapsed_ns(&self) -> u128 {
+        #[cfg(feature = "std")]
+        {
+            self.start.elapsed().as_nanos()
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            0
+        }
+    }
+}
+
+/// Elapsed time measurement of fine grained insertion steps, always zero without the timing
+/// feature
+pub(crate) struct StepTimer {
+    #[cfg(feature = "timing")]
+    start: std::time::Instant,
+}
+
+impl StepTimer {
+    /// Starts measurement
+    #[inline]
+    pub(crate) fn start() -> StepTimer {
+        StepTimer {
+            #[cfg(feature = "timing")]
+            start: std::time::Instant::now(),
+        }
+    }
+
+    /// Gets elapsed time since start, in nanoseconds
+    #[inline]
+    pub(crate) fn elapsed_ns(&self) -> u128 {
+        #[cfg(feature = "timing")]
+        {
+            self.start.elapsed().as_nanos()
+        }
+        #[cfg(not(feature = "timing"))]
+        {
+            0
+        }
+    }
+}
@@ -1,10 +1,34 @@
+use alloc::collections::{BTreeSet, BinaryHeap, VecDeque};
+use alloc::vec;
+use alloc::vec::Vec;
 use anyhow::Result;
+use core::cmp::Reverse;
 use log;
 use robust::{self, Coord};
-use std::time::Instant;
+#[cfg(feature = "std")]
+use std::io::{self, BufRead, Read, Write};
 
-use super::geometry_operations_2d::{build_hilbert_curve, is_convex};
+#[cfg(feature = "std")]
+use crate::binary_io::{
+    read_f64, read_header, read_u64, write_f64, write_header, write_u64, BINARY_INFINITY,
+};
+use crate::compat::{sqrt, HashMap, HashSet, StepTimer, Timer};
+use crate::error::{check_index, DegenerateInput, PointOutOfBounds, WalkFailed};
+#[cfg(feature = "std")]
+use crate::text_io::{for_each_point, PointFormat};
+
+use crate::delaunay_3d::geometry_operations_3d::orient_3d;
+
+use super::geometry_operations_2d::{
+    barycentric_coordinates, build_hilbert_curve, circle_center_with_tolerance, in_circle,
+    in_circle_fast, in_circle_int, is_convex, is_convex_int, is_in_diametral_circle, is_in_polygon,
+    is_triangle_flat, line_normal_and_factor, minimal_enclosing_circle, orient_2d, orient_2d_int,
+    triangle_angles, triangle_quality, triangulate_polygon, INT_COORD_LIMIT,
+};
+use super::point_2d::Point2;
 use super::simplicial_struct_2d::{self, Node, SimplicialStructure2D};
+use super::spatial_hint_2d::SpatialHint;
+use super::voronoi_2d::{find_root, VoronoiDiagram};
 
 /// Extended triangle, including point at infinity
 pub enum ExtendedTriangle {
@@ -14,25 +38,380 @@ pub enum ExtendedTriangle {
     Segment([[f64; 2]; 2]),
 }
 
-/// 2D Delaunay structure
-pub struct DelaunayStructure2D {
+/// Extended circumcircle, including circle through a point at infinity
+#[derive(Debug, Clone, Copy)]
+pub enum ExtendedCircle {
+    /// Circumcircle of a regular triangle
+    Circle {
+        /// Circle center
+        center: [f64; 2],
+        /// Circle radius
+        radius: f64,
+    },
+    /// Line of a triangle with a point at infinity, points pt of the line verify
+    /// normal . pt = factor
+    Line {
+        /// Unit normal, pointing outside the convex hull
+        normal: [f64; 2],
+        /// Line offset along the normal
+        factor: f64,
+    },
+}
+
+/// In-circle predicate used by insertions and validity checks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PredicateMode {
+    /// Plain floating point determinant, may fail on degenerate or nearly cocircular inputs
+    Fast,
+    /// Adaptive exact predicate of the robust crate
+    #[default]
+    Robust,
+}
+
+/// Handling of an inserted vertex coinciding with a vertex of the triangulation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DuplicatePolicy {
+    /// Vertex left out of the triangulation, with a warning
+    #[default]
+    Skip,
+    /// Vertex mapped to the coinciding one (see [`DelaunayStructure2D::merged_vertex`])
+    Merge,
+}
+
+/// Location of an inserted vertex relative to the triangle found by the walk
+enum PointLocation {
+    /// Coincides with a vertex
+    Vertex(usize),
+    /// Lies on a halfedge
+    Edge(usize),
+    /// Lies strictly inside
+    Inside,
+}
+
+/// Location of a vertex inserted by the last single insertions, to undo them
+#[derive(Clone)]
+enum InsertedLocation {
+    /// Coinciding with an existing vertex, not inserted
+    Skipped,
+    /// Within a triangle
+    Inside,
+    /// On the edge between two nodes, splitting a constraint or not
+    Edge([Node; 2], bool),
+}
+
+/// Single insertion as recorded in the undo log
+#[derive(Clone)]
+struct UndoStep {
+    ind_vertex: usize,
+    location: InsertedLocation,
+    // flipped edges, as nodes of the new diagonals
+    flips: Vec<[Node; 2]>,
+    integer_coordinates: bool,
+    // simplicial generation after the insertion
+    generation: u64,
+}
+
+// integer coordinates within the range of integer predicates
+fn is_int_coordinate(vert: [f64; 2]) -> bool {
+    vert.iter()
+        .all(|&val| val.abs() <= INT_COORD_LIMIT as f64 && val as i32 as f64 == val)
+}
+
+fn to_int(vert: [f64; 2]) -> [i32; 2] {
+    [vert[0] as i32, vert[1] as i32]
+}
+
+// halfedge of the finite triangle across he, if not in a strip yet
+fn next_in_strip<'a>(
+    he: simplicial_struct_2d::IterHalfEdge<'a>,
+    in_strip: &[bool],
+) -> Option<simplicial_struct_2d::IterHalfEdge<'a>> {
+    let he_opp = he.opposite_halfedge();
+    let tri_opp = he_opp.triangle();
+    if tri_opp.contains_infinity() || in_strip[tri_opp.ind()] {
+        None
+    } else {
+        Some(he_opp)
+    }
+}
+
+// corner of a polygon clipped by a triangle
+#[derive(Copy, Clone)]
+enum ClipCorner {
+    // polygon vertex
+    Polygon(usize),
+    // triangle vertex
+    Triangle(usize),
+    // crossing of a triangle edge and a polygon edge
+    Crossing(usize, usize),
+}
+
+// edge carrying a clipped polygon side: polygon edge (false) or triangle edge (true), with index
+type ClipCarrier = (bool, usize);
+
+// clips a polygon by a counterclockwise triangle (Sutherland-Hodgman algorithm), tracking where
+// each corner comes from
+fn clip_polygon_by_triangle_tracked(
+    polygon: &[[f64; 2]],
+    triangle: &[[f64; 2]; 3],
+) -> Vec<([f64; 2], ClipCorner)> {
+    let mut clipped: Vec<([f64; 2], ClipCorner, ClipCarrier)> = polygon
+        .iter()
+        .enumerate()
+        .map(|(j, &pt)| (pt, ClipCorner::Polygon(j), (false, j)))
+        .collect();
+    for i in 0..3 {
+        let edg0 = triangle[i];
+        let edg1 = triangle[(i + 1) % 3];
+        let to_clip = clipped;
+        clipped = Vec::new();
+        let nb_pts = to_clip.len();
+        for j in 0..nb_pts {
+            let (pt0, corner0, carrier0) = to_clip[j];
+            let (pt1, _, _) = to_clip[(j + 1) % nb_pts];
+            let side0 = orient_2d(edg0, edg1, pt0);
+            let side1 = orient_2d(edg0, edg1, pt1);
+            let crossing = || {
+                let t = side0 / (side0 - side1);
+                let pt = [
+                    pt0[0] + t * (pt1[0] - pt0[0]),
+                    pt0[1] + t * (pt1[1] - pt0[1]),
+                ];
+                let corner = match carrier0 {
+                    (false, ind_edge) => ClipCorner::Crossing(i, ind_edge),
+                    // two triangle edges meet at their shared vertex
+                    (true, k) if k == (i + 1) % 3 => ClipCorner::Triangle(k),
+                    (true, _) => ClipCorner::Triangle(i),
+                };
+                (pt, corner)
+            };
+            if side0 >= 0. {
+                if side1 < 0. {
+                    // leaving the half plane, next side lies on the triangle edge
+                    if side0 == 0. {
+                        clipped.push((pt0, corner0, (true, i)));
+                    } else {
+                        clipped.push((pt0, corner0, carrier0));
+                        let (pt, corner) = crossing();
+                        clipped.push((pt, corner, (true, i)));
+                    }
+                } else {
+                    clipped.push((pt0, corner0, carrier0));
+                }
+            } else if side1 > 0. {
+                // entering the half plane
+                let (pt, corner) = crossing();
+                clipped.push((pt, corner, carrier0));
+            }
+        }
+    }
+    clipped
+        .into_iter()
+        .map(|(pt, corner, _)| (pt, corner))
+        .collect()
+}
+
+#[cfg(feature = "std")]
+const BINARY_MAGIC_2D: &[u8; 4] = b"SDL2";
+
+// safe triangles of a tile as global indices, safe flags of tile triangles, and frontier points
+#[cfg(feature = "rayon")]
+type TileClassification = (Vec<[usize; 3]>, Vec<bool>, Vec<usize>);
+
+// safe triangles of a stitched side as global indices, safe flags of its triangles, and frontier
+// flags of its vertices
+type StitchClassification = (Vec<[usize; 3]>, Vec<bool>, Vec<bool>);
+
+/// Vertices and triangles as vertex indices
+pub type IndexedMesh2D = (Vec<[f64; 2]>, Vec<[usize; 3]>);
+
+/// Clipped triangles as vertex indices, with the vertices created by splitting
+pub type ClippedTriangles = (Vec<[usize; 3]>, Vec<[f64; 2]>);
+
+#[derive(Clone)]
+/// 2D Delaunay structure, optionally storing an attribute of type A per triangle
+pub struct DelaunayStructure2D<A = ()> {
     simpl_struct: simplicial_struct_2d::SimplicialStructure2D,
     vertex_coordinates: Vec<[f64; 2]>,
-    walk_ms: u128,
-    insert_ms: u128,
-    flip_ms: u128,
+    constraints: BTreeSet<[usize; 2]>,
+
+    // halfedges stack reused between insertions
+    he_to_evaluate: Vec<usize>,
+
+    // relative tolerance of circumcenter computations
+    flat_tolerance: f64,
+
+    // in-circle predicate for non integer coordinates
+    predicate_mode: PredicateMode,
+
+    // handling of coinciding vertices, and vertices merged into the coinciding ones
+    duplicate_policy: DuplicatePolicy,
+    merged_vertices: HashMap<usize, usize>,
+
+    // all vertices have integer coordinates, predicates computed in integer arithmetic
+    integer_coordinates: bool,
+
+    // flips performed by insertions, in total and for a single insertion
+    total_flips: usize,
+    max_flips_single_insert: usize,
+
+    // optional grid of walk starting points for single insertions
+    spatial_hint: Option<SpatialHint>,
+
+    // optional box (min and max corners) containing accepted points
+    bounds: Option<([f64; 2], [f64; 2])>,
+
+    // vertices inserted by the last bulk insertion, in insertion order
+    insertion_order: Vec<usize>,
+
+    // vertices that cannot be moved or removed, missing flags being false
+    fixed: Vec<bool>,
+
+    // triangle attributes, indexed by simplicial triangle labels
+    attributes: Vec<A>,
+
+    // last single insertions, and flips recorded during the current one
+    undo_log: VecDeque<UndoStep>,
+    undo_depth: usize,
+    recorded_flips: Option<Vec<[Node; 2]>>,
+
+    nb_walk_fallbacks: usize,
+    walk_ns: u128,
+    insert_ns: u128,
+    flip_ns: u128,
+}
+
+impl Default for DelaunayStructure2D {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl DelaunayStructure2D {
     /// Initialize Delaunay structure
     pub fn new() -> DelaunayStructure2D {
+        Self::new_with_attributes()
+    }
+
+    /// Initialize Delaunay structure accepting only points within a box (min and max corners)
+    ///
+    /// Insertions fail with [`PointOutOfBounds`] if a point lies outside, before inserting
+    /// anything.
+    ///
+    /// [`PointOutOfBounds`]: crate::error::PointOutOfBounds
+    pub fn with_bounds(pt_min: [f64; 2], pt_max: [f64; 2]) -> DelaunayStructure2D {
+        let mut del_struct = Self::new();
+        del_struct.bounds = Some((pt_min, pt_max));
+        del_struct
+    }
+}
+
+impl<A> DelaunayStructure2D<A> {
+    /// Initialize Delaunay structure storing triangle attributes
+    ///
+    /// Triangles created by an insertion or a flip inherit the attribute of the triangle they
+    /// replace, attributes follow triangles moved by removals and reorderings.
+    pub fn new_with_attributes() -> DelaunayStructure2D<A> {
         DelaunayStructure2D {
             simpl_struct: simplicial_struct_2d::SimplicialStructure2D::new(),
             vertex_coordinates: Vec::new(),
-            walk_ms: 0,
-            insert_ms: 0,
-            flip_ms: 0,
+            constraints: BTreeSet::new(),
+            he_to_evaluate: Vec::new(),
+            flat_tolerance: 0.,
+            predicate_mode: PredicateMode::Robust,
+            duplicate_policy: DuplicatePolicy::Skip,
+            merged_vertices: HashMap::new(),
+            integer_coordinates: false,
+            total_flips: 0,
+            max_flips_single_insert: 0,
+            spatial_hint: None,
+            bounds: None,
+            undo_log: VecDeque::new(),
+            undo_depth: 0,
+            recorded_flips: None,
+            insertion_order: Vec::new(),
+            fixed: Vec::new(),
+            attributes: Vec::new(),
+            nb_walk_fallbacks: 0,
+            walk_ns: 0,
+            insert_ns: 0,
+            flip_ns: 0,
+        }
+    }
+
+    /// Sets attribute of a triangle
+    ///
+    /// The attribute is overwritten if no other triangle inherited it, attributes left without
+    /// triangle being reused.
+    pub fn set_attr(&mut self, ind_triangle: usize, attr: A) -> Result<()> {
+        check_index(ind_triangle, self.simpl_struct.get_nb_triangles())?;
+        if let Some(label) = self.simpl_struct.get_label(ind_triangle) {
+            if !self.simpl_struct.is_label_shared(label) {
+                self.attributes[label] = attr;
+                return Ok(());
+            }
         }
+        if let Some(label) = self.simpl_struct.take_unused_label() {
+            self.simpl_struct.set_label(ind_triangle, label)?;
+            self.attributes[label] = attr;
+        } else {
+            self.simpl_struct
+                .set_label(ind_triangle, self.attributes.len())?;
+            self.attributes.push(attr);
+        }
+        Ok(())
+    }
+
+    /// Gets attribute of a triangle, None if not set
+    pub fn get_attr(&self, ind_triangle: usize) -> Option<&A> {
+        self.simpl_struct
+            .get_label(ind_triangle)
+            .and_then(|label| self.attributes.get(label))
+    }
+
+    // checks points against bounds, if any
+    fn check_bounds<P: Point2>(&self, pts: &[P]) -> Result<()> {
+        if let Some((pt_min, pt_max)) = self.bounds {
+            let inside = |[x, y]: [f64; 2]| {
+                x >= pt_min[0] && x <= pt_max[0] && y >= pt_min[1] && y <= pt_max[1]
+            };
+            if let Some(index) = pts.iter().position(|pt| !inside(pt.xy())) {
+                return Err(anyhow::Error::msg(PointOutOfBounds { index }));
+            }
+        }
+        Ok(())
+    }
+
+    // fails with DegenerateInput if nothing is triangulated yet and all points coincide
+    // (bounding box extent within rounding error)
+    fn check_extent<P: Point2>(&self, pts: &[P]) -> Result<()> {
+        if self.simpl_struct.get_nb_triangles() != 0 {
+            return Ok(());
+        }
+        let mut all_pts = self
+            .vertex_coordinates
+            .iter()
+            .copied()
+            .chain(pts.iter().map(|pt| pt.xy()));
+        let Some(first) = all_pts.next() else {
+            return Ok(());
+        };
+        let (mut pt_min, mut pt_max) = (first, first);
+        for pt in all_pts {
+            pt_min = [pt_min[0].min(pt[0]), pt_min[1].min(pt[1])];
+            pt_max = [pt_max[0].max(pt[0]), pt_max[1].max(pt[1])];
+        }
+        let scale = pt_min
+            .iter()
+            .chain(pt_max.iter())
+            .fold(1f64, |acc, val| acc.max(val.abs()));
+        let eps = f64::EPSILON * scale;
+        if pt_max[0] - pt_min[0] <= eps && pt_max[1] - pt_min[1] <= eps {
+            return Err(anyhow::Error::msg(DegenerateInput {
+                nb_points: self.vertex_coordinates.len() + pts.len(),
+            }));
+        }
+        Ok(())
     }
 
     /// Gets simplicial structure
@@ -40,12 +419,270 @@ impl DelaunayStructure2D {
         &self.simpl_struct
     }
 
+    /// Gets mutable simplicial structure, to corrupt it in tests
+    #[cfg(feature = "testgen")]
+    pub fn get_simplicial_mut(&mut self) -> &mut SimplicialStructure2D {
+        &mut self.simpl_struct
+    }
+
     /// Gets graph vertices
     pub fn get_vertices(&self) -> &Vec<[f64; 2]> {
         &self.vertex_coordinates
     }
 
+    /// Gets vertex coordinates, failing with [`IndexOutOfBounds`] on a wrong index
+    ///
+    /// [`IndexOutOfBounds`]: crate::error::IndexOutOfBounds
+    #[must_use = "query result should be used"]
+    pub fn get_vertex(&self, ind_vertex: usize) -> Result<[f64; 2]> {
+        check_index(ind_vertex, self.vertex_coordinates.len())?;
+        Ok(self.vertex_coordinates[ind_vertex])
+    }
+
+    /// Gets graph vertices as nalgebra vectors
+    #[cfg(feature = "nalgebra")]
+    pub fn get_vertices_na(&self) -> Vec<nalgebra::Vector2<f64>> {
+        self.vertex_coordinates
+            .iter()
+            .map(|vert| nalgebra::Vector2::new(vert[0], vert[1]))
+            .collect()
+    }
+
+    /// Sets relative flatness tolerance (0 by default, exact test)
+    ///
+    /// Triangles whose area is at most tolerance times their squared longest edge length are
+    /// considered flat, and have no circumcircle.
+    pub fn set_flat_tolerance(&mut self, rel_eps: f64) {
+        self.flat_tolerance = rel_eps;
+    }
+
+    /// Sets in-circle predicate ([`PredicateMode::Robust`] by default)
+    ///
+    /// [`PredicateMode::Fast`] suits well separated, noise free data: on degenerate inputs it may
+    /// produce a non Delaunay or invalid triangulation. Integer coordinates always use exact
+    /// integer predicates.
+    pub fn set_predicate_mode(&mut self, mode: PredicateMode) {
+        self.predicate_mode = mode;
+    }
+
+    /// Sets handling of inserted vertices coinciding with a vertex of the triangulation
+    /// ([`DuplicatePolicy::Skip`] by default)
+    pub fn set_duplicate_policy(&mut self, policy: DuplicatePolicy) {
+        self.duplicate_policy = policy;
+    }
+
+    /// Gets vertex of the triangulation a coinciding vertex has been merged into, None if the
+    /// vertex has not been merged (see [`DuplicatePolicy::Merge`])
+    pub fn merged_vertex(&self, ind_vertex: usize) -> Option<usize> {
+        self.merged_vertices.get(&ind_vertex).copied()
+    }
+
+    /// Sets number of single insertions ([`Self::insert_vertex`]) that can be undone (0 by
+    /// default, no undo log)
+    pub fn set_undo_depth(&mut self, depth: usize) {
+        self.undo_depth = depth;
+        while self.undo_log.len() > depth {
+            self.undo_log.pop_front();
+        }
+    }
+
+    /// Enables spatial hint with resolution cells per side, or disables it with None
+    ///
+    /// The hint is a coarse grid over the bounding box of inserted vertices, storing a triangle
+    /// near each cell. It is updated on insertion, and gives the walk starting point of
+    /// [`Self::insert_vertex`] when no triangle is provided, instead of the last triangle.
+    pub fn set_spatial_hint(&mut self, resolution: Option<usize>) -> Result<()> {
+        let Some(resolution) = resolution else {
+            self.spatial_hint = None;
+            return Ok(());
+        };
+        if resolution == 0 {
+            return Err(anyhow::Error::msg(
+                "Spatial hint resolution should be positive",
+            ));
+        }
+        let located: Vec<([f64; 2], usize)> = (0..self.vertex_coordinates.len())
+            .filter_map(|ind_vertex| {
+                let he = self.get_simplicial().get_node_halfedge(ind_vertex)?;
+                Some((self.vertex_coordinates[ind_vertex], he.triangle().ind()))
+            })
+            .collect();
+        if located.is_empty() {
+            return Err(anyhow::Error::msg(
+                "Needs inserted vertices to build spatial hint",
+            ));
+        }
+        let mut pt_min = located[0].0;
+        let mut pt_max = located[0].0;
+        for &(pt, _) in located.iter() {
+            pt_min = [pt_min[0].min(pt[0]), pt_min[1].min(pt[1])];
+            pt_max = [pt_max[0].max(pt[0]), pt_max[1].max(pt[1])];
+        }
+        self.spatial_hint = Some(SpatialHint::new((pt_min, pt_max), resolution, &located));
+        Ok(())
+    }
+
+    /// Permutes triangle storage along the Hilbert curve of triangle centroids, for cache
+    /// friendly iteration
+    ///
+    /// Infinite triangles are placed according to the midpoint of their finite edge. Permutation
+    /// is filled with the old index of each new triangle index, to remap per-triangle data.
+    pub fn spatial_reorder(&mut self, permutation: &mut Vec<usize>) -> Result<()> {
+        permutation.clear();
+        let nb_triangles = self.simpl_struct.get_nb_triangles();
+        if nb_triangles == 0 {
+            return Ok(());
+        }
+        let mut centers = Vec::with_capacity(nb_triangles);
+        for ind_triangle in 0..nb_triangles {
+            let center = match self.get_extended_triangle(ind_triangle)? {
+                ExtendedTriangle::Triangle([pt0, pt1, pt2]) => [
+                    (pt0[0] + pt1[0] + pt2[0]) / 3.,
+                    (pt0[1] + pt1[1] + pt2[1]) / 3.,
+                ],
+                ExtendedTriangle::Segment([pt0, pt1]) => {
+                    [(pt0[0] + pt1[0]) * 0.5, (pt0[1] + pt1[1]) * 0.5]
+                }
+            };
+            centers.push(center);
+        }
+        let indices: Vec<usize> = (0..nb_triangles).collect();
+        permutation.extend(build_hilbert_curve(&centers, &indices));
+        if permutation.len() != nb_triangles {
+            permutation.clear();
+            return Err(anyhow::Error::msg("Could not order triangles"));
+        }
+        self.simpl_struct.permute_triangles(permutation)?;
+
+        if let Some(hint) = self.spatial_hint.as_mut() {
+            let mut old_to_new = vec![0; nb_triangles];
+            for (ind_new, &ind_old) in permutation.iter().enumerate() {
+                old_to_new[ind_old] = ind_new;
+            }
+            hint.remap_triangles(&old_to_new);
+        }
+        Ok(())
+    }
+
+    /// Number of flips performed by all insertions
+    pub fn get_total_flips(&self) -> usize {
+        self.total_flips
+    }
+
+    /// Gets vertices inserted by the last bulk insertion, in the order they were inserted
+    ///
+    /// Inserting them one by one with [`Self::insert_vertex`] in this order replays the
+    /// insertion. Empty after a tiled parallel insertion.
+    pub fn last_insertion_order(&self) -> &[usize] {
+        &self.insertion_order
+    }
+
+    /// Number of walks which required an exhaustive search
+    pub fn get_nb_walk_fallbacks(&self) -> usize {
+        self.nb_walk_fallbacks
+    }
+
+    /// Largest number of flips performed by a single insertion
+    pub fn get_max_flips_single_insert(&self) -> usize {
+        self.max_flips_single_insert
+    }
+
+    /// Checks if a triangle is flat up to the flatness tolerance (false for infinite triangles)
+    #[must_use = "query result should be used"]
+    pub fn is_triangle_nearly_flat(&self, ind_triangle: usize) -> Result<bool> {
+        let flat = match self.get_extended_triangle(ind_triangle)? {
+            ExtendedTriangle::Triangle([pt0, pt1, pt2]) => {
+                is_triangle_flat(pt0, pt1, pt2, self.flat_tolerance)
+            }
+            ExtendedTriangle::Segment(_) => false,
+        };
+        Ok(flat)
+    }
+
+    /// Gets finite triangles which are flat up to the flatness tolerance
+    #[must_use = "query result should be used"]
+    pub fn degenerate_triangles(&self) -> Result<Vec<usize>> {
+        let mut degenerate = Vec::new();
+        for ind_tri in 0..self.get_simplicial().get_nb_triangles() {
+            if self.is_triangle_nearly_flat(ind_tri)? {
+                degenerate.push(ind_tri);
+            }
+        }
+        Ok(degenerate)
+    }
+
+    /// Gets radius ratio quality of a finite triangle, 1 for an equilateral triangle and 0 for a
+    /// flat one
+    #[must_use = "query result should be used"]
+    pub fn triangle_quality(&self, ind_triangle: usize) -> Result<f64> {
+        let [pt0, pt1, pt2] = self
+            .triangle_coords(ind_triangle)?
+            .ok_or(anyhow::Error::msg("Triangle contains infinity"))?;
+        Ok(triangle_quality(pt0, pt1, pt2))
+    }
+
+    /// Gets interior angles of a finite triangle in radians, in the order of its vertices
+    #[must_use = "query result should be used"]
+    pub fn triangle_angles(&self, ind_triangle: usize) -> Result<[f64; 3]> {
+        let [pt0, pt1, pt2] = self
+            .triangle_coords(ind_triangle)?
+            .ok_or(anyhow::Error::msg("Triangle contains infinity"))?;
+        Ok(triangle_angles(pt0, pt1, pt2))
+    }
+
+    /// Gets minimal and maximal interior angles over finite triangles, in radians
+    #[must_use = "query result should be used"]
+    pub fn angle_bounds(&self) -> Result<(f64, f64)> {
+        let (mut min, mut max, mut nb) = (f64::INFINITY, f64::NEG_INFINITY, 0);
+        self.for_each_finite_triangle(|[ind0, ind1, ind2]| {
+            let vertices = self.get_vertices();
+            for angle in triangle_angles(vertices[ind0], vertices[ind1], vertices[ind2]) {
+                min = min.min(angle);
+                max = max.max(angle);
+            }
+            nb += 1;
+        });
+        if nb == 0 {
+            return Err(anyhow::Error::msg("No finite triangle"));
+        }
+        Ok((min, max))
+    }
+
+    /// Gets minimal quality over finite triangles
+    #[must_use = "query result should be used"]
+    pub fn min_quality(&self) -> Result<f64> {
+        let (min, _, nb) = self.quality_stats();
+        if nb == 0 {
+            return Err(anyhow::Error::msg("No finite triangle"));
+        }
+        Ok(min)
+    }
+
+    /// Gets mean quality over finite triangles
+    #[must_use = "query result should be used"]
+    pub fn mean_quality(&self) -> Result<f64> {
+        let (_, sum, nb) = self.quality_stats();
+        if nb == 0 {
+            return Err(anyhow::Error::msg("No finite triangle"));
+        }
+        Ok(sum / nb as f64)
+    }
+
+    // minimum, sum and number of finite triangle qualities
+    fn quality_stats(&self) -> (f64, f64, usize) {
+        let (mut min, mut sum, mut nb) = (f64::INFINITY, 0., 0);
+        self.for_each_finite_triangle(|[ind0, ind1, ind2]| {
+            let vertices = self.get_vertices();
+            let quality = triangle_quality(vertices[ind0], vertices[ind1], vertices[ind2]);
+            min = min.min(quality);
+            sum += quality;
+            nb += 1;
+        });
+        (min, sum, nb)
+    }
+
     /// Gets extended triangle from index
+    #[must_use = "query result should be used"]
     pub fn get_extended_triangle(&self, ind_triangle: usize) -> Result<ExtendedTriangle> {
         let [node1, node2, node3] = self.get_simplicial().get_triangle(ind_triangle)?.nodes();
 
@@ -95,153 +732,1417 @@ impl DelaunayStructure2D {
         Ok(ext_tri)
     }
 
-    fn is_vertex_strict_in_circle(&self, ind_vert: usize, ind_tri: usize) -> Result<bool> {
-        let vert = self.get_vertices()[ind_vert];
-        let ext_tri = self.get_extended_triangle(ind_tri)?;
-
-        let sign = match ext_tri {
-            ExtendedTriangle::Triangle(tri) => robust::incircle(
-                Coord {
-                    x: tri[0][0],
-                    y: tri[0][1],
-                },
-                Coord {
-                    x: tri[1][0],
-                    y: tri[1][1],
-                },
-                Coord {
-                    x: tri[2][0],
-                    y: tri[2][1],
-                },
-                Coord {
-                    x: vert[0],
-                    y: vert[1],
-                },
-            ),
-            ExtendedTriangle::Segment(lin) => robust::orient2d(
-                Coord {
-                    x: lin[0][0],
-                    y: lin[0][1],
-                },
-                Coord {
-                    x: lin[1][0],
-                    y: lin[1][1],
-                },
-                Coord {
-                    x: vert[0],
-                    y: vert[1],
-                },
-            ),
-        };
-        Ok(sign > 0.)
+    /// Gets circumcircle of a triangle from index
+    #[must_use = "query result should be used"]
+    pub fn get_circumcircle(&self, ind_triangle: usize) -> Result<ExtendedCircle> {
+        match self.get_extended_triangle(ind_triangle)? {
+            ExtendedTriangle::Triangle([pt0, pt1, pt2]) => {
+                let center = circle_center_with_tolerance(pt0, pt1, pt2, self.flat_tolerance)
+                    .ok_or_else(|| anyhow::Error::msg("Flat triangle has no circumcircle"))?;
+                let radius = sqrt(
+                    (pt0[0] - center[0]) * (pt0[0] - center[0])
+                        + (pt0[1] - center[1]) * (pt0[1] - center[1]),
+                );
+                Ok(ExtendedCircle::Circle { center, radius })
+            }
+            ExtendedTriangle::Segment([pt0, pt1]) => {
+                // hull edge with interior on the right, outside on the left
+                let (normal, factor) = line_normal_and_factor(pt0, pt1)
+                    .ok_or(anyhow::Error::msg("Degenerate hull edge"))?;
+                Ok(ExtendedCircle::Line { normal, factor })
+            }
+        }
     }
 
-    fn is_triangle_flat(&self, ind_tri: usize) -> Result<bool> {
-        let ext_tri = self.get_extended_triangle(ind_tri)?;
-
-        let flat = if let ExtendedTriangle::Triangle(tri) = ext_tri {
-            let sign = robust::orient2d(
-                Coord {
-                    x: tri[0][0],
-                    y: tri[0][1],
-                },
-                Coord {
-                    x: tri[1][0],
-                    y: tri[1][1],
-                },
-                Coord {
-                    x: tri[2][0],
-                    y: tri[2][1],
-                },
-            );
-            sign == 0.
+    /// Gets triangle coordinates from index, None if the triangle contains infinity
+    #[must_use = "query result should be used"]
+    pub fn triangle_coords(&self, ind_triangle: usize) -> Result<Option<[[f64; 2]; 3]>> {
+        if let ExtendedTriangle::Triangle(tri) = self.get_extended_triangle(ind_triangle)? {
+            Ok(Some(tri))
         } else {
-            false
-        };
-        Ok(flat)
+            Ok(None)
+        }
     }
 
-    fn choose_he<'a>(
-        &self,
-        vec_edg: &Vec<simplicial_struct_2d::IterHalfEdge<'a>>,
-        vert: &[f64; 2],
-    ) -> Option<simplicial_struct_2d::IterHalfEdge<'a>> {
-        for &he in vec_edg {
-            let ind1 = he.first_node();
-            let ind2 = he.last_node();
-            if let (Node::Value(v1), Node::Value(v2)) = (ind1, ind2) {
-                let pt1 = self.get_vertices()[v1];
-                let pt2 = self.get_vertices()[v2];
-                let sign = robust::orient2d(
-                    Coord {
-                        x: pt1[0],
-                        y: pt1[1],
-                    },
-                    Coord {
-                        x: pt2[0],
-                        y: pt2[1],
-                    },
-                    Coord {
-                        x: vert[0],
-                        y: vert[1],
-                    },
-                );
-                if he.triangle().contains_infinity() {
-                    if sign <= 0. {
-                        return Some(he);
-                    }
-                } else if sign < 0. {
-                    return Some(he);
-                }
-            }
+    /// Checks if both structures have the same vertex coordinates and the same set of finite
+    /// triangles, regardless of triangle storage order
+    #[must_use = "comparison result should be used"]
+    pub fn same_triangulation(&self, other: &Self) -> bool {
+        if self.get_vertices() != other.get_vertices() {
+            return false;
         }
-        None
+        let sorted_triangles = |del_struct: &Self| {
+            let mut triangles = Vec::new();
+            del_struct.for_each_finite_triangle(|mut tri| {
+                tri.sort_unstable();
+                triangles.push(tri);
+            });
+            triangles.sort_unstable();
+            triangles
+        };
+        sorted_triangles(self) == sorted_triangles(other)
     }
 
-    fn walk_by_visibility(&self, ind_vert: usize, ind_starting_triangle: usize) -> Result<usize> {
-        let vert = self.get_vertices()[ind_vert];
-        let mut ind_tri_cur = ind_starting_triangle;
-        let start_tri = self.get_simplicial().get_triangle(ind_tri_cur)?;
-        let mut vec_edg: Vec<simplicial_struct_2d::IterHalfEdge> =
-            start_tri.halfedges().iter().map(|&he| he).collect();
-        let mut side = false;
-        loop {
-            if let Some(he) = self.choose_he(&vec_edg, &vert) {
-                let he_opp = he.opposite_halfedge();
-                ind_tri_cur = he_opp.triangle().ind();
-                vec_edg.clear();
-                if side {
-                    vec_edg.push(he_opp.next_halfedge());
-                    vec_edg.push(he_opp.prev_halfedge());
+    /// Gets finite triangles as vertex indices
+    ///
+    /// Vertices of each triangle are guaranteed to be in counterclockwise order (positive
+    /// orientation), flat triangles being left as is.
+    #[must_use = "query result should be used"]
+    pub fn finite_triangles(&self) -> Result<Vec<[usize; 3]>> {
+        let mut triangles = Vec::new();
+        self.for_each_finite_triangle(|tri| triangles.push(tri));
+        Ok(triangles)
+    }
+
+    /// Calls f on each finite triangle (counterclockwise vertex indices), without collecting them
+    pub fn for_each_finite_triangle(&self, mut f: impl FnMut([usize; 3])) {
+        for ind_tri in 0..self.get_simplicial().get_nb_triangles() {
+            let Ok(tri) = self.get_simplicial().get_triangle(ind_tri) else {
+                continue;
+            };
+            if let [Node::Value(ind_v1), Node::Value(ind_v2), Node::Value(ind_v3)] = tri.nodes() {
+                let [pt1, pt2, pt3] =
+                    [ind_v1, ind_v2, ind_v3].map(|ind| self.vertex_coordinates[ind]);
+                if self.orient(pt1, pt2, pt3) < 0. {
+                    f([ind_v1, ind_v3, ind_v2]);
                 } else {
-                    vec_edg.push(he_opp.prev_halfedge());
-                    vec_edg.push(he_opp.next_halfedge());
+                    f([ind_v1, ind_v2, ind_v3]);
                 }
-                side = !side;
-            } else {
-                return Ok(ind_tri_cur);
             }
         }
     }
 
-    fn should_flip_halfedge(&self, ind_he: usize) -> Result<bool> {
-        let he = self.get_simplicial().get_halfedge(ind_he)?;
-        let ind_tri_abd = he.triangle().ind();
-        let node_a = he.prev_halfedge().first_node();
-        let node_b = he.first_node();
+    /// Exports vertices and finite triangles, triangles being counterclockwise (see
+    /// finite_triangles)
+    #[must_use = "query result should be used"]
+    pub fn to_indexed_mesh(&self) -> Result<IndexedMesh2D> {
+        Ok((self.vertex_coordinates.clone(), self.finite_triangles()?))
+    }
 
-        let ind_tri_bcd = he.opposite_halfedge().triangle().ind();
-        let node_c = he.opposite_halfedge().prev_halfedge().first_node();
-        let node_d = he.opposite_halfedge().first_node();
+    /// Groups finite triangles into strips of adjacent triangles, each run of vertex indices
+    /// describing counterclockwise triangles in the triangle strip convention
+    ///
+    /// Triangle i of a run has vertices i, i+1, i+2, the first two being swapped for odd i. Runs
+    /// are built greedily, and are not joined by degenerate triangles.
+    #[must_use = "query result should be used"]
+    pub fn to_triangle_strips(&self) -> Result<Vec<Vec<u32>>> {
+        let simpl = self.get_simplicial();
+        let nb_triangles = simpl.get_nb_triangles();
+        let to_u32 = |node: Node| -> Result<u32> {
+            match node {
+                Node::Value(ind_v) => u32::try_from(ind_v)
+                    .map_err(|_| anyhow::Error::msg("Vertex index does not fit in u32")),
+                Node::Infinity => Err(anyhow::Error::msg("Infinite vertex in strip")),
+            }
+        };
+        let mut in_strip = vec![false; nb_triangles];
+        let mut strips = Vec::new();
+        for ind_tri in 0..nb_triangles {
+            let tri = simpl.get_triangle(ind_tri)?;
+            let [Node::Value(ind_v1), Node::Value(ind_v2), Node::Value(ind_v3)] = tri.nodes()
+            else {
+                continue;
+            };
+            if in_strip[ind_tri] {
+                continue;
+            }
+            in_strip[ind_tri] = true;
 
-        match (node_a, node_b, node_c, node_d) {
-            (Node::Value(ind_node_a), Node::Value(_), Node::Value(ind_node_c), Node::Value(_)) => {
-                Ok(self.is_vertex_strict_in_circle(ind_node_c, ind_tri_abd)?
-                    || self.is_vertex_strict_in_circle(ind_node_a, ind_tri_bcd)?)
+            // starts with the edge toward a free neighbor as last strip edge
+            let halfedges = tri.halfedges();
+            let mut he = halfedges
+                .iter()
+                .copied()
+                .find(|&he| next_in_strip(he, &in_strip).is_some())
+                .unwrap_or(halfedges[0]);
+            let [pt1, pt2, pt3] = [ind_v1, ind_v2, ind_v3].map(|ind| self.vertex_coordinates[ind]);
+            let (node_x, node_y) = (he.first_node(), he.last_node());
+            let node_z = he.prev_halfedge().first_node();
+            let mut last = if self.orient(pt1, pt2, pt3) < 0. {
+                node_x
+            } else {
+                node_y
+            };
+            let mut strip = vec![to_u32(node_z)?];
+            if last.equals(&node_y) {
+                strip.extend([to_u32(node_x)?, to_u32(node_y)?]);
+            } else {
+                strip.extend([to_u32(node_y)?, to_u32(node_x)?]);
             }
-            (Node::Infinity, Node::Value(_), Node::Value(ind_node_c), Node::Value(_)) => {
-                Ok(self.is_vertex_strict_in_circle(ind_node_c, ind_tri_abd)?
-                    || self.is_triangle_flat(ind_tri_bcd)?)
+
+            while let Some(he_opp) = next_in_strip(he, &in_strip) {
+                in_strip[he_opp.triangle().ind()] = true;
+                let node_apex = he_opp.prev_halfedge().first_node();
+                strip.push(to_u32(node_apex)?);
+                // next shared edge, between the two last strip vertices
+                he = if he_opp.next_halfedge().first_node().equals(&last) {
+                    he_opp.next_halfedge()
+                } else {
+                    he_opp.prev_halfedge()
+                };
+                last = node_apex;
+            }
+            strips.push(strip);
+        }
+        Ok(strips)
+    }
+
+    /// Computes vertex normals of the height field (x, y, height), averaging the normals of the
+    /// finite triangles around each vertex weighted by their area
+    ///
+    /// Vertices outside the triangulation get a zero normal.
+    #[must_use = "query result should be used"]
+    pub fn vertex_normals_from_heights(&self, heights: &[f64]) -> Result<Vec<[f64; 3]>> {
+        if heights.len() != self.vertex_coordinates.len() {
+            return Err(anyhow::Error::msg(
+                "Number of heights differs from number of vertices",
+            ));
+        }
+        let mut normals = vec![[0.; 3]; heights.len()];
+        self.for_each_finite_triangle(|tri| {
+            let [pt0, pt1, pt2] = tri.map(|ind| {
+                let pt = self.vertex_coordinates[ind];
+                [pt[0], pt[1], heights[ind]]
+            });
+            let u = [pt1[0] - pt0[0], pt1[1] - pt0[1], pt1[2] - pt0[2]];
+            let v = [pt2[0] - pt0[0], pt2[1] - pt0[1], pt2[2] - pt0[2]];
+            // norm is twice the triangle area
+            let normal = [
+                u[1] * v[2] - u[2] * v[1],
+                u[2] * v[0] - u[0] * v[2],
+                u[0] * v[1] - u[1] * v[0],
+            ];
+            for ind in tri {
+                for k in 0..3 {
+                    normals[ind][k] += normal[k];
+                }
+            }
+        });
+        for normal in normals.iter_mut() {
+            let norm = sqrt(normal[0] * normal[0] + normal[1] * normal[1] + normal[2] * normal[2]);
+            if norm > 0. {
+                *normal = normal.map(|val| val / norm);
+            }
+        }
+        Ok(normals)
+    }
+
+    /// Iterates over finite edges, each edge appearing once with sorted vertex indices
+    pub fn finite_edges(&self) -> impl Iterator<Item = [usize; 2]> + '_ {
+        (0..3 * self.get_simplicial().get_nb_triangles()).filter_map(|ind_he| {
+            let he = self.get_simplicial().get_halfedge(ind_he).ok()?;
+            match (he.first_node(), he.last_node()) {
+                (Node::Value(ind_a), Node::Value(ind_b)) if ind_a < ind_b => Some([ind_a, ind_b]),
+                _ => None,
+            }
+        })
+    }
+
+    /// Builds undirected graph of finite edges weighted by their length, node weights being
+    /// vertex indices
+    ///
+    /// Only vertices of the triangulation get a node.
+    #[cfg(feature = "petgraph")]
+    pub fn to_petgraph(&self) -> petgraph::graph::UnGraph<usize, f64> {
+        let mut graph = petgraph::graph::UnGraph::new_undirected();
+        let nodes: Vec<Option<petgraph::graph::NodeIndex>> = (0..self.vertex_coordinates.len())
+            .map(|ind_vertex| {
+                self.get_simplicial()
+                    .get_node_halfedge(ind_vertex)
+                    .map(|_| graph.add_node(ind_vertex))
+            })
+            .collect();
+        for [ind_a, ind_b] in self.finite_edges() {
+            if let (Some(node_a), Some(node_b)) = (nodes[ind_a], nodes[ind_b]) {
+                let (pt_a, pt_b) = (
+                    self.vertex_coordinates[ind_a],
+                    self.vertex_coordinates[ind_b],
+                );
+                let length = sqrt(
+                    (pt_b[0] - pt_a[0]) * (pt_b[0] - pt_a[0])
+                        + (pt_b[1] - pt_a[1]) * (pt_b[1] - pt_a[1]),
+                );
+                graph.add_edge(node_a, node_b, length);
+            }
+        }
+        graph
+    }
+
+    /// Computes (min, mean, max) length of finite edges
+    #[must_use = "query result should be used"]
+    pub fn edge_length_stats(&self) -> Result<(f64, f64, f64)> {
+        let mut nb_edges = 0;
+        let (mut len_min, mut len_sum, mut len_max) = (f64::INFINITY, 0., 0_f64);
+        for edge in self.finite_edges() {
+            let len = self.edge_length(edge);
+            nb_edges += 1;
+            len_min = len_min.min(len);
+            len_sum += len;
+            len_max = len_max.max(len);
+        }
+        if nb_edges == 0 {
+            return Err(anyhow::Error::msg("No finite edge"));
+        }
+        Ok((len_min, len_sum / (nb_edges as f64), len_max))
+    }
+
+    /// Computes histogram of finite edge lengths, with bins evenly spread between min and max
+    /// lengths
+    #[must_use = "query result should be used"]
+    pub fn edge_length_histogram(&self, bins: usize) -> Result<Vec<usize>> {
+        if bins == 0 {
+            return Err(anyhow::Error::msg("Histogram needs at least one bin"));
+        }
+        let (len_min, _, len_max) = self.edge_length_stats()?;
+        let mut histogram = vec![0; bins];
+        for edge in self.finite_edges() {
+            let len = self.edge_length(edge);
+            let ind_bin = if len_max > len_min {
+                (((len - len_min) / (len_max - len_min) * (bins as f64)) as usize).min(bins - 1)
+            } else {
+                0
+            };
+            histogram[ind_bin] += 1;
+        }
+        Ok(histogram)
+    }
+
+    /// Computes connected components of the graph of finite edges shorter than max_len
+    ///
+    /// Returns the number of components and the component of each vertex, numbered by
+    /// increasing first vertex. Vertices outside the triangulation are isolated components.
+    #[must_use = "query result should be used"]
+    pub fn components_under_length(&self, max_len: f64) -> Result<(usize, Vec<usize>)> {
+        if max_len.is_nan() || max_len < 0. {
+            return Err(anyhow::Error::msg("Maximal length should be non negative"));
+        }
+        let nb_vertices = self.vertex_coordinates.len();
+        let mut parent: Vec<usize> = (0..nb_vertices).collect();
+        for edge in self.finite_edges() {
+            if self.edge_length(edge) < max_len {
+                let (root_a, root_b) = (
+                    find_root(&mut parent, edge[0]),
+                    find_root(&mut parent, edge[1]),
+                );
+                parent[root_a.max(root_b)] = root_a.min(root_b);
+            }
+        }
+
+        let mut nb_components = 0;
+        let mut component = vec![usize::MAX; nb_vertices];
+        for ind_vertex in 0..nb_vertices {
+            let root = find_root(&mut parent, ind_vertex);
+            if component[root] == usize::MAX {
+                component[root] = nb_components;
+                nb_components += 1;
+            }
+            component[ind_vertex] = component[root];
+        }
+        Ok((nb_components, component))
+    }
+
+    fn edge_length(&self, [ind_a, ind_b]: [usize; 2]) -> f64 {
+        let pt_a = self.vertex_coordinates[ind_a];
+        let pt_b = self.vertex_coordinates[ind_b];
+        sqrt((pt_b[0] - pt_a[0]) * (pt_b[0] - pt_a[0]) + (pt_b[1] - pt_a[1]) * (pt_b[1] - pt_a[1]))
+    }
+
+    /// Iterates over finite triangles, with their vertex indices and neighbor triangles
+    ///
+    /// Neighbor i is the triangle across the edge from vertex i to vertex i+1, None if it contains
+    /// infinity.
+    pub fn triangles_with_neighbors(
+        &self,
+    ) -> impl Iterator<Item = (usize, [usize; 3], [Option<usize>; 3])> + '_ {
+        (0..self.get_simplicial().get_nb_triangles()).filter_map(|ind_triangle| {
+            let tri = self.get_simplicial().get_triangle(ind_triangle).ok()?;
+            if let [Node::Value(ind_v1), Node::Value(ind_v2), Node::Value(ind_v3)] = tri.nodes() {
+                let neighbors = tri.halfedges().map(|he| {
+                    let tri_opp = he.opposite_halfedge().triangle();
+                    if tri_opp.contains_infinity() {
+                        None
+                    } else {
+                        Some(tri_opp.ind())
+                    }
+                });
+                Some((ind_triangle, [ind_v1, ind_v2, ind_v3], neighbors))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Gets apex vertices of the three neighbor triangles
+    ///
+    /// Apex i is the vertex of the triangle across the edge from vertex i to vertex i+1 which is
+    /// not on this edge, None if this triangle contains infinity.
+    #[must_use = "query result should be used"]
+    pub fn neighbor_apices(&self, ind_tri: usize) -> Result<[Option<usize>; 3]> {
+        let tri = self.get_simplicial().get_triangle(ind_tri)?;
+        Ok(tri.halfedges().map(|he| {
+            let he_opp = he.opposite_halfedge();
+            if he_opp.triangle().contains_infinity() {
+                return None;
+            }
+            match he_opp.prev_halfedge().first_node() {
+                Node::Value(ind_v) => Some(ind_v),
+                Node::Infinity => None,
+            }
+        }))
+    }
+
+    /// Gets finite triangles whose three vertices belong to subset
+    #[must_use = "query result should be used"]
+    pub fn triangles_within_subset(&self, subset: &HashSet<usize>) -> Result<Vec<[usize; 3]>> {
+        let mut triangles = Vec::new();
+        for ind_tri in 0..self.get_simplicial().get_nb_triangles() {
+            if let [Node::Value(ind_v1), Node::Value(ind_v2), Node::Value(ind_v3)] =
+                self.get_simplicial().get_triangle(ind_tri)?.nodes()
+            {
+                if subset.contains(&ind_v1) && subset.contains(&ind_v2) && subset.contains(&ind_v3)
+                {
+                    triangles.push([ind_v1, ind_v2, ind_v3]);
+                }
+            }
+        }
+        Ok(triangles)
+    }
+
+    /// Checks if a vertex lies on the convex hull (one of its triangles contains infinity)
+    #[must_use = "query result should be used"]
+    pub fn is_boundary_vertex(&self, ind_vertex: usize) -> Result<bool> {
+        self.get_vertex(ind_vertex)?;
+        let he_first = self
+            .get_simplicial()
+            .get_node_halfedge(ind_vertex)
+            .ok_or(anyhow::Error::msg("Vertex not in graph"))?;
+        let mut he_cur = he_first;
+        loop {
+            if he_cur.triangle().contains_infinity() {
+                return Ok(true);
+            }
+            he_cur = he_cur.prev_halfedge().opposite_halfedge();
+            if he_cur.ind() == he_first.ind() {
+                return Ok(false);
+            }
+        }
+    }
+
+    /// Gets histogram of interior vertex valences (number of edges at a vertex), mapping each
+    /// valence to its number of vertices
+    ///
+    /// Hull vertices are excluded, their fan reaching infinity.
+    #[must_use = "query result should be used"]
+    pub fn valence_histogram(&self) -> Result<HashMap<usize, usize>> {
+        let mut histogram = HashMap::new();
+        for ind_vertex in 0..self.vertex_coordinates.len() {
+            let fan = self.get_simplicial().get_outgoing_halfedges(ind_vertex);
+            if fan.is_empty() || fan.iter().any(|he| he.triangle().contains_infinity()) {
+                continue;
+            }
+            *histogram.entry(fan.len()).or_insert(0) += 1;
+        }
+        Ok(histogram)
+    }
+
+    /// Gets numbers of finite vertices, edges and triangles, with the Euler characteristic
+    /// V - E + F counting the outer face
+    ///
+    /// The characteristic of a valid triangulation (a triangulated disk) is 2, other values
+    /// indicate a corrupted structure.
+    #[must_use = "query result should be used"]
+    pub fn euler_characteristic(&self) -> Result<(usize, usize, usize, i64)> {
+        let nb_vertices = (0..self.vertex_coordinates.len())
+            .filter(|&ind_vertex| {
+                self.get_simplicial()
+                    .get_node_halfedge(ind_vertex)
+                    .is_some()
+            })
+            .count();
+        let mut nb_edges = 0;
+        let mut nb_triangles = 0;
+        for ind_tri in 0..self.get_simplicial().get_nb_triangles() {
+            let tri = self.get_simplicial().get_triangle(ind_tri)?;
+            if tri.contains_infinity() {
+                continue;
+            }
+            nb_triangles += 1;
+            // interior edges counted once, from the smaller vertex
+            nb_edges += tri
+                .halfedges()
+                .iter()
+                .filter(|he| match (he.first_node(), he.last_node()) {
+                    (Node::Value(ind1), Node::Value(ind2)) => {
+                        ind1 < ind2 || he.opposite_halfedge().triangle().contains_infinity()
+                    }
+                    _ => false,
+                })
+                .count();
+        }
+        let characteristic = nb_vertices as i64 - nb_edges as i64 + nb_triangles as i64 + 1;
+        Ok((nb_vertices, nb_edges, nb_triangles, characteristic))
+    }
+
+    /// Checks if two vertices are linked by an edge
+    ///
+    /// Both vertex fans are walked simultaneously, stopping at the end of the smaller one.
+    #[must_use = "query result should be used"]
+    pub fn are_adjacent(&self, ind_a: usize, ind_b: usize) -> Result<bool> {
+        self.get_vertex(ind_a)?;
+        self.get_vertex(ind_b)?;
+        let (he_first_a, he_first_b) = match (
+            self.get_simplicial().get_node_halfedge(ind_a),
+            self.get_simplicial().get_node_halfedge(ind_b),
+        ) {
+            (Some(he_a), Some(he_b)) => (he_a, he_b),
+            _ => return Ok(false),
+        };
+        let (node_a, node_b) = (Node::Value(ind_a), Node::Value(ind_b));
+        let (mut he_a, mut he_b) = (he_first_a, he_first_b);
+        loop {
+            if he_a.last_node().equals(&node_b) || he_b.last_node().equals(&node_a) {
+                return Ok(true);
+            }
+            he_a = he_a.prev_halfedge().opposite_halfedge();
+            he_b = he_b.prev_halfedge().opposite_halfedge();
+            if he_a.ind() == he_first_a.ind() || he_b.ind() == he_first_b.ind() {
+                return Ok(false);
+            }
+        }
+    }
+
+    /// Computes shortest path between two vertices along finite edges, weighted by their length
+    ///
+    /// A* search guided by the straight line distance to the goal. Returns the vertex indices from
+    /// start to goal, None if goal cannot be reached (vertex outside the triangulation).
+    #[must_use = "query result should be used"]
+    pub fn shortest_path(&self, start: usize, goal: usize) -> Result<Option<Vec<usize>>> {
+        let pt_goal = self.get_vertex(goal)?;
+        self.get_vertex(start)?;
+        let dist = |pt0: [f64; 2], pt1: [f64; 2]| {
+            sqrt((pt1[0] - pt0[0]) * (pt1[0] - pt0[0]) + (pt1[1] - pt0[1]) * (pt1[1] - pt0[1]))
+        };
+
+        let nb_vertices = self.vertex_coordinates.len();
+        let mut cost = vec![f64::INFINITY; nb_vertices];
+        let mut previous = vec![usize::MAX; nb_vertices];
+        // non negative estimates, ordered as their bit patterns
+        let mut to_visit = BinaryHeap::new();
+        cost[start] = 0.;
+        to_visit.push(Reverse((
+            dist(self.vertex_coordinates[start], pt_goal).to_bits(),
+            start,
+        )));
+        while let Some(Reverse((_, ind_v))) = to_visit.pop() {
+            if ind_v == goal {
+                let mut path = vec![goal];
+                while *path.last().unwrap() != start {
+                    path.push(previous[*path.last().unwrap()]);
+                }
+                path.reverse();
+                return Ok(Some(path));
+            }
+            let pt_v = self.vertex_coordinates[ind_v];
+            for he in self.get_simplicial().get_outgoing_halfedges(ind_v) {
+                let Node::Value(ind_next) = he.last_node() else {
+                    continue;
+                };
+                let pt_next = self.vertex_coordinates[ind_next];
+                let cost_next = cost[ind_v] + dist(pt_v, pt_next);
+                if cost_next < cost[ind_next] {
+                    cost[ind_next] = cost_next;
+                    previous[ind_next] = ind_v;
+                    let estimate = cost_next + dist(pt_next, pt_goal);
+                    to_visit.push(Reverse((estimate.to_bits(), ind_next)));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Gets the finite triangle containing halfedge from ind_a to ind_b (on the left of the edge),
+    /// None if this triangle contains infinity
+    #[must_use = "query result should be used"]
+    pub fn triangle_left_of_edge(&self, ind_a: usize, ind_b: usize) -> Result<Option<usize>> {
+        self.get_vertex(ind_a)?;
+        self.get_vertex(ind_b)?;
+        let he = self
+            .get_simplicial()
+            .get_halfedge_between(ind_a, ind_b)
+            .ok_or(anyhow::Error::msg("No edge between vertices"))?;
+        if he.triangle().contains_infinity() {
+            Ok(None)
+        } else {
+            Ok(Some(he.triangle().ind()))
+        }
+    }
+
+    /// Checks if some finite triangle is flat or clockwise oriented
+    #[must_use = "query result should be used"]
+    pub fn has_inverted_elements(&self) -> Result<bool> {
+        for ind_tri in 0..self.get_simplicial().get_nb_triangles() {
+            if let Some([pt0, pt1, pt2]) = self.triangle_coords(ind_tri)? {
+                if self.orient(pt0, pt1, pt2) <= 0. {
+                    return Ok(true);
+                }
+            }
+        }
+        Ok(false)
+    }
+
+    /// Gets convex hull vertices, in counterclockwise order
+    #[must_use = "query result should be used"]
+    pub fn convex_hull(&self) -> Result<Vec<usize>> {
+        // hull edges are the finite edges of infinite triangles, oriented clockwise
+        let mut next_vertex = HashMap::new();
+        for ind_tri in 0..self.get_simplicial().get_nb_triangles() {
+            let tri = self.get_simplicial().get_triangle(ind_tri)?;
+            if !tri.contains_infinity() {
+                continue;
+            }
+            for he in tri.halfedges() {
+                if let (Node::Value(ind_v1), Node::Value(ind_v2)) =
+                    (he.first_node(), he.last_node())
+                {
+                    next_vertex.insert(ind_v2, ind_v1);
+                }
+            }
+        }
+
+        let mut hull = Vec::new();
+        if let Some(&ind_first) = next_vertex.keys().min() {
+            let mut ind_cur = ind_first;
+            loop {
+                hull.push(ind_cur);
+                ind_cur = next_vertex[&ind_cur];
+                if ind_cur == ind_first {
+                    break;
+                }
+                if hull.len() > next_vertex.len() {
+                    return Err(anyhow::Error::msg("Inconsistent convex hull"));
+                }
+            }
+        }
+        Ok(hull)
+    }
+
+    /// Gets convex hull vertex coordinates, in counterclockwise order
+    #[must_use = "query result should be used"]
+    pub fn boundary_polygon(&self) -> Result<Vec<[f64; 2]>> {
+        Ok(self
+            .convex_hull()?
+            .into_iter()
+            .map(|ind_v| self.vertex_coordinates[ind_v])
+            .collect())
+    }
+
+    /// Gets convex hull vertex coordinates, in counterclockwise order, as a closed ring ending
+    /// with its first point
+    #[must_use = "query result should be used"]
+    pub fn boundary_polygon_closed(&self) -> Result<Vec<[f64; 2]>> {
+        let mut polygon = self.boundary_polygon()?;
+        if let Some(&first) = polygon.first() {
+            polygon.push(first);
+        }
+        Ok(polygon)
+    }
+
+    /// Gets convex hull edges with their unit outward normal, in counterclockwise order
+    #[must_use = "query result should be used"]
+    pub fn hull_edge_normals(&self) -> Result<Vec<([usize; 2], [f64; 2])>> {
+        let hull = self.convex_hull()?;
+        let mut normals = Vec::with_capacity(hull.len());
+        for (ind, &ind_a) in hull.iter().enumerate() {
+            let ind_b = hull[(ind + 1) % hull.len()];
+            // interior lies on the left of edge a -> b, thus on the right of b -> a
+            let (normal, _) = line_normal_and_factor(
+                self.vertex_coordinates[ind_b],
+                self.vertex_coordinates[ind_a],
+            )
+            .ok_or(anyhow::Error::msg("Degenerate hull edge"))?;
+            normals.push(([ind_a, ind_b], normal));
+        }
+        Ok(normals)
+    }
+
+    /// Computes convex hull area (shoelace formula over hull vertices)
+    #[must_use = "query result should be used"]
+    pub fn hull_area(&self) -> Result<f64> {
+        let hull = self.convex_hull()?;
+        let mut double_area = 0.;
+        for (ind, &ind_a) in hull.iter().enumerate() {
+            let pt_a = self.vertex_coordinates[ind_a];
+            let pt_b = self.vertex_coordinates[hull[(ind + 1) % hull.len()]];
+            double_area += pt_a[0] * pt_b[1] - pt_b[0] * pt_a[1];
+        }
+        Ok(0.5 * double_area)
+    }
+
+    /// Computes convex hull perimeter
+    #[must_use = "query result should be used"]
+    pub fn hull_perimeter(&self) -> Result<f64> {
+        let hull = self.convex_hull()?;
+        Ok((0..hull.len())
+            .map(|ind| self.edge_length([hull[ind], hull[(ind + 1) % hull.len()]]))
+            .sum())
+    }
+
+    /// Computes centroid of the region covered by finite triangles, weighting triangle centroids
+    /// by their area
+    #[must_use = "query result should be used"]
+    pub fn region_centroid(&self) -> Result<[f64; 2]> {
+        let mut double_area = 0.;
+        let mut weighted = [0., 0.];
+        self.for_each_finite_triangle(|[ind0, ind1, ind2]| {
+            let [pt0, pt1, pt2] = [ind0, ind1, ind2].map(|ind| self.vertex_coordinates[ind]);
+            let area =
+                (pt1[0] - pt0[0]) * (pt2[1] - pt0[1]) - (pt1[1] - pt0[1]) * (pt2[0] - pt0[0]);
+            double_area += area;
+            weighted[0] += area * (pt0[0] + pt1[0] + pt2[0]) / 3.;
+            weighted[1] += area * (pt0[1] + pt1[1] + pt2[1]) / 3.;
+        });
+        if double_area <= 0. {
+            return Err(anyhow::Error::msg("No finite triangle with positive area"));
+        }
+        Ok([weighted[0] / double_area, weighted[1] / double_area])
+    }
+
+    /// Gets farthest-point Delaunay triangulation, built on strictly convex hull vertices
+    ///
+    /// The circumcircle of each triangle contains all vertices. Its dual is the farthest-point
+    /// Voronoi diagram. The hull polygon is fan triangulated, then flipped until no apex lies
+    /// outside the circumcircle of the adjacent triangle.
+    #[must_use = "query result should be used"]
+    pub fn farthest_point_triangulation(&self) -> Result<Vec<[usize; 3]>> {
+        let hull = self.convex_hull()?;
+        let nb_hull = hull.len();
+        let corners: Vec<usize> = (0..nb_hull)
+            .filter(|&ind| {
+                let pt_prev = self.vertex_coordinates[hull[(ind + nb_hull - 1) % nb_hull]];
+                let pt_next = self.vertex_coordinates[hull[(ind + 1) % nb_hull]];
+                orient_2d(pt_prev, self.vertex_coordinates[hull[ind]], pt_next) > 0.
+            })
+            .map(|ind| hull[ind])
+            .collect();
+        if corners.len() < 3 {
+            return Err(anyhow::Error::msg(
+                "Needs at least 3 vertices on convex hull",
+            ));
+        }
+
+        // fan from first corner, each corner being outside the last hull edge
+        let mut simpl = SimplicialStructure2D::new();
+        simpl.first_triangle([corners[0], corners[1], corners[2]])?;
+        for ind in 3..corners.len() {
+            let ind_tri = simpl
+                .get_halfedge_between(corners[0], corners[ind - 1])
+                .ok_or(anyhow::Error::msg("Inconsistent convex hull"))?
+                .triangle()
+                .ind();
+            simpl.insert_node_within_triangle(corners[ind], ind_tri)?;
+        }
+
+        let coord = |node: Node| match node {
+            Node::Value(ind) => Some(Coord {
+                x: self.vertex_coordinates[ind][0],
+                y: self.vertex_coordinates[ind][1],
+            }),
+            Node::Infinity => None,
+        };
+        loop {
+            let mut flipped = false;
+            for ind_he in 0..3 * simpl.get_nb_triangles() {
+                let he = simpl.get_halfedge(ind_he)?;
+                let he_opp = he.opposite_halfedge();
+                if he.triangle().contains_infinity() || he_opp.triangle().contains_infinity() {
+                    continue;
+                }
+                let nodes = [
+                    he.first_node(),
+                    he.last_node(),
+                    he.next_halfedge().last_node(),
+                    he_opp.next_halfedge().last_node(),
+                ];
+                if let [Some(pt0), Some(pt1), Some(pt2), Some(pt_apex)] = nodes.map(coord) {
+                    // flips if apex is outside circumcircle
+                    if robust::incircle(pt0, pt1, pt2, pt_apex) < 0. {
+                        simpl.flip_halfedge(ind_he)?;
+                        flipped = true;
+                    }
+                }
+            }
+            if !flipped {
+                break;
+            }
+        }
+
+        let mut triangles = Vec::with_capacity(corners.len() - 2);
+        for ind_tri in 0..simpl.get_nb_triangles() {
+            if let [Node::Value(ind0), Node::Value(ind1), Node::Value(ind2)] =
+                simpl.get_triangle(ind_tri)?.nodes()
+            {
+                triangles.push([ind0, ind1, ind2]);
+            }
+        }
+        Ok(triangles)
+    }
+
+    /// Gets minimal enclosing circle (center, radius) of the triangulated vertices, computed from
+    /// the convex hull
+    #[must_use = "query result should be used"]
+    pub fn minimal_enclosing_circle(&self) -> Result<([f64; 2], f64)> {
+        let hull_pts: Vec<[f64; 2]> = self
+            .convex_hull()?
+            .into_iter()
+            .map(|ind| self.vertex_coordinates[ind])
+            .collect();
+        minimal_enclosing_circle(&hull_pts)
+            .ok_or_else(|| anyhow::Error::msg("No triangle in structure"))
+    }
+
+    /// Gets ordered list of finite triangles crossed by segment a -> b
+    ///
+    /// The march starts from the triangle containing a (empty list if a is outside the convex
+    /// hull) and stops at the triangle containing b, or when the segment exits the convex hull.
+    #[must_use = "query result should be used"]
+    pub fn triangles_along_segment(&self, a: [f64; 2], b: [f64; 2]) -> Result<Vec<usize>> {
+        if self.get_simplicial().get_nb_triangles() == 0 {
+            return Err(anyhow::Error::msg("No triangle in structure"));
+        }
+        let mut vec_tri = Vec::new();
+        let mut tri = self.get_simplicial().get_triangle(self.locate(a, 0)?)?;
+
+        while !tri.contains_infinity() {
+            if vec_tri.len() > self.get_simplicial().get_nb_triangles() {
+                return Err(anyhow::Error::msg("Segment march did not terminate"));
+            }
+            vec_tri.push(tri.ind());
+
+            // exit edge: b strictly beyond the edge, segment line passing from first node
+            // (strictly on its right) to last node (on its left or on it)
+            let mut he_exit = None;
+            for he in tri.halfedges() {
+                if let (Node::Value(ind_v1), Node::Value(ind_v2)) =
+                    (he.first_node(), he.last_node())
+                {
+                    let pt1 = self.get_vertices()[ind_v1];
+                    let pt2 = self.get_vertices()[ind_v2];
+                    if self.orient(pt1, pt2, b) < 0.
+                        && self.orient(a, b, pt1) < 0.
+                        && self.orient(a, b, pt2) >= 0.
+                    {
+                        he_exit = Some(he);
+                        break;
+                    }
+                }
+            }
+            match he_exit {
+                Some(he) => tri = he.opposite_halfedge().triangle(),
+                None => break,
+            }
+        }
+        Ok(vec_tri)
+    }
+
+    /// Samples a per vertex field on a w x h grid covering bbox (min and max corners), with
+    /// linear interpolation within triangles
+    ///
+    /// Values are given at cell centers, row by row from the min corner, None outside the convex
+    /// hull.
+    #[must_use = "query result should be used"]
+    pub fn rasterize_linear(
+        &self,
+        field: &[f64],
+        bbox: ([f64; 2], [f64; 2]),
+        w: usize,
+        h: usize,
+    ) -> Result<Vec<Option<f64>>> {
+        if field.len() != self.vertex_coordinates.len() {
+            return Err(anyhow::Error::msg("Field size differs from vertex count"));
+        }
+        if self.get_simplicial().get_nb_triangles() == 0 {
+            return Err(anyhow::Error::msg("Empty triangulation"));
+        }
+        let (pt_min, pt_max) = bbox;
+        let step = [
+            (pt_max[0] - pt_min[0]) / (w as f64),
+            (pt_max[1] - pt_min[1]) / (h as f64),
+        ];
+
+        let mut raster = Vec::with_capacity(w * h);
+        // seeding each walk from the previous cell triangle
+        let mut ind_tri = 0;
+        for j in 0..h {
+            for i in 0..w {
+                let pt = [
+                    pt_min[0] + (i as f64 + 0.5) * step[0],
+                    pt_min[1] + (j as f64 + 0.5) * step[1],
+                ];
+                ind_tri = self.locate(pt, ind_tri)?;
+                let value = match self.get_simplicial().get_triangle(ind_tri)?.nodes() {
+                    [Node::Value(ind_v1), Node::Value(ind_v2), Node::Value(ind_v3)] => {
+                        let tri = [ind_v1, ind_v2, ind_v3].map(|ind| self.vertex_coordinates[ind]);
+                        barycentric_coordinates(tri, pt).map(|bary| {
+                            bary[0] * field[ind_v1]
+                                + bary[1] * field[ind_v2]
+                                + bary[2] * field[ind_v3]
+                        })
+                    }
+                    _ => None,
+                };
+                raster.push(value);
+            }
+        }
+        Ok(raster)
+    }
+
+    /// Gets current modification generation (token for triangles_changed_since)
+    pub fn current_generation(&self) -> u64 {
+        self.simpl_struct.current_generation()
+    }
+
+    /// Gets triangles created or modified (by insertion or flip) after given generation
+    pub fn triangles_changed_since(&self, generation: u64) -> Vec<usize> {
+        self.simpl_struct.triangles_changed_since(generation)
+    }
+
+    /// Gets Voronoi edge dual to a halfedge, as the segment between the circumcenters of its two
+    /// adjacent triangles
+    ///
+    /// Returns None if one of the triangles contains infinity (or is flat).
+    #[must_use = "query result should be used"]
+    pub fn voronoi_edge(&self, ind_he: usize) -> Result<Option<([f64; 2], [f64; 2])>> {
+        let he = self.get_simplicial().get_halfedge(ind_he)?;
+        let tri1 = self.triangle_coords(he.triangle().ind())?;
+        let tri2 = self.triangle_coords(he.opposite_halfedge().triangle().ind())?;
+        if let (Some([pt0, pt1, pt2]), Some([pt3, pt4, pt5])) = (tri1, tri2) {
+            if let (Some(ctr1), Some(ctr2)) = (
+                circle_center_with_tolerance(pt0, pt1, pt2, self.flat_tolerance),
+                circle_center_with_tolerance(pt3, pt4, pt5, self.flat_tolerance),
+            ) {
+                return Ok(Some((ctr1, ctr2)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Gets unbounded Voronoi edges, as rays dual to convex hull edges in counterclockwise order
+    ///
+    /// Each ray starts from the circumcenter of the finite triangle along the hull edge, and goes
+    /// along the unit outward normal of this edge.
+    #[must_use = "query result should be used"]
+    pub fn unbounded_cell_rays(&self) -> Result<Vec<([f64; 2], [f64; 2])>> {
+        // finite triangle along each counterclockwise hull edge
+        let mut hull_triangle = HashMap::new();
+        for ind_tri in 0..self.get_simplicial().get_nb_triangles() {
+            let tri = self.get_simplicial().get_triangle(ind_tri)?;
+            if !tri.contains_infinity() {
+                continue;
+            }
+            for he in tri.halfedges() {
+                if let (Node::Value(ind_v1), Node::Value(ind_v2)) =
+                    (he.first_node(), he.last_node())
+                {
+                    hull_triangle.insert([ind_v2, ind_v1], he.opposite_halfedge().triangle().ind());
+                }
+            }
+        }
+
+        let mut rays = Vec::new();
+        for (edge, normal) in self.hull_edge_normals()? {
+            let &ind_tri = hull_triangle
+                .get(&edge)
+                .ok_or(anyhow::Error::msg("Inconsistent convex hull"))?;
+            let [pt0, pt1, pt2] = self
+                .triangle_coords(ind_tri)?
+                .ok_or(anyhow::Error::msg("Infinite triangle along convex hull"))?;
+            let center = circle_center_with_tolerance(pt0, pt1, pt2, self.flat_tolerance)
+                .ok_or(anyhow::Error::msg("Flat triangle along convex hull"))?;
+            rays.push((center, normal));
+        }
+        Ok(rays)
+    }
+
+    /// Approximates medial axis of the domain bounded by constrained edges, as the Voronoi edges
+    /// dual to Delaunay edges shared by two triangles of the domain
+    ///
+    /// A triangle is within the domain if reaching it from outside crosses an odd number of
+    /// boundary edges, holes being excluded.
+    #[must_use = "query result should be used"]
+    pub fn medial_axis(
+        &self,
+        boundary_constraints: &[[usize; 2]],
+    ) -> Result<Vec<([f64; 2], [f64; 2])>> {
+        let mut boundary = HashSet::new();
+        for &[ind_a, ind_b] in boundary_constraints.iter() {
+            if !self.is_constrained_edge(ind_a, ind_b) {
+                return Err(anyhow::Error::msg("Boundary edge is not constrained"));
+            }
+            boundary.insert([ind_a.min(ind_b), ind_a.max(ind_b)]);
+        }
+
+        let simpl = self.get_simplicial();
+        let nb_triangles = simpl.get_nb_triangles();
+        let mut ind_outside = None;
+        for ind_triangle in 0..nb_triangles {
+            if simpl.get_triangle(ind_triangle)?.contains_infinity() {
+                ind_outside = Some(ind_triangle);
+                break;
+            }
+        }
+        let ind_outside = ind_outside.ok_or(anyhow::Error::msg("Empty triangulation"))?;
+
+        // number of boundary edges crossed from outside, constrained regions filled with
+        // flood_fill_domains by increasing depth (other constraints not changing it)
+        let mut depth = vec![usize::MAX; nb_triangles];
+        let mut seeds = VecDeque::from([(ind_outside, 0)]);
+        while let Some((ind_seed, level)) = seeds.pop_front() {
+            if depth[ind_seed] != usize::MAX {
+                continue;
+            }
+            let reached = self.flood_fill_domains(ind_seed)?;
+            for ind_triangle in (0..nb_triangles).filter(|&ind| reached[ind]) {
+                depth[ind_triangle] = level;
+                for he in simpl.get_triangle(ind_triangle)?.halfedges() {
+                    let ind_opp = he.opposite_halfedge().triangle().ind();
+                    if reached[ind_opp] || depth[ind_opp] != usize::MAX {
+                        continue;
+                    }
+                    let crosses_boundary = match (he.first_node(), he.last_node()) {
+                        (Node::Value(ind1), Node::Value(ind2)) => {
+                            boundary.contains(&[ind1.min(ind2), ind1.max(ind2)])
+                        }
+                        _ => false,
+                    };
+                    if crosses_boundary {
+                        seeds.push_back((ind_opp, level + 1));
+                    } else {
+                        seeds.push_front((ind_opp, level));
+                    }
+                }
+            }
+        }
+
+        let mut axis = Vec::new();
+        for ind_he in 0..3 * nb_triangles {
+            let he = simpl.get_halfedge(ind_he)?;
+            let he_opp = he.opposite_halfedge();
+            if ind_he > he_opp.ind() {
+                continue;
+            }
+            if depth[he.triangle().ind()] % 2 == 1 && depth[he_opp.triangle().ind()] % 2 == 1 {
+                if let Some(edge) = self.voronoi_edge(ind_he)? {
+                    axis.push(edge);
+                }
+            }
+        }
+        Ok(axis)
+    }
+
+    /// Gets vertex nearest to a point among the vertices of the triangle containing it and of its
+    /// neighbors, None if farther than tolerance
+    #[must_use = "query result should be used"]
+    pub fn snap_to_vertex(&self, point: [f64; 2], tolerance: f64) -> Result<Option<usize>> {
+        if tolerance.is_nan() || tolerance < 0. {
+            return Err(anyhow::Error::msg("Tolerance should be non negative"));
+        }
+        if self.simpl_struct.get_nb_triangles() == 0 {
+            return Err(anyhow::Error::msg("Empty triangulation"));
+        }
+        let ind_triangle = self.locate(point, self.hinted_triangle(point))?;
+        let tri = self.get_simplicial().get_triangle(ind_triangle)?;
+
+        let mut nearest: Option<(usize, f64)> = None;
+        let candidates = tri.halfedges().into_iter().flat_map(|he| {
+            [
+                he.first_node(),
+                he.opposite_halfedge().prev_halfedge().first_node(),
+            ]
+        });
+        for node in candidates {
+            if let Node::Value(ind_v) = node {
+                let vert = self.get_vertices()[ind_v];
+                let diff = [vert[0] - point[0], vert[1] - point[1]];
+                let sq_dist = diff[0] * diff[0] + diff[1] * diff[1];
+                if nearest.map_or(true, |(_, sq_min)| sq_dist < sq_min) {
+                    nearest = Some((ind_v, sq_dist));
+                }
+            }
+        }
+        Ok(nearest
+            .filter(|&(_, sq_dist)| sq_dist <= tolerance * tolerance)
+            .map(|(ind_v, _)| ind_v))
+    }
+
+    /// Gets triangles whose circumcircle strictly contains a point, which its insertion would
+    /// remove, without modifying the structure
+    ///
+    /// The cavity is edge connected, and starts with the triangle containing the point. Triangles
+    /// with a point at infinity belong to it when the point lies strictly outside their edge.
+    #[must_use = "query result should be used"]
+    pub fn insertion_cavity(&self, point: [f64; 2]) -> Result<Vec<usize>> {
+        if self.get_simplicial().get_nb_triangles() == 0 {
+            return Err(anyhow::Error::msg("Empty triangulation"));
+        }
+        let ind_start = self.locate(point, self.hinted_triangle(point))?;
+        let (cavity, _) = self.cavity_from(point, ind_start)?;
+        Ok(cavity)
+    }
+
+    // insertion cavity grown from the triangle containing the point, made of triangles whose
+    // circumcircle strictly contains it, with a halfedge of its boundary
+    fn cavity_from(
+        &self,
+        point: [f64; 2],
+        ind_start: usize,
+    ) -> Result<(Vec<usize>, Option<usize>)> {
+        let simpl = self.get_simplicial();
+        let mut in_cavity = HashSet::new();
+        in_cavity.insert(ind_start);
+        let mut cavity = vec![ind_start];
+        let mut to_visit = vec![ind_start];
+        let mut ind_boundary = None;
+        while let Some(ind_triangle) = to_visit.pop() {
+            for he in simpl.get_triangle(ind_triangle)?.halfedges() {
+                if let Node::Value(ind_v) = he.first_node() {
+                    if self.get_vertices()[ind_v] == point {
+                        return Err(anyhow::Error::msg("Point coincides with a vertex"));
+                    }
+                }
+                let ind_opp = he.opposite_halfedge().triangle().ind();
+                if in_cavity.contains(&ind_opp) {
+                    continue;
+                }
+                if self.is_point_strict_in_circle(point, ind_opp)? {
+                    in_cavity.insert(ind_opp);
+                    cavity.push(ind_opp);
+                    to_visit.push(ind_opp);
+                } else {
+                    ind_boundary = Some(he.ind());
+                }
+            }
+        }
+        Ok((cavity, ind_boundary))
+    }
+
+    /// Computes Voronoi cell that a point would get if inserted, without modifying the structure
+    ///
+    /// Returns the natural neighbors of the point (vertex indices), counterclockwise, each with
+    /// the area its cell would lose to the new cell. The new cell area is their sum, and Sibson
+    /// weights are the areas divided by this sum. The point must lie strictly within the convex
+    /// hull.
+    #[must_use = "query result should be used"]
+    pub fn voronoi_cell_if_inserted(&self, point: [f64; 2]) -> Result<Vec<(usize, f64)>> {
+        let simpl = self.get_simplicial();
+        let nb_triangles = simpl.get_nb_triangles();
+        if nb_triangles == 0 {
+            return Err(anyhow::Error::msg("Empty triangulation"));
+        }
+        let ind_start = self.locate(point, self.hinted_triangle(point))?;
+        if simpl.get_triangle(ind_start)?.contains_infinity() {
+            return Err(anyhow::Error::msg("Point outside the convex hull"));
+        }
+
+        let (cavity, ind_boundary) = self.cavity_from(point, ind_start)?;
+        let in_cavity: HashSet<usize> = cavity.into_iter().collect();
+        let ind_boundary =
+            ind_boundary.ok_or(anyhow::Error::msg("Insertion cavity without boundary"))?;
+
+        let circumcenter = |pt0: [f64; 2], pt1: [f64; 2], pt2: [f64; 2]| {
+            circle_center_with_tolerance(pt0, pt1, pt2, self.flat_tolerance)
+                .ok_or(anyhow::Error::msg("Flat triangle in insertion cavity"))
+        };
+        let index = |node: Node| match node {
+            Node::Value(ind_v) => Ok(ind_v),
+            Node::Infinity => Err(anyhow::Error::msg("Point on the convex hull")),
+        };
+        let coords = |node: Node| index(node).map(|ind_v| self.get_vertices()[ind_v]);
+
+        // walks along the cavity boundary, counterclockwise, each boundary vertex losing the
+        // region between the new cell edge and the circumcenters of its cavity triangles
+        let mut neighbors = Vec::new();
+        let mut he_boundary = simpl.get_halfedge(ind_boundary)?;
+        loop {
+            let pt_prev = coords(he_boundary.first_node())?;
+            let ind_neighbor = index(he_boundary.last_node())?;
+            let pt_neighbor = self.get_vertices()[ind_neighbor];
+            if he_boundary
+                .opposite_halfedge()
+                .triangle()
+                .contains_infinity()
+                && orient_2d(pt_prev, pt_neighbor, point) <= 0.
+            {
+                return Err(anyhow::Error::msg("Point on the convex hull"));
+            }
+            let mut region = vec![circumcenter(pt_prev, pt_neighbor, point)?];
+            let mut he = he_boundary;
+            loop {
+                let [pt0, pt1, pt2] = self
+                    .triangle_coords(he.triangle().ind())?
+                    .ok_or(anyhow::Error::msg("Point on the convex hull"))?;
+                region.push(circumcenter(pt0, pt1, pt2)?);
+                // turns around the neighbor, to the next cavity triangle if any
+                let he_next = he.next_halfedge();
+                if !in_cavity.contains(&he_next.opposite_halfedge().triangle().ind()) {
+                    he = he_next;
+                    break;
+                }
+                he = he_next.opposite_halfedge();
+            }
+            let pt_next = coords(he.last_node())?;
+            region.push(circumcenter(pt_neighbor, pt_next, point)?);
+
+            let mut area = 0.;
+            for ind in 0..region.len() {
+                let (pt0, pt1) = (region[ind], region[(ind + 1) % region.len()]);
+                area += pt0[0] * pt1[1] - pt1[0] * pt0[1];
+            }
+            neighbors.push((ind_neighbor, 0.5 * area.abs()));
+
+            he_boundary = he;
+            if he_boundary.ind() == ind_boundary {
+                break;
+            }
+        }
+        Ok(neighbors)
+    }
+
+    /// Computes Voronoi diagram clipped to bbox (min and max corners), such that all cells are
+    /// bounded
+    ///
+    /// Each cell is the intersection of the bounding box with the half planes closer to its site
+    /// than to its Delaunay neighbors. Vertices are shared between cells along common edges.
+    #[must_use = "query result should be used"]
+    pub fn to_voronoi_halfedge(&self, bbox: ([f64; 2], [f64; 2])) -> Result<VoronoiDiagram> {
+        if bbox.0[0] >= bbox.1[0] || bbox.0[1] >= bbox.1[1] {
+            return Err(anyhow::Error::msg("Empty bounding box"));
+        }
+        let mut sites = Vec::new();
+        for ind_vert in 0..self.vertex_coordinates.len() {
+            let neighbors: Vec<(usize, [f64; 2])> = self
+                .get_simplicial()
+                .get_outgoing_halfedges(ind_vert)
+                .iter()
+                .filter_map(|he| match he.last_node() {
+                    Node::Value(ind_neigh) => Some((ind_neigh, self.vertex_coordinates[ind_neigh])),
+                    Node::Infinity => None,
+                })
+                .collect();
+            if !neighbors.is_empty() {
+                sites.push((ind_vert, self.vertex_coordinates[ind_vert], neighbors));
+            }
+        }
+        Ok(VoronoiDiagram::build(&sites, bbox))
+    }
+
+    // orientation predicate, exact integer one when all coordinates are integers
+    fn orient(&self, pt0: [f64; 2], pt1: [f64; 2], pt2: [f64; 2]) -> f64 {
+        // pt2 may be a query point with non integer coordinates
+        if self.integer_coordinates && is_int_coordinate(pt2) {
+            orient_2d_int(to_int(pt0), to_int(pt1), to_int(pt2)) as f64
+        } else {
+            orient_2d(pt0, pt1, pt2)
+        }
+    }
+
+    // convexity predicate, exact integer one when all coordinates are integers
+    fn convexity(&self, pt0: [f64; 2], pt1: [f64; 2], pt2: [f64; 2]) -> i8 {
+        if self.integer_coordinates {
+            is_convex_int(to_int(pt0), to_int(pt1), to_int(pt2))
+        } else {
+            is_convex(pt0, pt1, pt2)
+        }
+    }
+
+    fn is_vertex_strict_in_circle(&self, ind_vert: usize, ind_tri: usize) -> Result<bool> {
+        self.is_point_strict_in_circle(self.get_vertices()[ind_vert], ind_tri)
+    }
+
+    fn is_point_strict_in_circle(&self, vert: [f64; 2], ind_tri: usize) -> Result<bool> {
+        let ext_tri = self.get_extended_triangle(ind_tri)?;
+        let integer = self.integer_coordinates && is_int_coordinate(vert);
+
+        let sign = match ext_tri {
+            ExtendedTriangle::Triangle(tri) if integer => {
+                in_circle_int(to_int(tri[0]), to_int(tri[1]), to_int(tri[2]), to_int(vert)) as f64
+            }
+            ExtendedTriangle::Segment(lin) if integer => self.orient(lin[0], lin[1], vert),
+            ExtendedTriangle::Triangle(tri) if self.predicate_mode == PredicateMode::Fast => {
+                in_circle_fast(tri[0], tri[1], tri[2], vert)
+            }
+            ExtendedTriangle::Triangle(tri) => in_circle(tri[0], tri[1], tri[2], vert),
+            ExtendedTriangle::Segment(lin) => robust::orient2d(
+                Coord {
+                    x: lin[0][0],
+                    y: lin[0][1],
+                },
+                Coord {
+                    x: lin[1][0],
+                    y: lin[1][1],
+                },
+                Coord {
+                    x: vert[0],
+                    y: vert[1],
+                },
+            ),
+        };
+        Ok(sign > 0.)
+    }
+
+    fn is_triangle_flat(&self, ind_tri: usize) -> Result<bool> {
+        let flat = match self.get_extended_triangle(ind_tri)? {
+            ExtendedTriangle::Triangle([pt0, pt1, pt2]) if self.integer_coordinates => {
+                self.orient(pt0, pt1, pt2) == 0.
+            }
+            ExtendedTriangle::Triangle([pt0, pt1, pt2]) => is_triangle_flat(pt0, pt1, pt2, 0.),
+            ExtendedTriangle::Segment(_) => false,
+        };
+        Ok(flat)
+    }
+
+    /// Chooses the next halfedge to cross during the walk
+    ///
+    /// Tie-breaking rule: a point lying exactly on the line of a hull edge is assigned to the
+    /// adjacent finite triangle if it lies within the edge, otherwise the walk moves along the
+    /// hull towards the point, to the neighboring infinite triangle. A point lying exactly on
+    /// an interior edge stays in the current triangle.
+    fn choose_he<'a>(
+        &self,
+        vec_edg: &[simplicial_struct_2d::IterHalfEdge<'a>],
+        vert: &[f64; 2],
+    ) -> Option<simplicial_struct_2d::IterHalfEdge<'a>> {
+        for &he in vec_edg {
+            let ind1 = he.first_node();
+            let ind2 = he.last_node();
+            if let (Node::Value(v1), Node::Value(v2)) = (ind1, ind2) {
+                let pt1 = self.get_vertices()[v1];
+                let pt2 = self.get_vertices()[v2];
+                let sign = self.orient(pt1, pt2, *vert);
+                if sign < 0. {
+                    return Some(he);
+                }
+                if sign == 0. && he.triangle().contains_infinity() {
+                    let dir = [pt2[0] - pt1[0], pt2[1] - pt1[1]];
+                    let dot = (vert[0] - pt1[0]) * dir[0] + (vert[1] - pt1[1]) * dir[1];
+                    if dot < 0. {
+                        // before first node, moves to previous hull edge
+                        return Some(he.prev_halfedge());
+                    } else if dot > dir[0] * dir[0] + dir[1] * dir[1] {
+                        // after last node, moves to next hull edge
+                        return Some(he.next_halfedge());
+                    } else {
+                        return Some(he);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    // walk starting triangle, from the spatial hint if any, or the last triangle
+    fn hinted_triangle(&self, vert: [f64; 2]) -> usize {
+        let nb_triangles = self.simpl_struct.get_nb_triangles();
+        self.spatial_hint
+            .as_ref()
+            .and_then(|hint| hint.get(vert))
+            .filter(|&ind_triangle| ind_triangle < nb_triangles)
+            .unwrap_or(nb_triangles.saturating_sub(1))
+    }
+
+    // triangle whose edges do not see the point, checking all triangles
+    fn walk_check_all(&self, vert: [f64; 2]) -> Result<usize> {
+        for ind_tri in 0..self.get_simplicial().get_nb_triangles() {
+            let tri = self.get_simplicial().get_triangle(ind_tri)?;
+            if self.choose_he(&tri.halfedges(), &vert).is_none() {
+                return Ok(ind_tri);
+            }
+        }
+        Err(anyhow::Error::msg(
+            "Could not find triangle containing point",
+        ))
+    }
+
+    // triangle containing point, walking by visibility from the starting triangle
+    fn locate(&self, vert: [f64; 2], ind_starting_triangle: usize) -> Result<usize> {
+        Ok(self.locate_with_fallback(vert, ind_starting_triangle)?.0)
+    }
+
+    // as locate, also telling if all triangles were checked, a visibility walk possibly cycling
+    // on constrained or non Delaunay triangulations
+    fn locate_with_fallback(
+        &self,
+        vert: [f64; 2],
+        ind_starting_triangle: usize,
+    ) -> Result<(usize, bool)> {
+        match self.walk_to_point(vert, ind_starting_triangle) {
+            Ok(ind) => Ok((ind, false)),
+            Err(err) if err.downcast_ref::<WalkFailed>().is_some() => {
+                Ok((self.walk_check_all(vert)?, true))
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn walk_to_point(&self, vert: [f64; 2], ind_starting_triangle: usize) -> Result<usize> {
+        let mut ind_tri_cur = ind_starting_triangle;
+        let start_tri = self.get_simplicial().get_triangle(ind_tri_cur)?;
+        let mut vec_edg = start_tri.halfedges();
+        let mut nb_edg = 3;
+        let mut side = false;
+        let mut nb_steps = 0;
+        // a walk without cycle visits each triangle at most once
+        let max_steps = self.get_simplicial().get_nb_triangles();
+        loop {
+            if nb_steps > max_steps {
+                return Err(anyhow::Error::msg(WalkFailed { nb_steps }));
+            }
+            nb_steps += 1;
+            if let Some(he) = self.choose_he(&vec_edg[..nb_edg], &vert) {
+                let he_opp = he.opposite_halfedge();
+                ind_tri_cur = he_opp.triangle().ind();
+                if side {
+                    vec_edg[0] = he_opp.next_halfedge();
+                    vec_edg[1] = he_opp.prev_halfedge();
+                } else {
+                    vec_edg[0] = he_opp.prev_halfedge();
+                    vec_edg[1] = he_opp.next_halfedge();
+                }
+                nb_edg = 2;
+                side = !side;
+            } else {
+                return Ok(ind_tri_cur);
+            }
+        }
+    }
+
+    /// Local Delaunay criterion: true if neither apex of the two triangles around the halfedge
+    /// lies strictly within the circumcircle of the other triangle (negation of
+    /// should_flip_halfedge)
+    #[must_use = "query result should be used"]
+    pub fn is_locally_delaunay(&self, ind_he: usize) -> Result<bool> {
+        Ok(!self.should_flip_halfedge(ind_he)?)
+    }
+
+    /// Delaunay flip criterion, true if the halfedge should be flipped
+    #[must_use = "query result should be used"]
+    pub fn should_flip_halfedge(&self, ind_he: usize) -> Result<bool> {
+        let he = self.get_simplicial().get_halfedge(ind_he)?;
+        let ind_tri_abd = he.triangle().ind();
+        let node_a = he.prev_halfedge().first_node();
+        let node_b = he.first_node();
+
+        let ind_tri_bcd = he.opposite_halfedge().triangle().ind();
+        let node_c = self.get_simplicial().opposite_apex(ind_he)?;
+        let node_d = he.opposite_halfedge().first_node();
+
+        match (node_a, node_b, node_c, node_d) {
+            (Node::Value(ind_node_a), Node::Value(_), Node::Value(ind_node_c), Node::Value(_)) => {
+                Ok(self.is_vertex_strict_in_circle(ind_node_c, ind_tri_abd)?
+                    || self.is_vertex_strict_in_circle(ind_node_a, ind_tri_bcd)?)
+            }
+            (Node::Infinity, Node::Value(_), Node::Value(ind_node_c), Node::Value(_)) => {
+                Ok(self.is_vertex_strict_in_circle(ind_node_c, ind_tri_abd)?
+                    || self.is_triangle_flat(ind_tri_bcd)?)
             }
             (
                 Node::Value(ind_node_a),
@@ -252,199 +2153,1912 @@ impl DelaunayStructure2D {
                 let pt_a = self.get_vertices()[ind_node_a];
                 let pt_c = self.get_vertices()[ind_node_c];
                 let pt_d = self.get_vertices()[ind_node_d];
-                Ok(is_convex(pt_c, pt_d, pt_a) == 1)
+                Ok(self.convexity(pt_c, pt_d, pt_a) == 1)
+            }
+            (Node::Value(ind_node_a), Node::Value(_), Node::Infinity, Node::Value(_)) => {
+                Ok(self.is_triangle_flat(ind_tri_abd)?
+                    || self.is_vertex_strict_in_circle(ind_node_a, ind_tri_bcd)?)
+            }
+            (
+                Node::Value(ind_node_a),
+                Node::Value(ind_node_b),
+                Node::Value(ind_node_c),
+                Node::Infinity,
+            ) => {
+                let pt_a = self.get_vertices()[ind_node_a];
+                let pt_b = self.get_vertices()[ind_node_b];
+                let pt_c = self.get_vertices()[ind_node_c];
+                Ok(self.convexity(pt_a, pt_b, pt_c) == 1)
+            }
+            (_, _, _, _) => Err(anyhow::Error::msg("Multiple infinity linked together")),
+        }
+    }
+
+    /// Locates vertex on the triangle found by the walk: coinciding with a triangle vertex, lying
+    /// on one of its finite edges, or strictly inside (or outside the hull for an infinite
+    /// triangle)
+    fn locate_on_triangle(&self, ind_vertex: usize, ind_triangle: usize) -> Result<PointLocation> {
+        let vert = self.get_vertices()[ind_vertex];
+        let tri = self.get_simplicial().get_triangle(ind_triangle)?;
+        for he in tri.halfedges() {
+            if let Node::Value(ind_v) = he.first_node() {
+                if self.get_vertices()[ind_v] == vert {
+                    return Ok(PointLocation::Vertex(ind_v));
+                }
+            }
+        }
+        for he in tri.halfedges() {
+            if let (Node::Value(ind_v1), Node::Value(ind_v2)) = (he.first_node(), he.last_node()) {
+                let pt1 = self.get_vertices()[ind_v1];
+                let pt2 = self.get_vertices()[ind_v2];
+                // within the segment, the hull edge line extending outside an infinite triangle
+                let within = (pt1[0] - vert[0]) * (pt2[0] - vert[0])
+                    + (pt1[1] - vert[1]) * (pt2[1] - vert[1])
+                    < 0.;
+                if within && self.orient(pt1, pt2, vert) == 0. {
+                    return Ok(PointLocation::Edge(he.ind()));
+                }
+            }
+        }
+        Ok(PointLocation::Inside)
+    }
+
+    fn insert_vertex_helper<F>(
+        &mut self,
+        ind_vertex: usize,
+        near_to: usize,
+        should_flip: &F,
+    ) -> Result<InsertedLocation>
+    where
+        F: Fn(&Self, usize) -> Result<bool>,
+    {
+        let now = StepTimer::start();
+        let (ind_triangle, fallback) =
+            self.locate_with_fallback(self.get_vertices()[ind_vertex], near_to)?;
+        if fallback {
+            self.nb_walk_fallbacks += 1;
+        }
+
+        let nano = now.elapsed_ns();
+        self.walk_ns += nano;
+
+        let now = StepTimer::start();
+        self.he_to_evaluate.clear();
+        let location = match self.locate_on_triangle(ind_vertex, ind_triangle)? {
+            PointLocation::Vertex(ind_existing) => {
+                match self.duplicate_policy {
+                    DuplicatePolicy::Skip => log::warn!(
+                        "Vertex {} coincides with vertex {}, skipped",
+                        ind_vertex,
+                        ind_existing
+                    ),
+                    DuplicatePolicy::Merge => {
+                        self.merged_vertices.insert(ind_vertex, ind_existing);
+                    }
+                }
+                return Ok(InsertedLocation::Skipped);
+            }
+            PointLocation::Edge(ind_he) => {
+                let he = self.get_simplicial().get_halfedge(ind_he)?;
+                let nodes = [he.first_node(), he.last_node()];
+                let mut split = false;
+                if let [Node::Value(ind_a), Node::Value(ind_b)] = nodes {
+                    if self
+                        .constraints
+                        .remove(&[ind_a.min(ind_b), ind_a.max(ind_b)])
+                    {
+                        // splits the constraint
+                        self.constraints
+                            .insert([ind_a.min(ind_vertex), ind_a.max(ind_vertex)]);
+                        self.constraints
+                            .insert([ind_b.min(ind_vertex), ind_b.max(ind_vertex)]);
+                        split = true;
+                    }
+                }
+                let (_, he_outer) = self.simpl_struct.insert_node_on_edge(ind_vertex, ind_he)?;
+                self.he_to_evaluate.extend_from_slice(&he_outer);
+                InsertedLocation::Edge(nodes, split)
+            }
+            PointLocation::Inside => {
+                let (_, he_outer) = self
+                    .simpl_struct
+                    .insert_node_within_triangle(ind_vertex, ind_triangle)?;
+                self.he_to_evaluate.extend_from_slice(&he_outer);
+                InsertedLocation::Inside
+            }
+        };
+
+        let nano = now.elapsed_ns();
+        self.insert_ns += nano;
+
+        let now = StepTimer::start();
+        let nb_flips = self.flip_evaluated_halfedges(should_flip)?;
+        self.total_flips += nb_flips;
+        self.max_flips_single_insert = self.max_flips_single_insert.max(nb_flips);
+
+        let nano = now.elapsed_ns();
+        self.flip_ns += nano;
+
+        if self.spatial_hint.is_some() {
+            let ind_triangle = self
+                .get_simplicial()
+                .get_node_halfedge(ind_vertex)
+                .map(|he| he.triangle().ind());
+            if let (Some(hint), Some(ind_triangle)) = (self.spatial_hint.as_mut(), ind_triangle) {
+                hint.update(self.vertex_coordinates[ind_vertex], ind_triangle);
+            }
+        }
+
+        Ok(location)
+    }
+
+    // flips halfedges of the evaluation stack until the criterion holds everywhere, returns the
+    // number of flips
+    fn flip_evaluated_halfedges<F>(&mut self, should_flip: &F) -> Result<usize>
+    where
+        F: Fn(&Self, usize) -> Result<bool>,
+    {
+        let mut nb_flips = 0;
+        while let Some(ind_he) = self.he_to_evaluate.pop() {
+            if !self.is_halfedge_constrained(ind_he)? && should_flip(self, ind_he)? {
+                nb_flips += 1;
+                let he = self.get_simplicial().get_halfedge(ind_he)?;
+                let ind_he_add1 = he.prev_halfedge().opposite_halfedge().ind();
+                let ind_he_add2 = he.next_halfedge().opposite_halfedge().ind();
+                let ind_he_add3 = he
+                    .opposite_halfedge()
+                    .prev_halfedge()
+                    .opposite_halfedge()
+                    .ind();
+                let ind_he_add4 = he
+                    .opposite_halfedge()
+                    .next_halfedge()
+                    .opposite_halfedge()
+                    .ind();
+                self.simpl_struct.flip_halfedge(ind_he)?;
+                if let Some(flips) = self.recorded_flips.as_mut() {
+                    // new diagonal is the last halfedge of the first triangle
+                    let he_new = self.simpl_struct.get_halfedge((ind_he / 3) * 3 + 2)?;
+                    flips.push([he_new.first_node(), he_new.last_node()]);
+                }
+                self.he_to_evaluate.push(ind_he_add1);
+                self.he_to_evaluate.push(ind_he_add2);
+                self.he_to_evaluate.push(ind_he_add3);
+                self.he_to_evaluate.push(ind_he_add4);
+            }
+        }
+        Ok(nb_flips)
+    }
+
+    fn insert_first_triangle(&mut self, indices_to_insert: &mut Vec<usize>) -> Result<()> {
+        let now = Timer::start();
+        // first triangle insertion
+        if self.simpl_struct.get_nb_triangles() == 0 {
+            let ind1 = indices_to_insert.pop().unwrap();
+            let pt1 = self.get_vertices()[ind1];
+
+            let mut aligned = Vec::new();
+
+            // duplicates of the first vertex cannot bound the first edge
+            let ind2 = loop {
+                match indices_to_insert.pop() {
+                    Some(ind2) if self.get_vertices()[ind2] == pt1 => aligned.push(ind2),
+                    Some(ind2) => break ind2,
+                    None => {
+                        return Err(anyhow::Error::msg(
+                            "Could not find three non aligned points",
+                        ))
+                    }
+                }
+            };
+            let pt2 = self.get_vertices()[ind2];
+
+            loop {
+                if let Some(ind3) = indices_to_insert.pop() {
+                    let pt3 = self.get_vertices()[ind3];
+
+                    let sign = self.orient(pt1, pt2, pt3);
+
+                    if sign > 0. {
+                        self.simpl_struct.first_triangle([ind1, ind2, ind3])?
+                    } else if sign < 0. {
+                        self.simpl_struct.first_triangle([ind1, ind3, ind2])?
+                    } else {
+                        aligned.push(ind3);
+                        continue;
+                    };
+                    self.insertion_order.extend_from_slice(&[ind1, ind2, ind3]);
+                } else {
+                    return Err(anyhow::Error::msg(
+                        "Could not find three non aligned points",
+                    ));
+                }
+
+                break;
+            }
+
+            indices_to_insert.append(&mut aligned);
+        }
+        let nano = now.elapsed_ns();
+        log::debug!("First triangle computed in {}ms", nano as f32 / 1e6);
+        Ok(())
+    }
+
+    /// insert a single vertex in the structure
+    ///
+    /// On an empty structure, vertices are stored until three of them are not aligned, the first
+    /// triangle being then built from all of them. These insertions cannot be undone.
+    pub fn insert_vertex(&mut self, vertex: [f64; 2], near_to: Option<usize>) -> Result<()> {
+        self.insert_vertex_with_criterion(vertex, near_to, Self::should_flip_halfedge)
+    }
+
+    /// insert a single vertex in the structure, flipping halfedges according to a custom
+    /// criterion instead of the Delaunay one
+    pub fn insert_vertex_with_criterion<F>(
+        &mut self,
+        vertex: [f64; 2],
+        near_to: Option<usize>,
+        should_flip: F,
+    ) -> Result<()>
+    where
+        F: Fn(&Self, usize) -> Result<bool>,
+    {
+        self.check_bounds(&[vertex])?;
+        if self.simpl_struct.get_nb_triangles() == 0 {
+            // vertices accumulate until three of them are not aligned
+            self.integer_coordinates &= is_int_coordinate(vertex);
+            self.vertex_coordinates.push(vertex);
+            return self.insert_pending_vertices(&should_flip);
+        }
+        let near_to = near_to.unwrap_or_else(|| self.hinted_triangle(vertex));
+        check_index(near_to, self.simpl_struct.get_nb_triangles())?;
+        let indices_to_insert = self.vertex_coordinates.len();
+        let integer_coordinates = self.integer_coordinates;
+        self.integer_coordinates &= is_int_coordinate(vertex);
+        self.vertex_coordinates.push(vertex);
+        if self.undo_depth > 0 {
+            self.recorded_flips = Some(Vec::new());
+        }
+        let location = self.insert_vertex_helper(indices_to_insert, near_to, &should_flip);
+        let flips = self.recorded_flips.take();
+        if let (Some(flips), Ok(location)) = (flips, &location) {
+            if self.undo_log.len() == self.undo_depth {
+                self.undo_log.pop_front();
+            }
+            self.undo_log.push_back(UndoStep {
+                ind_vertex: indices_to_insert,
+                location: location.clone(),
+                flips,
+                integer_coordinates,
+                generation: self.simpl_struct.current_generation(),
+            });
+        }
+        location?;
+        #[cfg(feature = "timing")]
+        log::debug!("Walks computed in {}ms", self.walk_ns as f32 / 1e6);
+        #[cfg(feature = "timing")]
+        log::debug!("Insertions computed in {}ms", self.insert_ns as f32 / 1e6);
+        #[cfg(feature = "timing")]
+        log::debug!("Flips computed in {}ms", self.flip_ns as f32 / 1e6);
+        Ok(())
+    }
+
+    /// insert a set of vertices in the structure
+    ///
+    /// On an empty structure, vertices stored by [`Self::insert_vertex`] are inserted as well.
+    /// Fails with `DegenerateInput` if the structure is empty and all points are identical.
+    pub fn insert_vertices<P: Point2>(
+        &mut self,
+        to_insert: &[P],
+        reorder_points: bool,
+    ) -> Result<()> {
+        self.check_bounds(to_insert)?;
+        if self.get_vertices().len() + to_insert.len() < 3 {
+            return Err(anyhow::Error::msg(
+                "Needs at least 3 vertices to compute Delaunay",
+            ));
+        }
+        self.check_extent(to_insert)?;
+
+        let first = self.vertex_coordinates.len();
+        self.vertex_coordinates
+            .extend(to_insert.iter().map(|vert| vert.xy()));
+        self.integer_coordinates &= self.vertex_coordinates[first..]
+            .iter()
+            .all(|&vert| is_int_coordinate(vert));
+        // vertices stored by single insertions are still pending on an empty structure
+        let first = if self.simpl_struct.get_nb_triangles() == 0 {
+            0
+        } else {
+            first
+        };
+        let indices_to_insert: Vec<usize> = (first..self.vertex_coordinates.len()).collect();
+        self.insert_indices(indices_to_insert, reorder_points)
+    }
+
+    /// Inserts a set of nalgebra vectors in the structure, reordering them
+    ///
+    /// ```
+    /// use nalgebra::Vector2;
+    /// use simple_delaunay_lib::delaunay_2d::delaunay_struct_2d::DelaunayStructure2D;
+    ///
+    /// let mut del_struct = DelaunayStructure2D::new();
+    /// let points = [
+    ///     Vector2::new(0., 0.),
+    ///     Vector2::new(1., 0.),
+    ///     Vector2::new(0., 1.),
+    ///     Vector2::new(1., 1.2),
+    /// ];
+    /// del_struct.add_vertices_na(&points).unwrap();
+    /// assert_eq!(del_struct.get_vertices_na(), points);
+    /// ```
+    #[cfg(feature = "nalgebra")]
+    pub fn add_vertices_na(&mut self, to_insert: &[nalgebra::Vector2<f64>]) -> Result<()> {
+        self.insert_vertices(to_insert, true)
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<A: Clone + Send + Sync> DelaunayStructure2D<A> {
+    /// Inserts vertices in an empty structure, triangulating spatial tiles in parallel
+    ///
+    /// Tile triangles whose circumcircle does not cross a boundary shared with another tile are
+    /// Delaunay triangles of the whole set. The remaining region is covered by the triangulation
+    /// of the other tile vertices, restricted to triangles lying outside the kept ones. Falls
+    /// back to [`Self::insert_vertices`] if the structure is not empty.
+    pub fn insert_vertices_parallel(&mut self, pts: &[[f64; 2]]) -> Result<()> {
+        use rayon::prelude::*;
+
+        self.check_bounds(pts)?;
+        if !self.vertex_coordinates.is_empty() || pts.len() < 3 {
+            return self.insert_vertices(pts, true);
+        }
+        let mut pt_min = pts[0];
+        let mut pt_max = pts[0];
+        for pt in pts.iter() {
+            pt_min = [pt_min[0].min(pt[0]), pt_min[1].min(pt[1])];
+            pt_max = [pt_max[0].max(pt[0]), pt_max[1].max(pt[1])];
+        }
+        let size_side = [pt_max[0] - pt_min[0], pt_max[1] - pt_min[1]];
+        if !(size_side[0] > 0. && size_side[1] > 0.) {
+            return self.insert_vertices(pts, true);
+        }
+
+        // at least two tiles per thread, and two tiles per side
+        let mut nb_side = 2;
+        while nb_side * nb_side < 2 * rayon::current_num_threads() {
+            nb_side += 1;
+        }
+        let size = [size_side[0] / nb_side as f64, size_side[1] / nb_side as f64];
+        let tile_of = |pt: [f64; 2]| {
+            let col = (((pt[0] - pt_min[0]) / size[0]) as usize).min(nb_side - 1);
+            let row = (((pt[1] - pt_min[1]) / size[1]) as usize).min(nb_side - 1);
+            row * nb_side + col
+        };
+        let mut tile_points = vec![Vec::new(); nb_side * nb_side];
+        let mut local_index = Vec::with_capacity(pts.len());
+        for (ind, &pt) in pts.iter().enumerate() {
+            let ind_tile = tile_of(pt);
+            local_index.push(tile_points[ind_tile].len());
+            tile_points[ind_tile].push(ind);
+        }
+
+        let now = Timer::start();
+        let template = self.clone();
+        let tiles: Vec<Option<Self>> = tile_points
+            .par_iter()
+            .map(|indices| {
+                let coords: Vec<[f64; 2]> = indices.iter().map(|&ind| pts[ind]).collect();
+                let mut tile = template.clone();
+                tile.insert_vertices(&coords, true).ok().map(|_| tile)
+            })
+            .collect();
+        log::debug!("Tiles computed in {}ms", now.elapsed_ns() as f32 / 1e6);
+
+        // safe triangles, as global indices and as flags on tile triangles, and frontier points
+        let margin = 1e-9 * (size[0] + size[1]);
+        let classified: Vec<TileClassification> = tiles
+            .par_iter()
+            .enumerate()
+            .map(|(ind_tile, tile)| {
+                let indices = &tile_points[ind_tile];
+                let tile = match tile {
+                    Some(tile) => tile,
+                    None => return Ok((Vec::new(), Vec::new(), indices.clone())),
+                };
+                let (col, row) = (ind_tile % nb_side, ind_tile / nb_side);
+                let lower = [
+                    pt_min[0] + col as f64 * size[0],
+                    pt_min[1] + row as f64 * size[1],
+                ];
+                // only boundaries shared with another tile bound the circumcircles
+                let bound = |shared: bool, val: f64, infinity: f64| {
+                    if shared {
+                        val
+                    } else {
+                        infinity
+                    }
+                };
+                let x_min = bound(col > 0, lower[0] + margin, f64::NEG_INFINITY);
+                let x_max = bound(
+                    col + 1 < nb_side,
+                    lower[0] + size[0] - margin,
+                    f64::INFINITY,
+                );
+                let y_min = bound(row > 0, lower[1] + margin, f64::NEG_INFINITY);
+                let y_max = bound(
+                    row + 1 < nb_side,
+                    lower[1] + size[1] - margin,
+                    f64::INFINITY,
+                );
+
+                let nb_triangles = tile.get_simplicial().get_nb_triangles();
+                let mut safe = Vec::new();
+                let mut is_safe = vec![false; nb_triangles];
+                let mut frontier = vec![false; indices.len()];
+                for (ind_tri, safe_tri) in is_safe.iter_mut().enumerate() {
+                    let circle = match tile.triangle_coords(ind_tri)? {
+                        Some([pt0, pt1, pt2]) => circle_center_with_tolerance(pt0, pt1, pt2, 0.)
+                            .map(|ctr| {
+                                (
+                                    ctr,
+                                    sqrt((pt0[0] - ctr[0]).powi(2) + (pt0[1] - ctr[1]).powi(2)),
+                                )
+                            }),
+                        None => None,
+                    };
+                    *safe_tri = circle.is_some_and(|(ctr, rad)| {
+                        ctr[0] - rad > x_min
+                            && ctr[0] + rad < x_max
+                            && ctr[1] - rad > y_min
+                            && ctr[1] + rad < y_max
+                    });
+                    let nodes = tile.get_simplicial().get_triangle(ind_tri)?.nodes();
+                    if *safe_tri {
+                        let mut tri = [0; 3];
+                        for (ind_glob, node) in tri.iter_mut().zip(nodes.iter()) {
+                            if let Node::Value(ind_v) = node {
+                                *ind_glob = indices[*ind_v];
+                            }
+                        }
+                        safe.push(tri);
+                    } else {
+                        for node in nodes.iter() {
+                            if let Node::Value(ind_v) = node {
+                                frontier[*ind_v] = true;
+                            }
+                        }
+                    }
+                }
+                let frontier = (0..indices.len())
+                    .filter(|&ind_v| frontier[ind_v])
+                    .map(|ind_v| indices[ind_v])
+                    .collect();
+                Ok((safe, is_safe, frontier))
+            })
+            .collect::<Result<_>>()?;
+
+        // triangulation of frontier points, restricted to the region not covered by safe triangles
+        let now = Timer::start();
+        let frontier: Vec<usize> = classified
+            .iter()
+            .flat_map(|(_, _, frontier)| frontier.iter().copied())
+            .collect();
+        let coords: Vec<[f64; 2]> = frontier.iter().map(|&ind| pts[ind]).collect();
+        let mut frontier_struct = template.clone();
+        frontier_struct.insert_vertices(&coords, true)?;
+        let frontier_triangles: Vec<[usize; 3]> = frontier_struct
+            .finite_triangles()?
+            .iter()
+            .map(|tri| [frontier[tri[0]], frontier[tri[1]], frontier[tri[2]]])
+            .collect();
+
+        let is_covered = |tri: &[usize; 3]| -> Result<bool> {
+            let ctr = [
+                (pts[tri[0]][0] + pts[tri[1]][0] + pts[tri[2]][0]) / 3.,
+                (pts[tri[0]][1] + pts[tri[1]][1] + pts[tri[2]][1]) / 3.,
+            ];
+            let ind_tile = tile_of(ctr);
+            let tile = match &tiles[ind_tile] {
+                Some(tile) => tile,
+                None => return Ok(false),
+            };
+            // a triangle within safe triangles of a tile only has vertices of this tile
+            let start = tri
+                .iter()
+                .filter(|&&ind| tile_of(pts[ind]) == ind_tile)
+                .find_map(|&ind| tile.get_simplicial().get_node_halfedge(local_index[ind]));
+            match start {
+                Some(he) => {
+                    let ind_tri = tile.locate(ctr, he.triangle().ind())?;
+                    Ok(classified[ind_tile].1[ind_tri])
+                }
+                None => Ok(false),
+            }
+        };
+        let remaining: Vec<[usize; 3]> = frontier_triangles
+            .par_iter()
+            .map(|tri| Ok((*tri, is_covered(tri)?)))
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .filter(|(_, covered)| !covered)
+            .map(|(tri, _)| tri)
+            .collect();
+        log::debug!(
+            "Frontier of {} points computed in {}ms",
+            frontier.len(),
+            now.elapsed_ns() as f32 / 1e6
+        );
+
+        let now = Timer::start();
+        let mut triangles: Vec<[usize; 3]> = classified
+            .iter()
+            .flat_map(|(safe, _, _)| safe.iter().copied())
+            .collect();
+        triangles.extend(remaining);
+
+        self.simpl_struct = SimplicialStructure2D::from_triangles(&triangles)?;
+        log::debug!("Tiles merged in {}ms", now.elapsed_ns() as f32 / 1e6);
+        self.vertex_coordinates = pts.to_vec();
+        self.integer_coordinates &= pts.iter().all(|&vert| is_int_coordinate(vert));
+        self.insertion_order.clear();
+        Ok(())
+    }
+}
+
+impl<A> DelaunayStructure2D<A> {
+    /// Merges a new point set into the existing triangulation
+    ///
+    /// Points are sorted along a Hilbert curve among themselves and inserted incrementally, each
+    /// walk starting around the previously inserted point. Capacity is reserved beforehand.
+    pub fn merge_points(&mut self, pts: &[[f64; 2]]) -> Result<()> {
+        self.vertex_coordinates.reserve(pts.len());
+        self.simpl_struct.reserve(pts.len());
+        self.insert_vertices(pts, true)
+    }
+
+    /// Merges another triangulation whose vertices lie in a disjoint bounding box
+    ///
+    /// Triangles of each side whose circumcircle does not reach the bounding box of the other side
+    /// are kept. The seam is covered by the triangulation of the remaining vertices, restricted to
+    /// triangles outside the kept ones. Overlapping bounding boxes, constraints or attributes fall
+    /// back to inserting the other vertices one by one. Vertices of other are appended, keeping
+    /// their order, and its triangle attributes and fixed flags are dropped.
+    pub fn stitch(&mut self, other: Self) -> Result<()> {
+        let other_pts = other.get_vertices();
+        self.check_bounds(other_pts)?;
+        if self.get_simplicial().get_nb_triangles() == 0
+            || other.get_simplicial().get_nb_triangles() == 0
+        {
+            return self.insert_vertices(other_pts, true);
+        }
+        let bbox = |pts: &[[f64; 2]]| {
+            pts.iter().fold((pts[0], pts[0]), |(pt_min, pt_max), pt| {
+                (
+                    [pt_min[0].min(pt[0]), pt_min[1].min(pt[1])],
+                    [pt_max[0].max(pt[0]), pt_max[1].max(pt[1])],
+                )
+            })
+        };
+        let (self_min, self_max) = bbox(self.get_vertices());
+        let (other_min, other_max) = bbox(other_pts);
+        let overlap = self_min[0] <= other_max[0]
+            && other_min[0] <= self_max[0]
+            && self_min[1] <= other_max[1]
+            && other_min[1] <= self_max[1];
+        if overlap
+            || !self.constraints.is_empty()
+            || !other.constraints.is_empty()
+            || !self.attributes.is_empty()
+        {
+            for &vert in other_pts.iter() {
+                self.insert_vertex(vert, None)?;
+            }
+            return Ok(());
+        }
+
+        let now = Timer::start();
+        let offset = self.vertex_coordinates.len();
+        let margin = 1e-9
+            * (self_max[0].max(other_max[0]) - self_min[0].min(other_min[0])
+                + self_max[1].max(other_max[1])
+                - self_min[1].min(other_min[1]));
+
+        let classify = |side: &Self,
+                        first: usize,
+                        (box_min, box_max): ([f64; 2], [f64; 2])|
+         -> Result<StitchClassification> {
+            let nb_triangles = side.get_simplicial().get_nb_triangles();
+            let mut safe = Vec::new();
+            let mut is_safe = vec![false; nb_triangles];
+            let mut frontier = vec![false; side.get_vertices().len()];
+            for (ind_tri, safe_tri) in is_safe.iter_mut().enumerate() {
+                let circle = match side.triangle_coords(ind_tri)? {
+                    Some([pt0, pt1, pt2]) => {
+                        circle_center_with_tolerance(pt0, pt1, pt2, 0.).map(|ctr| {
+                            (
+                                ctr,
+                                sqrt(
+                                    (pt0[0] - ctr[0]) * (pt0[0] - ctr[0])
+                                        + (pt0[1] - ctr[1]) * (pt0[1] - ctr[1]),
+                                ),
+                            )
+                        })
+                    }
+                    None => None,
+                };
+                // circle away from the other box
+                *safe_tri = circle.is_some_and(|(ctr, rad)| {
+                    let dx = (box_min[0] - ctr[0]).max(ctr[0] - box_max[0]).max(0.);
+                    let dy = (box_min[1] - ctr[1]).max(ctr[1] - box_max[1]).max(0.);
+                    sqrt(dx * dx + dy * dy) > rad + margin
+                });
+                let nodes = side.get_simplicial().get_triangle(ind_tri)?.nodes();
+                if *safe_tri {
+                    let mut tri = [0; 3];
+                    for (ind_glob, node) in tri.iter_mut().zip(nodes.iter()) {
+                        if let Node::Value(ind_v) = node {
+                            *ind_glob = first + ind_v;
+                        }
+                    }
+                    safe.push(tri);
+                } else {
+                    for node in nodes.iter() {
+                        if let Node::Value(ind_v) = node {
+                            frontier[*ind_v] = true;
+                        }
+                    }
+                }
+            }
+            Ok((safe, is_safe, frontier))
+        };
+        let (self_safe, self_is_safe, self_frontier) = classify(self, 0, (other_min, other_max))?;
+        let (other_safe, other_is_safe, other_frontier) =
+            classify(&other, offset, (self_min, self_max))?;
+
+        let pts: Vec<[f64; 2]> = self
+            .get_vertices()
+            .iter()
+            .chain(other_pts.iter())
+            .copied()
+            .collect();
+        let frontier: Vec<usize> = (0..offset)
+            .filter(|&ind_v| self_frontier[ind_v])
+            .chain((0..other_pts.len()).filter_map(|ind_v| {
+                if other_frontier[ind_v] {
+                    Some(offset + ind_v)
+                } else {
+                    None
+                }
+            }))
+            .collect();
+        let coords: Vec<[f64; 2]> = frontier.iter().map(|&ind| pts[ind]).collect();
+        let mut frontier_struct = DelaunayStructure2D::new();
+        frontier_struct.set_flat_tolerance(self.flat_tolerance);
+        frontier_struct.set_predicate_mode(self.predicate_mode);
+        frontier_struct.insert_vertices(&coords, true)?;
+
+        // a triangle within safe triangles of a side only has vertices of this side
+        let is_covered = |tri: &[usize; 3]| -> Result<bool> {
+            let ctr = [
+                (pts[tri[0]][0] + pts[tri[1]][0] + pts[tri[2]][0]) / 3.,
+                (pts[tri[0]][1] + pts[tri[1]][1] + pts[tri[2]][1]) / 3.,
+            ];
+            for (side, first, is_safe) in
+                [(&*self, 0, &self_is_safe), (&other, offset, &other_is_safe)]
+            {
+                let start = tri
+                    .iter()
+                    .filter(|&&ind| ind >= first && ind - first < side.get_vertices().len())
+                    .find_map(|&ind| side.get_simplicial().get_node_halfedge(ind - first));
+                if let Some(he) = start {
+                    if is_safe[side.locate(ctr, he.triangle().ind())?] {
+                        return Ok(true);
+                    }
+                }
+            }
+            Ok(false)
+        };
+        let mut triangles = self_safe;
+        triangles.extend(other_safe);
+        for tri in frontier_struct.finite_triangles()? {
+            let tri = [frontier[tri[0]], frontier[tri[1]], frontier[tri[2]]];
+            if !is_covered(&tri)? {
+                triangles.push(tri);
+            }
+        }
+
+        let generation = self.simpl_struct.current_generation();
+        self.simpl_struct = SimplicialStructure2D::from_triangles(&triangles)?;
+        self.simpl_struct.mark_rebuilt(generation);
+        self.vertex_coordinates = pts;
+        self.integer_coordinates &= other.integer_coordinates;
+        self.insertion_order.clear();
+        self.undo_log.clear();
+        // triangle indices changed
+        if let Some(resolution) = self.spatial_hint.as_ref().map(|hint| hint.resolution()) {
+            self.set_spatial_hint(Some(resolution))?;
+        }
+        log::debug!(
+            "Stitched with a frontier of {} points in {}ms",
+            frontier.len(),
+            now.elapsed_ns() as f32 / 1e6
+        );
+        Ok(())
+    }
+
+    /// Inserts a set of vertices with integer coordinates, predicates being computed in exact
+    /// integer arithmetic while all vertices have integer coordinates
+    ///
+    /// Coordinates should not exceed [`INT_COORD_LIMIT`] in absolute value. The structure should
+    /// be empty or contain only integer vertices.
+    pub fn insert_vertices_int(&mut self, pts: &[[i32; 2]]) -> Result<()> {
+        if pts.iter().any(|pt| {
+            pt[0].unsigned_abs() > INT_COORD_LIMIT as u32
+                || pt[1].unsigned_abs() > INT_COORD_LIMIT as u32
+        }) {
+            return Err(anyhow::Error::msg("Integer coordinate out of range"));
+        }
+        if self.vertex_coordinates.is_empty() {
+            self.integer_coordinates = true;
+        } else if !self.integer_coordinates {
+            return Err(anyhow::Error::msg(
+                "Structure already contains non integer vertices",
+            ));
+        }
+        let pts_f64: Vec<[f64; 2]> = pts.iter().map(|pt| [pt[0] as f64, pt[1] as f64]).collect();
+        self.insert_vertices(&pts_f64, true)
+    }
+
+    /// Checks if predicates are computed in integer arithmetic
+    pub fn has_integer_coordinates(&self) -> bool {
+        self.integer_coordinates
+    }
+
+    /// Appends vertices coordinates without inserting them, returns their index range
+    ///
+    /// Fails with [`PointOutOfBounds`] if a point lies outside the bounds, before registering
+    /// anything.
+    ///
+    /// [`PointOutOfBounds`]: crate::error::PointOutOfBounds
+    pub fn register_vertices(
+        &mut self,
+        to_register: &[[f64; 2]],
+    ) -> Result<core::ops::Range<usize>> {
+        self.check_bounds(to_register)?;
+        let first = self.vertex_coordinates.len();
+        self.integer_coordinates &= to_register.iter().all(|&vert| is_int_coordinate(vert));
+        self.vertex_coordinates.extend_from_slice(to_register);
+        Ok(first..self.vertex_coordinates.len())
+    }
+
+    /// Inserts previously registered vertices, only this subset being reordered along a Hilbert
+    /// curve
+    pub fn insert_registered(&mut self, indices: &[usize]) -> Result<()> {
+        let mut to_insert = HashSet::new();
+        for &ind in indices.iter() {
+            check_index(ind, self.vertex_coordinates.len())?;
+            if self.simpl_struct.get_node_halfedge(ind).is_some() || !to_insert.insert(ind) {
+                return Err(anyhow::Error::msg("Vertex already inserted"));
+            }
+        }
+        if self.simpl_struct.get_nb_triangles() == 0 && indices.len() < 3 {
+            return Err(anyhow::Error::msg(
+                "Needs at least 3 vertices to compute Delaunay",
+            ));
+        }
+        self.insert_indices(indices.to_vec(), true)
+    }
+
+    fn insert_indices(
+        &mut self,
+        indices_to_insert: Vec<usize>,
+        reorder_points: bool,
+    ) -> Result<()> {
+        self.insert_indices_with_criterion(
+            indices_to_insert,
+            reorder_points,
+            &Self::should_flip_halfedge,
+        )
+    }
+
+    // builds the first triangle from vertices accumulated by single insertions, if three of them
+    // are not aligned
+    fn insert_pending_vertices<F>(&mut self, should_flip: &F) -> Result<()>
+    where
+        F: Fn(&Self, usize) -> Result<bool>,
+    {
+        let vertices = &self.vertex_coordinates;
+        let pt0 = vertices[0];
+        let Some(ind1) = (1..vertices.len()).find(|&ind| vertices[ind] != pt0) else {
+            return Ok(());
+        };
+        let Some(ind2) =
+            (1..vertices.len()).find(|&ind| self.orient(pt0, vertices[ind1], vertices[ind]) != 0.)
+        else {
+            return Ok(());
+        };
+        // non aligned vertices popped first
+        let mut indices_to_insert: Vec<usize> = (1..vertices.len())
+            .filter(|&ind| ind != ind1 && ind != ind2)
+            .collect();
+        indices_to_insert.extend([ind2, ind1, 0]);
+        self.insert_indices_with_criterion(indices_to_insert, false, should_flip)
+    }
+
+    fn insert_indices_with_criterion<F>(
+        &mut self,
+        mut indices_to_insert: Vec<usize>,
+        reorder_points: bool,
+        should_flip: &F,
+    ) -> Result<()>
+    where
+        F: Fn(&Self, usize) -> Result<bool>,
+    {
+        if reorder_points {
+            let now = Timer::start();
+            indices_to_insert = build_hilbert_curve(self.get_vertices(), &indices_to_insert);
+            let nano = now.elapsed_ns();
+            log::debug!("Hilbert curve computed in {}ms", nano as f32 / 1e6);
+        }
+
+        self.insertion_order.clear();
+        if self.simpl_struct.get_nb_triangles() == 0 {
+            self.insert_first_triangle(&mut indices_to_insert)?;
+        }
+
+        // each walk starts around the previously inserted vertex
+        let mut near_to = self.simpl_struct.get_nb_triangles() - 1;
+        while let Some(ind_vertex) = indices_to_insert.pop() {
+            self.insertion_order.push(ind_vertex);
+            self.insert_vertex_helper(ind_vertex, near_to, should_flip)?;
+            if let Some(he) = self.simpl_struct.get_node_halfedge(ind_vertex) {
+                near_to = he.triangle().ind();
+            }
+        }
+        #[cfg(feature = "timing")]
+        log::debug!("Walks computed in {}ms", self.walk_ns as f32 / 1e6);
+        #[cfg(feature = "timing")]
+        log::debug!("Insertions computed in {}ms", self.insert_ns as f32 / 1e6);
+        #[cfg(feature = "timing")]
+        log::debug!("Flips computed in {}ms", self.flip_ns as f32 / 1e6);
+
+        Ok(())
+    }
+
+    /// Inserts points read line by line from a text source, returns the number of points added
+    /// to the triangulation
+    ///
+    /// Points are inserted by batches while reading, the z coordinate of [`PointFormat::Xyz`]
+    /// being ignored. Points coinciding with a vertex are not counted, and points stored until
+    /// the first triangle can be built (see [`Self::insert_vertex`]) are counted by the call
+    /// building it. A malformed line or a non finite coordinate fails with [`ParseError`], the
+    /// previous batches staying inserted.
+    ///
+    /// [`ParseError`]: crate::error::ParseError
+    #[cfg(feature = "std")]
+    pub fn insert_from_reader<R: BufRead>(&mut self, r: R, format: PointFormat) -> Result<usize> {
+        const BATCH_SIZE: usize = 1 << 16;
+        let nb_inserted = |del: &Self| {
+            (0..del.vertex_coordinates.len())
+                .filter(|&ind| del.simpl_struct.get_node_halfedge(ind).is_some())
+                .count()
+        };
+        let nb_before = nb_inserted(self);
+        let mut batch = Vec::new();
+        for_each_point(r, format, |pt| {
+            batch.push([pt[0], pt[1]]);
+            if batch.len() == BATCH_SIZE {
+                self.insert_vertices(&batch, true)?;
+                batch.clear();
+            }
+            Ok(())
+        })?;
+        if batch.len() < 3 {
+            // too few vertices for a bulk insertion, stored for the next ones if no triangle is
+            // built yet
+            for &vert in batch.iter() {
+                self.insert_vertex(vert, None)?;
+            }
+        } else {
+            self.insert_vertices(&batch, true)?;
+        }
+        Ok(nb_inserted(self) - nb_before)
+    }
+
+    /// insert a set of vertices in the structure, skipping duplicates of inserted vertices and non
+    /// finite vertices
+    ///
+    /// Returns, for each input vertex, its index in the structure if it has been inserted.
+    pub fn insert_vertices_reporting(
+        &mut self,
+        to_insert: &[[f64; 2]],
+    ) -> Result<Vec<Option<usize>>> {
+        // -0. and 0. share the same key
+        let key = |vert: &[f64; 2]| [(vert[0] + 0.).to_bits(), (vert[1] + 0.).to_bits()];
+        // vertices stored on an empty structure are inserted along with the new ones
+        let is_empty = self.simpl_struct.get_nb_triangles() == 0;
+        let mut known: HashSet<[u64; 2]> = (0..self.vertex_coordinates.len())
+            .filter(|&ind| is_empty || self.simpl_struct.get_node_halfedge(ind).is_some())
+            .map(|ind| key(&self.vertex_coordinates[ind]))
+            .collect();
+
+        let mut status = Vec::new();
+        let mut accepted = Vec::new();
+        for vert in to_insert.iter() {
+            if vert[0].is_finite() && vert[1].is_finite() && known.insert(key(vert)) {
+                status.push(Some(self.get_vertices().len() + accepted.len()));
+                accepted.push(*vert);
+            } else {
+                status.push(None);
+            }
+        }
+
+        self.insert_vertices(&accepted, true)?;
+        Ok(status)
+    }
+
+    /// Inserts the midpoint of every finite edge, returning the range of midpoint indices
+    ///
+    /// Midpoints are inserted as Delaunay vertices, splitting constraints. When no triangle is
+    /// obtuse, this is the uniform split of each triangle into four.
+    pub fn refine_uniform(&mut self) -> Result<core::ops::Range<usize>> {
+        let mut midpoints = Vec::new();
+        for ind_tri in 0..self.get_simplicial().get_nb_triangles() {
+            for he in self.get_simplicial().get_triangle(ind_tri)?.halfedges() {
+                // each edge taken once, from its smaller vertex
+                if let (Node::Value(ind1), Node::Value(ind2)) = (he.first_node(), he.last_node()) {
+                    if ind1 < ind2 {
+                        let (pt1, pt2) =
+                            (self.vertex_coordinates[ind1], self.vertex_coordinates[ind2]);
+                        midpoints.push([(pt1[0] + pt2[0]) * 0.5, (pt1[1] + pt2[1]) * 0.5]);
+                    }
+                }
+            }
+        }
+        let first = self.vertex_coordinates.len();
+        if !midpoints.is_empty() {
+            self.insert_vertices(&midpoints, true)?;
+        }
+        Ok(first..self.vertex_coordinates.len())
+    }
+
+    /// Inserts vertices (see [`Self::insert_vertices`]) marked as fixed
+    pub fn add_fixed_vertices(&mut self, pts: &[[f64; 2]]) -> Result<()> {
+        let first = self.vertex_coordinates.len();
+        self.insert_vertices(pts, true)?;
+        for ind_vertex in first..self.vertex_coordinates.len() {
+            self.set_fixed(ind_vertex, true)?;
+        }
+        Ok(())
+    }
+
+    /// Marks a vertex as fixed or not, fixed vertices being refused by [`Self::move_vertex`] and
+    /// [`Self::remove_vertices`]
+    pub fn set_fixed(&mut self, ind_vertex: usize, fixed: bool) -> Result<()> {
+        self.get_vertex(ind_vertex)?;
+        if ind_vertex >= self.fixed.len() {
+            self.fixed.resize(ind_vertex + 1, false);
+        }
+        self.fixed[ind_vertex] = fixed;
+        Ok(())
+    }
+
+    /// Checks if a vertex is fixed
+    pub fn is_fixed(&self, ind_vertex: usize) -> bool {
+        self.fixed.get(ind_vertex).copied().unwrap_or(false)
+    }
+
+    /// Moves a vertex, keeping the connectivity unchanged
+    ///
+    /// Neither orientation nor Delaunay property are restored, triangles around the vertex may
+    /// invert (see has_inverted_elements). Fixed vertices cannot be moved.
+    pub fn move_vertex(&mut self, ind_vertex: usize, vertex: [f64; 2]) -> Result<()> {
+        self.get_vertex(ind_vertex)?;
+        if self.is_fixed(ind_vertex) {
+            return Err(anyhow::Error::msg("Cannot move fixed vertex"));
+        }
+        self.vertex_coordinates[ind_vertex] = vertex;
+        self.integer_coordinates &= is_int_coordinate(vertex);
+        self.undo_log.clear();
+        Ok(())
+    }
+
+    /// Undoes the last single insertion, flipping back its flips and removing the vertex
+    ///
+    /// Fails if the undo log is empty, or if the structure was modified since this insertion.
+    /// Triangle indices are not preserved.
+    pub fn undo_last_insert(&mut self) -> Result<()> {
+        let step = self
+            .undo_log
+            .pop_back()
+            .ok_or(anyhow::Error::msg("Undo log is empty"))?;
+        if step.generation != self.simpl_struct.current_generation()
+            || step.ind_vertex + 1 != self.vertex_coordinates.len()
+        {
+            self.undo_log.clear();
+            return Err(anyhow::Error::msg(
+                "Structure modified since the last insertion",
+            ));
+        }
+
+        let not_linked = || anyhow::Error::msg("Nodes are not linked by an edge");
+        for &[node1, node2] in step.flips.iter().rev() {
+            let ind_he = self
+                .halfedge_between_nodes(node1, node2)
+                .ok_or_else(not_linked)?;
+            self.simpl_struct.flip_halfedge(ind_he)?;
+        }
+        match step.location {
+            InsertedLocation::Skipped => (),
+            InsertedLocation::Inside => {
+                let ind_he = self
+                    .simpl_struct
+                    .get_outgoing_halfedges(step.ind_vertex)
+                    .first()
+                    .map(|he| he.ind())
+                    .ok_or(anyhow::Error::msg("Vertex not in graph"))?;
+                self.simpl_struct.collapse_halfedge(ind_he)?;
+            }
+            InsertedLocation::Edge(nodes, split) => {
+                let ind_he = self
+                    .halfedge_between_nodes(Node::Value(step.ind_vertex), nodes[0])
+                    .ok_or_else(not_linked)?;
+                self.simpl_struct.collapse_halfedge(ind_he)?;
+                if let ([Node::Value(ind_a), Node::Value(ind_b)], true) = (nodes, split) {
+                    let ind_v = step.ind_vertex;
+                    self.constraints
+                        .remove(&[ind_a.min(ind_v), ind_a.max(ind_v)]);
+                    self.constraints
+                        .remove(&[ind_b.min(ind_v), ind_b.max(ind_v)]);
+                    self.constraints
+                        .insert([ind_a.min(ind_b), ind_a.max(ind_b)]);
+                }
+            }
+        }
+        self.vertex_coordinates.pop();
+        self.fixed.truncate(self.vertex_coordinates.len());
+        self.merged_vertices.remove(&step.ind_vertex);
+        self.integer_coordinates = step.integer_coordinates;
+        if let Some(previous) = self.undo_log.back_mut() {
+            previous.generation = self.simpl_struct.current_generation();
+        }
+        Ok(())
+    }
+
+    /// Removes a vertex from the triangulation, the hole being retriangulated
+    ///
+    /// The vertex coordinates are kept, so that vertex indices are preserved.
+    pub fn remove_vertex(&mut self, ind_vertex: usize) -> Result<()> {
+        self.remove_vertices(&[ind_vertex])
+    }
+
+    /// Removes a set of vertices from the triangulation
+    ///
+    /// The vertices are removed topologically one after the other, then the combined hole is
+    /// made Delaunay at once. The vertex coordinates are kept, so that vertex indices are
+    /// preserved. If a vertex cannot be removed (remaining vertices would be aligned), no vertex
+    /// is removed and the structure is left Delaunay.
+    pub fn remove_vertices(&mut self, indices: &[usize]) -> Result<()> {
+        let mut to_remove = HashSet::new();
+        for &ind in indices.iter() {
+            self.get_vertex(ind)?;
+            if self.simpl_struct.get_node_halfedge(ind).is_none() {
+                return Err(anyhow::Error::msg("Vertex not in triangulation"));
+            }
+            if self.constraints.iter().any(|cstr| cstr.contains(&ind)) {
+                return Err(anyhow::Error::msg("Cannot remove constrained vertex"));
+            }
+            if self.is_fixed(ind) {
+                return Err(anyhow::Error::msg("Cannot remove fixed vertex"));
+            }
+            if !to_remove.insert(ind) {
+                return Err(anyhow::Error::msg("Vertex removed twice"));
+            }
+        }
+        // previous removals are undone if a vertex cannot be removed, a single vertex being
+        // only flipped around before failing
+        let snapshot = (indices.len() > 1).then(|| self.simpl_struct.clone());
+        let mut hole_edges = Vec::new();
+        let mut removal = Ok(());
+        for &ind in indices.iter() {
+            removal = self.remove_vertex_topology(ind, &mut hole_edges);
+            if removal.is_err() {
+                if let Some(simpl_struct) = snapshot {
+                    self.simpl_struct = simpl_struct;
+                    return removal;
+                }
+                break;
+            }
+        }
+
+        self.he_to_evaluate.clear();
+        for &[node1, node2] in hole_edges.iter() {
+            if let Some(ind_he) = self.halfedge_between_nodes(node1, node2) {
+                self.he_to_evaluate.push(ind_he);
+            }
+        }
+        self.flip_evaluated_halfedges(&Self::should_flip_halfedge)?;
+        let simpl_struct = &self.simpl_struct;
+        self.merged_vertices
+            .retain(|_, ind_existing| simpl_struct.get_node_halfedge(*ind_existing).is_some());
+        removal
+    }
+
+    fn halfedge_between_nodes(&self, node1: Node, node2: Node) -> Option<usize> {
+        match (node1, node2) {
+            (Node::Value(ind1), _) => self
+                .simpl_struct
+                .get_outgoing_halfedges(ind1)
+                .into_iter()
+                .find(|he| he.last_node().equals(&node2))
+                .map(|he| he.ind()),
+            (Node::Infinity, Node::Value(ind2)) => self
+                .simpl_struct
+                .get_outgoing_halfedges(ind2)
+                .into_iter()
+                .find(|he| he.last_node().equals(&Node::Infinity))
+                .map(|he| he.opposite_halfedge().ind()),
+            (Node::Infinity, Node::Infinity) => None,
+        }
+    }
+
+    fn node_coords(&self, node: Node) -> Option<[f64; 2]> {
+        match node {
+            Node::Value(ind) => Some(self.vertex_coordinates[ind]),
+            Node::Infinity => None,
+        }
+    }
+
+    // valid hull turn (interior on the right), possibly flat but not backwards
+    fn is_hull_turn_valid(&self, pt0: [f64; 2], pt1: [f64; 2], pt2: [f64; 2]) -> bool {
+        let sign = self.orient(pt0, pt1, pt2);
+        sign < 0.
+            || (sign == 0.
+                && (pt1[0] - pt0[0]) * (pt2[0] - pt1[0]) + (pt1[1] - pt0[1]) * (pt2[1] - pt1[1])
+                    > 0.)
+    }
+
+    // checks if collapsing the fan of outgoing halfedges onto the last node of the chosen one
+    // gives a valid triangulation
+    fn is_collapse_valid(&self, fan: &[usize], ind_fan: usize) -> Result<bool> {
+        let nb_fan = fan.len();
+        let fan_he = |shift: usize| {
+            self.simpl_struct
+                .get_halfedge(fan[(ind_fan + shift) % nb_fan])
+        };
+        let node_target = fan_he(0)?.last_node();
+        let link: Vec<Node> = (1..nb_fan)
+            .map(|shift| fan_he(shift).map(|he| he.last_node()))
+            .collect::<Result<_>>()?;
+
+        // finite triangles of the new fan should be counterclockwise
+        let mut nb_finite_new = 0;
+        for win in link.windows(2) {
+            if let (Some(pt0), Some(pt1), Some(pt2)) = (
+                self.node_coords(node_target),
+                self.node_coords(win[0]),
+                self.node_coords(win[1]),
+            ) {
+                if self.orient(pt0, pt1, pt2) <= 0. {
+                    return Ok(false);
+                }
+                nb_finite_new += 1;
             }
-            (Node::Value(ind_node_a), Node::Value(_), Node::Infinity, Node::Value(_)) => {
-                Ok(self.is_triangle_flat(ind_tri_abd)?
-                    || self.is_vertex_strict_in_circle(ind_node_a, ind_tri_bcd)?)
+        }
+
+        let shift_inf = match (0..nb_fan).find(|&shift| {
+            fan_he(shift)
+                .map(|he| he.last_node().equals(&Node::Infinity))
+                .unwrap_or(false)
+        }) {
+            Some(shift_inf) => shift_inf,
+            // interior node
+            None => return Ok(true),
+        };
+
+        // hull node: hull ... -> before -> first -> removed -> last -> after -> ..., the new
+        // hull chain goes through the whole link if collapsed onto infinity, or directly from
+        // first to last if collapsed onto one of them
+        let he_inf = fan_he(shift_inf)?;
+        let he_last = fan_he(shift_inf + nb_fan - 1)?;
+        let nodes_chain = if shift_inf == 0 {
+            link.clone()
+        } else if shift_inf == 1 || shift_inf == nb_fan - 1 {
+            vec![he_inf.prev_halfedge().first_node(), he_last.last_node()]
+        } else {
+            return Ok(false);
+        };
+        let node_before = he_inf
+            .next_halfedge()
+            .opposite_halfedge()
+            .prev_halfedge()
+            .first_node();
+        let node_after = he_last
+            .next_halfedge()
+            .opposite_halfedge()
+            .prev_halfedge()
+            .first_node();
+        let mut chain = Vec::new();
+        for node in [node_before]
+            .iter()
+            .chain(nodes_chain.iter())
+            .chain([node_after].iter())
+        {
+            match self.node_coords(*node) {
+                Some(pt) => chain.push(pt),
+                None => return Ok(false),
             }
-            (
-                Node::Value(ind_node_a),
-                Node::Value(ind_node_b),
-                Node::Value(ind_node_c),
-                Node::Infinity,
-            ) => {
-                let pt_a = self.get_vertices()[ind_node_a];
-                let pt_b = self.get_vertices()[ind_node_b];
-                let pt_c = self.get_vertices()[ind_node_c];
-                Ok(is_convex(pt_a, pt_b, pt_c) == 1)
+        }
+        if !chain
+            .windows(3)
+            .all(|win| self.is_hull_turn_valid(win[0], win[1], win[2]))
+        {
+            return Ok(false);
+        }
+
+        // some finite triangle should remain
+        let mut nb_infinite = 0;
+        let he_first = he_inf.opposite_halfedge();
+        let mut he_cur = he_first;
+        loop {
+            nb_infinite += 1;
+            he_cur = he_cur.prev_halfedge().opposite_halfedge();
+            if he_cur.ind() == he_first.ind() {
+                break;
             }
-            (_, _, _, _) => Err(anyhow::Error::msg("Multiple infinity linked together")),
         }
+        let nb_finite = self.simpl_struct.get_nb_triangles() - nb_infinite;
+        Ok(nb_finite + nb_finite_new > nb_fan - 2)
     }
 
-    fn insert_vertex_helper(&mut self, ind_vertex: usize, near_to: usize) -> Result<()> {
-        let now = Instant::now();
-        let ind_triangle = self.walk_by_visibility(ind_vertex, near_to)?;
+    // checks if flipping an outgoing halfedge gives two valid finite triangles
+    fn is_removal_flip_valid(&self, ind_he: usize) -> Result<bool> {
+        let he = self.simpl_struct.get_halfedge(ind_he)?;
+        let nodes = [
+            he.first_node(),
+            he.last_node(),
+            he.prev_halfedge().first_node(),
+            he.opposite_halfedge().prev_halfedge().first_node(),
+        ];
+        if let [Some(pt_v), Some(pt_w), Some(pt_x), Some(pt_y)] =
+            nodes.map(|node| self.node_coords(node))
+        {
+            Ok(self.orient(pt_x, pt_v, pt_y) > 0. && self.orient(pt_y, pt_w, pt_x) > 0.)
+        } else {
+            Ok(false)
+        }
+    }
 
-        let duration = now.elapsed();
-        let milli = duration.as_nanos();
-        self.walk_ms = self.walk_ms + milli;
+    // removes vertex by flipping its edges until it can be collapsed on a neighbor, records
+    // the edges of the hole
+    fn remove_vertex_topology(
+        &mut self,
+        ind_vertex: usize,
+        hole_edges: &mut Vec<[Node; 2]>,
+    ) -> Result<()> {
+        let outgoing = self.simpl_struct.get_outgoing_halfedges(ind_vertex);
+        for he in outgoing.iter() {
+            hole_edges.push([he.last_node(), he.next_halfedge().last_node()]);
+        }
 
-        let now = Instant::now();
-        let mut he_to_evaluate = Vec::new();
-        let [he1, he2, he3] = self.simpl_struct.get_triangle(ind_triangle)?.halfedges();
-        he_to_evaluate.push(he1.opposite_halfedge().ind());
-        he_to_evaluate.push(he2.opposite_halfedge().ind());
-        he_to_evaluate.push(he3.opposite_halfedge().ind());
-        let _ = self
-            .simpl_struct
-            .insert_node_within_triangle(ind_vertex, ind_triangle)?;
+        loop {
+            let fan: Vec<usize> = self
+                .simpl_struct
+                .get_outgoing_halfedges(ind_vertex)
+                .iter()
+                .map(|he| he.ind())
+                .collect();
 
-        let duration = now.elapsed();
-        let milli = duration.as_nanos();
-        self.insert_ms = self.insert_ms + milli;
+            for ind_fan in 0..fan.len() {
+                if self.is_collapse_valid(&fan, ind_fan)? {
+                    let he = self.simpl_struct.get_halfedge(fan[ind_fan])?;
+                    let node_target = he.last_node();
+                    for &ind_he in fan.iter() {
+                        let node = self.simpl_struct.get_halfedge(ind_he)?.last_node();
+                        hole_edges.push([node_target, node]);
+                    }
+                    self.simpl_struct.collapse_halfedge(fan[ind_fan])?;
+                    return Ok(());
+                }
+            }
 
-        let now = Instant::now();
-        while let Some(ind_he) = he_to_evaluate.pop() {
-            if self.should_flip_halfedge(ind_he)? {
-                let he = self.get_simplicial().get_halfedge(ind_he)?;
-                let ind_he_add1 = he.prev_halfedge().opposite_halfedge().ind();
-                let ind_he_add2 = he.next_halfedge().opposite_halfedge().ind();
-                let ind_he_add3 = he
-                    .opposite_halfedge()
-                    .prev_halfedge()
-                    .opposite_halfedge()
-                    .ind();
-                let ind_he_add4 = he
-                    .opposite_halfedge()
-                    .next_halfedge()
-                    .opposite_halfedge()
-                    .ind();
-                self.simpl_struct.flip_halfedge(ind_he);
-                he_to_evaluate.push(ind_he_add1);
-                he_to_evaluate.push(ind_he_add2);
-                he_to_evaluate.push(ind_he_add3);
-                he_to_evaluate.push(ind_he_add4);
+            let mut flipped = false;
+            for &ind_he in fan.iter() {
+                if self.is_removal_flip_valid(ind_he)? {
+                    let he = self.simpl_struct.get_halfedge(ind_he)?;
+                    hole_edges.push([
+                        he.prev_halfedge().first_node(),
+                        he.opposite_halfedge().prev_halfedge().first_node(),
+                    ]);
+                    self.simpl_struct.flip_halfedge(ind_he)?;
+                    flipped = true;
+                    break;
+                }
+            }
+            if !flipped {
+                return Err(anyhow::Error::msg("Could not remove vertex"));
             }
         }
+    }
 
-        let duration = now.elapsed();
-        let milli = duration.as_nanos();
-        self.flip_ms = self.flip_ms + milli;
+    fn is_halfedge_constrained(&self, ind_he: usize) -> Result<bool> {
+        if self.constraints.is_empty() {
+            return Ok(false);
+        }
+        let he = self.get_simplicial().get_halfedge(ind_he)?;
+        if let (Node::Value(ind1), Node::Value(ind2)) = (he.first_node(), he.last_node()) {
+            Ok(self.is_constrained_edge(ind1, ind2))
+        } else {
+            Ok(false)
+        }
+    }
 
-        Ok(())
+    /// Checks if edge between two vertices is constrained
+    pub fn is_constrained_edge(&self, ind_a: usize, ind_b: usize) -> bool {
+        self.constraints
+            .contains(&[ind_a.min(ind_b), ind_a.max(ind_b)])
     }
 
-    fn insert_first_triangle(&mut self, indices_to_insert: &mut Vec<usize>) -> Result<()> {
-        let now = Instant::now();
-        // first triangle insertion
-        if self.get_vertices().len() == indices_to_insert.len() {
-            let ind1 = indices_to_insert.pop().unwrap();
-            let ind2 = indices_to_insert.pop().unwrap();
-            let pt1 = self.get_vertices()[ind1];
-            let pt2 = self.get_vertices()[ind2];
+    /// Gets constrained edges (sorted vertex indices)
+    pub fn get_constraints(&self) -> &BTreeSet<[usize; 2]> {
+        &self.constraints
+    }
 
-            let mut aligned = Vec::new();
+    /// Flood fills triangles from a seed triangle, without crossing constrained edges
+    ///
+    /// Returns, for each triangle index (infinite ones included), if it is reached from the seed.
+    /// Starting from an outside seed identifies the exterior, holes being unreached regions.
+    #[must_use = "query result should be used"]
+    pub fn flood_fill_domains(&self, seed_triangle: usize) -> Result<Vec<bool>> {
+        let nb_triangles = self.get_simplicial().get_nb_triangles();
+        check_index(seed_triangle, nb_triangles)?;
+        let mut reached = vec![false; nb_triangles];
+        reached[seed_triangle] = true;
+        let mut to_visit = vec![seed_triangle];
+        while let Some(ind_triangle) = to_visit.pop() {
+            for he in self
+                .get_simplicial()
+                .get_triangle(ind_triangle)?
+                .halfedges()
+            {
+                if self.is_halfedge_constrained(he.ind())? {
+                    continue;
+                }
+                let ind_opp = he.opposite_halfedge().triangle().ind();
+                if !reached[ind_opp] {
+                    reached[ind_opp] = true;
+                    to_visit.push(ind_opp);
+                }
+            }
+        }
+        Ok(reached)
+    }
 
-            loop {
-                if let Some(ind3) = indices_to_insert.pop() {
-                    let pt3 = self.get_vertices()[ind3];
+    fn side_of_segment(&self, seg: [[f64; 2]; 2], node: Node) -> Result<f64> {
+        if let Node::Value(ind) = node {
+            Ok(self.orient(seg[0], seg[1], self.get_vertices()[ind]))
+        } else {
+            Err(anyhow::Error::msg("Constraint crosses convex hull"))
+        }
+    }
 
-                    let sign = robust::orient2d(
-                        Coord {
-                            x: pt1[0],
-                            y: pt1[1],
-                        },
-                        Coord {
-                            x: pt2[0],
-                            y: pt2[1],
-                        },
-                        Coord {
-                            x: pt3[0],
-                            y: pt3[1],
-                        },
-                    );
+    /// Finds halfedges crossed by segment [ind_start, ind_end], stopping at first vertex lying on
+    /// the segment. Returns crossed edges (as pairs of vertices) and the reached vertex.
+    fn edges_crossed_by_segment(
+        &self,
+        ind_start: usize,
+        ind_end: usize,
+    ) -> Result<(Vec<[usize; 2]>, usize)> {
+        let pt_start = self.get_vertices()[ind_start];
+        let pt_end = self.get_vertices()[ind_end];
+        let seg = [pt_start, pt_end];
 
-                    if sign > 0. {
-                        self.simpl_struct.first_triangle([ind1, ind2, ind3])?
-                    } else if sign < 0. {
-                        self.simpl_struct.first_triangle([ind1, ind3, ind2])?
-                    } else {
-                        aligned.push(ind3);
-                        continue;
-                    };
-                } else {
-                    return Err(anyhow::Error::msg(
-                        "Could not find three non aligned points",
-                    ));
+        // find first crossed halfedge, within a triangle around start vertex
+        let mut he_cur = None;
+        for he in self.get_simplicial().get_outgoing_halfedges(ind_start) {
+            let node_p = he.last_node();
+            let node_q = he.next_halfedge().last_node();
+            if let Node::Value(ind_p) = node_p {
+                let side_p = self.side_of_segment(seg, node_p)?;
+                let pt_p = self.get_vertices()[ind_p];
+                let scal = (pt_p[0] - pt_start[0]) * (pt_end[0] - pt_start[0])
+                    + (pt_p[1] - pt_start[1]) * (pt_end[1] - pt_start[1]);
+                if side_p == 0. && scal > 0. {
+                    // vertex lying on segment
+                    return Ok((Vec::new(), ind_p));
+                }
+                if let Node::Value(_) = node_q {
+                    let side_q = self.side_of_segment(seg, node_q)?;
+                    if side_p < 0. && side_q > 0. {
+                        he_cur = Some(he.next_halfedge());
+                        break;
+                    }
                 }
+            }
+        }
+        let mut he_cur = he_cur.ok_or(anyhow::Error::msg(
+            "Could not find edge crossed by constraint",
+        ))?;
 
-                break;
+        let mut crossed = Vec::new();
+        loop {
+            if let (Node::Value(ind1), Node::Value(ind2)) =
+                (he_cur.first_node(), he_cur.last_node())
+            {
+                if self.is_constrained_edge(ind1, ind2) {
+                    return Err(anyhow::Error::msg("Constraints are crossing"));
+                }
+                crossed.push([ind1, ind2]);
             }
+            let he_opp = he_cur.opposite_halfedge();
+            let node_r = he_opp.prev_halfedge().first_node();
+            if let Node::Value(ind_r) = node_r {
+                if ind_r == ind_end {
+                    return Ok((crossed, ind_end));
+                }
+            }
+            let side_r = self.side_of_segment(seg, node_r)?;
+            if side_r == 0. {
+                if let Node::Value(ind_r) = node_r {
+                    return Ok((crossed, ind_r));
+                }
+            } else if side_r < 0. {
+                he_cur = he_opp.prev_halfedge();
+            } else {
+                he_cur = he_opp.next_halfedge();
+            }
+        }
+    }
 
-            indices_to_insert.append(&mut aligned);
+    /// Forces edge [ind_start, ind_end] in the graph by flipping crossed edges (Sloan algorithm)
+    ///
+    /// Returns the reached vertex, and the edges created by flips.
+    fn force_edge(&mut self, ind_start: usize, ind_end: usize) -> Result<(usize, Vec<[usize; 2]>)> {
+        let (crossed, ind_reached) = self.edges_crossed_by_segment(ind_start, ind_end)?;
+        let seg = [
+            self.get_vertices()[ind_start],
+            self.get_vertices()[ind_reached],
+        ];
+
+        let mut to_flip: VecDeque<[usize; 2]> = crossed.into_iter().collect();
+        let mut new_edges = Vec::new();
+        let mut nb_failed = 0;
+        while let Some([ind_u, ind_w]) = to_flip.pop_front() {
+            let he = self
+                .get_simplicial()
+                .get_halfedge_between(ind_u, ind_w)
+                .ok_or(anyhow::Error::msg("Crossed edge not found"))?;
+            let node_x = he.prev_halfedge().first_node();
+            let node_y = he.opposite_halfedge().prev_halfedge().first_node();
+            let (ind_x, ind_y) = if let (Node::Value(ind_x), Node::Value(ind_y)) = (node_x, node_y)
+            {
+                (ind_x, ind_y)
+            } else {
+                return Err(anyhow::Error::msg("Constraint crosses convex hull"));
+            };
+            let pt_x = self.get_vertices()[ind_x];
+            let pt_y = self.get_vertices()[ind_y];
+            let side_u = self.orient(pt_x, pt_y, self.get_vertices()[ind_u]);
+            let side_w = self.orient(pt_x, pt_y, self.get_vertices()[ind_w]);
+            if side_u * side_w >= 0. {
+                // non convex quadrilateral, will be flipped later
+                nb_failed += 1;
+                if nb_failed > to_flip.len() {
+                    return Err(anyhow::Error::msg("Could not insert constraint"));
+                }
+                to_flip.push_back([ind_u, ind_w]);
+                continue;
+            }
+            nb_failed = 0;
+            let ind_he = he.ind();
+            self.simpl_struct.flip_halfedge(ind_he)?;
+
+            let side_x = self.orient(seg[0], seg[1], pt_x);
+            let side_y = self.orient(seg[0], seg[1], pt_y);
+            if side_x * side_y < 0. {
+                to_flip.push_back([ind_x, ind_y]);
+            } else {
+                new_edges.push([ind_x, ind_y]);
+            }
         }
-        let duration = now.elapsed();
-        let nano = duration.as_nanos();
-        log::info!("First triangle computed in {}ms", nano as f32 / 1e6);
-        Ok(())
+
+        Ok((ind_reached, new_edges))
     }
 
-    /// insert a single vertex in the structure
-    pub fn insert_vertex(&mut self, vertex: [f64; 2], near_to: Option<usize>) -> Result<()> {
-        if self.simpl_struct.get_nb_triangles() == 0 {
+    /// Inserts a constrained edge between two vertices already in the graph
+    ///
+    /// Edges crossing the constraint are flipped until the constraint appears in the graph. If
+    /// vertices lie on the constraint, it is split into several constrained edges. Edges created
+    /// by the flips are then flipped back to Delaunay on both sides of the constraint, giving a
+    /// constrained Delaunay triangulation. Constrained edges are never flipped by next
+    /// insertions.
+    pub fn insert_constraint(&mut self, ind_a: usize, ind_b: usize) -> Result<()> {
+        if ind_a == ind_b {
             return Err(anyhow::Error::msg(
-                "Needs at least 1 triangle to insert a single point",
+                "Constraint needs two different vertices",
             ));
         }
-        let indices_to_insert = self.vertex_coordinates.len();
-        self.vertex_coordinates.push(vertex);
-        self.insert_vertex_helper(
-            indices_to_insert,
-            near_to.unwrap_or(self.simpl_struct.get_nb_triangles() - 1),
-        )?;
-        log::info!("Walks computed in {}ms", self.walk_ms as f32 / 1e6);
-        log::info!("Insertions computed in {}ms", self.insert_ms as f32 / 1e6);
-        log::info!("Flips computed in {}ms", self.flip_ms as f32 / 1e6);
+        self.get_vertex(ind_a)?;
+        self.get_vertex(ind_b)?;
+        if self.get_simplicial().get_node_halfedge(ind_a).is_none()
+            || self.get_simplicial().get_node_halfedge(ind_b).is_none()
+        {
+            return Err(anyhow::Error::msg("Constraint vertex not in graph"));
+        }
+
+        let mut new_edges = Vec::new();
+        let mut ind_start = ind_a;
+        while ind_start != ind_b {
+            let ind_reached = if self
+                .get_simplicial()
+                .get_halfedge_between(ind_start, ind_b)
+                .is_some()
+            {
+                ind_b
+            } else {
+                let (ind_reached, edges) = self.force_edge(ind_start, ind_b)?;
+                new_edges.extend(edges);
+                ind_reached
+            };
+            self.constraints
+                .insert([ind_start.min(ind_reached), ind_start.max(ind_reached)]);
+            ind_start = ind_reached;
+        }
+
+        // restores Delaunay property within the polygons bounded by the constraint
+        self.he_to_evaluate.clear();
+        for [ind_x, ind_y] in new_edges {
+            if let Some(he) = self.get_simplicial().get_halfedge_between(ind_x, ind_y) {
+                self.he_to_evaluate.push(he.ind());
+            }
+        }
+        self.flip_evaluated_halfedges(&Self::should_flip_halfedge)?;
+
         Ok(())
     }
 
-    /// insert a set of vertices in the structure
-    pub fn insert_vertices(
-        &mut self,
-        to_insert: &Vec<[f64; 2]>,
-        reorder_points: bool,
-    ) -> Result<()> {
-        let mut indices_to_insert = Vec::new();
-        for &vert in to_insert.iter() {
-            indices_to_insert.push(self.vertex_coordinates.len());
-            self.vertex_coordinates.push(vert);
+    /// Checks constrained Delaunay property: no vertex visible across a non constrained edge lies
+    /// strictly within the circumcircle of a triangle
+    #[must_use = "query result should be used"]
+    pub fn is_constrained_delaunay(&self) -> Result<bool> {
+        if !self.get_simplicial().is_valid()? {
+            return Ok(false);
         }
 
-        if self.get_vertices().len() < 3 {
-            return Err(anyhow::Error::msg(
-                "Needs at least 3 vertices to compute Delaunay",
-            ));
+        let mut valid = true;
+        for ind_tri in 0..self.get_simplicial().get_nb_triangles() {
+            if self.is_triangle_flat(ind_tri)? {
+                log::error!(
+                    "Flat triangle: {}",
+                    self.get_simplicial().get_triangle(ind_tri)?
+                );
+                valid = false;
+            }
+            for he in self.get_simplicial().get_triangle(ind_tri)?.halfedges() {
+                if self.is_halfedge_constrained(he.ind())? {
+                    continue;
+                }
+                if let Node::Value(ind_apex) = self.get_simplicial().opposite_apex(he.ind())? {
+                    if self.is_vertex_strict_in_circle(ind_apex, ind_tri)? {
+                        log::error!(
+                            "Non constrained Delaunay triangle: {}",
+                            self.get_simplicial().get_triangle(ind_tri)?
+                        );
+                        valid = false;
+                    }
+                }
+            }
         }
 
-        if reorder_points {
-            let now = Instant::now();
-            indices_to_insert = build_hilbert_curve(self.get_vertices(), &indices_to_insert);
-            let duration = now.elapsed();
-            let nano = duration.as_nanos();
-            log::info!("Hilbert curve computed in {}ms", nano as f32 / 1e6);
+        Ok(valid)
+    }
+
+    /// Lists constrained edges whose diametral circle strictly contains a visible vertex (the
+    /// apex of one of the two neighbor triangles), known as encroached segments
+    #[must_use = "query result should be used"]
+    pub fn encroached_constraints(&self) -> Result<Vec<[usize; 2]>> {
+        let mut encroached = Vec::new();
+        for &[ind_a, ind_b] in self.constraints.iter() {
+            let he = self
+                .get_simplicial()
+                .get_halfedge_between(ind_a, ind_b)
+                .ok_or(anyhow::Error::msg("Constrained edge not in graph"))?;
+            let pt_a = self.get_vertices()[ind_a];
+            let pt_b = self.get_vertices()[ind_b];
+            let apex1 = he.prev_halfedge().first_node();
+            let apex2 = he.opposite_halfedge().prev_halfedge().first_node();
+            let is_encroached = [apex1, apex2].iter().any(|apex| {
+                if let Node::Value(ind_apex) = apex {
+                    is_in_diametral_circle(pt_a, pt_b, self.get_vertices()[*ind_apex])
+                } else {
+                    false
+                }
+            });
+            if is_encroached {
+                encroached.push([ind_a, ind_b]);
+            }
         }
+        Ok(encroached)
+    }
 
-        if self.simpl_struct.get_nb_triangles() == 0 {
-            self.insert_first_triangle(&mut indices_to_insert)?;
+    /// Finite triangles whose centroid lies inside a simple polygon, or with split, finite
+    /// triangles restricted to the polygon, triangles crossing its boundary being split
+    ///
+    /// Returns the triangles and the intersection vertices created by splitting, indexed after
+    /// graph vertices. Intersection vertices are shared by the pieces of adjacent triangles.
+    #[must_use = "query result should be used"]
+    pub fn clip_to_polygon(&self, polygon: &[[f64; 2]], split: bool) -> Result<ClippedTriangles> {
+        let nb_vertices = self.get_vertices().len();
+        let mut created = Vec::new();
+        let mut polygon_vertices: HashMap<usize, usize> = HashMap::new();
+        let mut crossings: HashMap<([usize; 2], usize), usize> = HashMap::new();
+        let mut clipped = Vec::new();
+        for ind_tri in 0..self.get_simplicial().get_nb_triangles() {
+            let [Node::Value(ind1), Node::Value(ind2), Node::Value(ind3)] =
+                self.get_simplicial().get_triangle(ind_tri)?.nodes()
+            else {
+                continue;
+            };
+            let mut inds = [ind1, ind2, ind3];
+            let mut tri = inds.map(|ind| self.get_vertices()[ind]);
+            if !split {
+                let centroid = [
+                    (tri[0][0] + tri[1][0] + tri[2][0]) / 3.,
+                    (tri[0][1] + tri[1][1] + tri[2][1]) / 3.,
+                ];
+                if is_in_polygon(polygon, centroid) {
+                    clipped.push(inds);
+                }
+                continue;
+            }
+
+            if orient_2d(tri[0], tri[1], tri[2]) < 0. {
+                inds.swap(1, 2);
+                tri.swap(1, 2);
+            }
+            let sub_polygon = clip_polygon_by_triangle_tracked(polygon, &tri);
+            if sub_polygon.len() < 3 {
+                continue;
+            }
+
+            let mut sub_inds = Vec::with_capacity(sub_polygon.len());
+            for &(pt, corner) in sub_polygon.iter() {
+                let ind = match corner {
+                    ClipCorner::Triangle(i) => inds[i],
+                    ClipCorner::Polygon(j) => match tri.iter().position(|&pt_tri| pt_tri == pt) {
+                        Some(i) => inds[i],
+                        None => *polygon_vertices.entry(j).or_insert_with(|| {
+                            created.push(pt);
+                            nb_vertices + created.len() - 1
+                        }),
+                    },
+                    ClipCorner::Crossing(i, j) => {
+                        let edge = [
+                            inds[i].min(inds[(i + 1) % 3]),
+                            inds[i].max(inds[(i + 1) % 3]),
+                        ];
+                        *crossings.entry((edge, j)).or_insert_with(|| {
+                            // computed from the edge as stored, identical for both triangles
+                            let [pt_a, pt_b] = edge.map(|ind| self.get_vertices()[ind]);
+                            let (pt_q0, pt_q1) = (polygon[j], polygon[(j + 1) % polygon.len()]);
+                            let side_a = orient_2d(pt_q0, pt_q1, pt_a);
+                            let side_b = orient_2d(pt_q0, pt_q1, pt_b);
+                            created.push(if side_a != side_b {
+                                let t = side_a / (side_a - side_b);
+                                [
+                                    pt_a[0] + t * (pt_b[0] - pt_a[0]),
+                                    pt_a[1] + t * (pt_b[1] - pt_a[1]),
+                                ]
+                            } else {
+                                pt
+                            });
+                            nb_vertices + created.len() - 1
+                        })
+                    }
+                };
+                sub_inds.push(ind);
+            }
+            let coords: Vec<[f64; 2]> = sub_polygon.iter().map(|&(pt, _)| pt).collect();
+            for [i1, i2, i3] in triangulate_polygon(&coords) {
+                clipped.push([sub_inds[i1], sub_inds[i2], sub_inds[i3]]);
+            }
         }
+        Ok((clipped, created))
+    }
 
-        loop {
-            if let Some(ind_vertex) = indices_to_insert.pop() {
-                self.insert_vertex_helper(ind_vertex, self.simpl_struct.get_nb_triangles() - 1)?;
-            } else {
-                break;
+    /// Writes structure in binary format (little endian), with constraints and fixed vertices but
+    /// without triangle attributes
+    #[cfg(feature = "std")]
+    pub fn write_bin<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        write_header(w, BINARY_MAGIC_2D)?;
+
+        write_u64(w, self.get_vertices().len() as u64)?;
+        for vert in self.get_vertices().iter() {
+            write_f64(w, vert[0])?;
+            write_f64(w, vert[1])?;
+        }
+
+        let (halfedge_first_node, halfedge_opposite) = self.get_simplicial().raw_halfedges();
+        write_u64(w, halfedge_first_node.len() as u64)?;
+        for node in halfedge_first_node.iter() {
+            match node {
+                Node::Infinity => write_u64(w, BINARY_INFINITY)?,
+                Node::Value(ind) => write_u64(w, *ind as u64)?,
             }
         }
-        log::info!("Walks computed in {}ms", self.walk_ms as f32 / 1e6);
-        log::info!("Insertions computed in {}ms", self.insert_ms as f32 / 1e6);
-        log::info!("Flips computed in {}ms", self.flip_ms as f32 / 1e6);
+        for &ind_opp in halfedge_opposite.iter() {
+            write_u64(w, ind_opp as u64)?;
+        }
 
+        write_u64(w, self.constraints.len() as u64)?;
+        for &[ind1, ind2] in self.constraints.iter() {
+            write_u64(w, ind1 as u64)?;
+            write_u64(w, ind2 as u64)?;
+        }
+
+        let fixed: Vec<usize> = (0..self.fixed.len())
+            .filter(|&ind| self.fixed[ind])
+            .collect();
+        write_u64(w, fixed.len() as u64)?;
+        for &ind in fixed.iter() {
+            write_u64(w, ind as u64)?;
+        }
         Ok(())
     }
+}
+
+#[cfg(feature = "std")]
+impl DelaunayStructure2D {
+    /// Reads structure from binary format
+    pub fn read_bin<R: Read>(r: &mut R) -> Result<DelaunayStructure2D> {
+        Self::read_bin_with_attributes(r)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<A> DelaunayStructure2D<A> {
+    /// Reads structure from binary format, storing triangle attributes (none being set)
+    pub fn read_bin_with_attributes<R: Read>(r: &mut R) -> Result<DelaunayStructure2D<A>> {
+        let version = read_header(r, BINARY_MAGIC_2D)?;
+        let mut del = Self::new_with_attributes();
+
+        let nb_vertices = read_u64(r)?;
+        for _ in 0..nb_vertices {
+            let x = read_f64(r)?;
+            let y = read_f64(r)?;
+            del.vertex_coordinates.push([x, y]);
+        }
+
+        let nb_halfedges = read_u64(r)?;
+        let mut halfedge_first_node = Vec::new();
+        for _ in 0..nb_halfedges {
+            let val = read_u64(r)?;
+            if val == BINARY_INFINITY {
+                halfedge_first_node.push(Node::Infinity);
+            } else {
+                check_index(val as usize, nb_vertices as usize)?;
+                halfedge_first_node.push(Node::Value(val as usize));
+            }
+        }
+        let mut halfedge_opposite = Vec::new();
+        for _ in 0..nb_halfedges {
+            halfedge_opposite.push(read_u64(r)? as usize);
+        }
+        del.simpl_struct =
+            SimplicialStructure2D::from_raw_halfedges(halfedge_first_node, halfedge_opposite)?;
+        // opposite halfedges join the same nodes in reverse order
+        if !del.simpl_struct.check_opposite_involution()? {
+            return Err(anyhow::Error::msg("Inconsistent halfedge arrays"));
+        }
+
+        let nb_constraints = read_u64(r)?;
+        for _ in 0..nb_constraints {
+            let ind1 = read_u64(r)? as usize;
+            let ind2 = read_u64(r)? as usize;
+            check_index(ind1, nb_vertices as usize)?;
+            check_index(ind2, nb_vertices as usize)?;
+            del.constraints.insert([ind1.min(ind2), ind1.max(ind2)]);
+        }
+
+        // fixed vertices stored since version 2
+        if version >= 2 {
+            let nb_fixed = read_u64(r)?;
+            for _ in 0..nb_fixed {
+                let ind = read_u64(r)? as usize;
+                check_index(ind, nb_vertices as usize)?;
+                del.set_fixed(ind, true)?;
+            }
+        }
+        Ok(del)
+    }
+}
+
+impl<A> DelaunayStructure2D<A> {
+    /// Gets vertices lifted onto the paraboloid z = x² + y², whose lower convex hull projects onto
+    /// the Delaunay triangulation
+    pub fn lifted_vertices(&self) -> Vec<[f64; 3]> {
+        self.vertex_coordinates
+            .iter()
+            .map(|pt| [pt[0], pt[1], pt[0] * pt[0] + pt[1] * pt[1]])
+            .collect()
+    }
+
+    /// Checks that lifted finite triangles are lower convex hull faces: no lifted apex of a
+    /// neighbor triangle lies strictly below the plane of a lifted triangle
+    ///
+    /// Independent of the in-circle predicate, up to the rounding of lifted heights.
+    #[must_use = "query result should be used"]
+    pub fn is_lower_hull_consistent(&self) -> Result<bool> {
+        let lifted = self.lifted_vertices();
+        let mut consistent = true;
+        for (ind_tri, tri, _) in self.triangles_with_neighbors() {
+            for &ind_apex in self.neighbor_apices(ind_tri)?.iter().flatten() {
+                // counterclockwise triangle, apex below its plane gives a positive orientation
+                let sign = orient_3d(
+                    lifted[tri[0]],
+                    lifted[tri[1]],
+                    lifted[tri[2]],
+                    lifted[ind_apex],
+                );
+                if sign > 0. {
+                    log::error!("Lifted vertex {} below triangle {}", ind_apex, ind_tri);
+                    consistent = false;
+                }
+            }
+        }
+        Ok(consistent)
+    }
+
+    /// Checks that no three vertices are aligned and no four are cocircular, with exact
+    /// predicates, among local configurations: triangles, consecutive hull vertices, and the
+    /// vertices of two adjacent triangles
+    ///
+    /// Aligned or cocircular vertices that are not neighbors in the triangulation are not
+    /// detected. A true result still guarantees that the Delaunay triangulation is unique.
+    /// Duplicated vertices break general position.
+    #[must_use = "query result should be used"]
+    pub fn is_in_general_position(&self) -> Result<bool> {
+        let simpl = self.get_simplicial();
+        if (0..self.vertex_coordinates.len()).any(|ind| simpl.get_node_halfedge(ind).is_none()) {
+            return Ok(false);
+        }
+        let integer = self.integer_coordinates;
+        for (ind_tri, tri, _) in self.triangles_with_neighbors() {
+            let [pt0, pt1, pt2] = tri.map(|ind| self.vertex_coordinates[ind]);
+            if self.orient(pt0, pt1, pt2) == 0. {
+                return Ok(false);
+            }
+            let [pt0, pt1, pt2] = if self.orient(pt0, pt1, pt2) < 0. {
+                [pt0, pt2, pt1]
+            } else {
+                [pt0, pt1, pt2]
+            };
+            for ind_apex in self.neighbor_apices(ind_tri)?.into_iter().flatten() {
+                let pt = self.vertex_coordinates[ind_apex];
+                let sign = if integer {
+                    in_circle_int(to_int(pt0), to_int(pt1), to_int(pt2), to_int(pt)) as f64
+                } else {
+                    in_circle(pt0, pt1, pt2, pt)
+                };
+                if sign == 0. {
+                    return Ok(false);
+                }
+            }
+        }
+        let hull = self.convex_hull()?;
+        for ind in 0..hull.len() {
+            let [pt0, pt1, pt2] =
+                [0, 1, 2].map(|shift| self.vertex_coordinates[hull[(ind + shift) % hull.len()]]);
+            if self.orient(pt0, pt1, pt2) == 0. {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
 
     /// Checks Delaunay graph validity (unit tests purpose)
+    #[must_use = "query result should be used"]
     pub fn is_valid(&self) -> Result<bool> {
         let mut valid = true;
 
@@ -454,15 +4068,23 @@ impl DelaunayStructure2D {
 
         for ind_tri in 0..self.get_simplicial().get_nb_triangles() {
             if self.is_triangle_flat(ind_tri)? {
-                log::error!("Flat triangle: ");
-                self.get_simplicial().get_triangle(ind_tri)?.println();
+                log::error!(
+                    "Flat triangle: {}",
+                    self.get_simplicial().get_triangle(ind_tri)?
+                );
                 valid = false;
             }
             for ind_vert in 0..self.vertex_coordinates.len() {
+                if self.get_simplicial().get_node_halfedge(ind_vert).is_none() {
+                    // registered but not inserted
+                    continue;
+                }
                 let in_circle = self.is_vertex_strict_in_circle(ind_vert, ind_tri)?;
                 if in_circle {
-                    log::error!("Non Delaunay triangle: ");
-                    self.get_simplicial().get_triangle(ind_tri)?.println();
+                    log::error!(
+                        "Non Delaunay triangle: {}",
+                        self.get_simplicial().get_triangle(ind_tri)?
+                    );
                     valid = false;
                 }
             }
@@ -470,4 +4092,52 @@ impl DelaunayStructure2D {
 
         Ok(valid)
     }
+
+    // checks that triangle is not flat, and that no neighbor apex lies strictly in its circle
+    #[cfg(feature = "rayon")]
+    fn is_triangle_locally_valid(&self, ind_tri: usize) -> Result<bool> {
+        if self.is_triangle_flat(ind_tri)? {
+            log::error!(
+                "Flat triangle: {}",
+                self.get_simplicial().get_triangle(ind_tri)?
+            );
+            return Ok(false);
+        }
+        for he in self.get_simplicial().get_triangle(ind_tri)?.halfedges() {
+            if let Node::Value(ind_apex) = he.opposite_halfedge().prev_halfedge().first_node() {
+                if self.is_vertex_strict_in_circle(ind_apex, ind_tri)? {
+                    log::error!(
+                        "Non Delaunay triangle: {}",
+                        self.get_simplicial().get_triangle(ind_tri)?
+                    );
+                    return Ok(false);
+                }
+            }
+        }
+        Ok(true)
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<A: Sync> DelaunayStructure2D<A> {
+    /// Checks Delaunay graph validity with local certificates checked in parallel
+    ///
+    /// Each triangle is checked against its neighbors only, which is equivalent to the global
+    /// check of [`Self::is_valid`] on a valid simplicial structure. Stops at the first failure.
+    #[must_use = "query result should be used"]
+    pub fn is_valid_parallel(&self) -> Result<bool> {
+        use rayon::prelude::*;
+
+        if !self.get_simplicial().is_valid()? {
+            return Ok(false);
+        }
+        let failure = (0..self.get_simplicial().get_nb_triangles())
+            .into_par_iter()
+            .map(|ind_tri| self.is_triangle_locally_valid(ind_tri))
+            .find_any(|res| !matches!(res, Ok(true)));
+        match failure {
+            None => Ok(true),
+            Some(res) => res,
+        }
+    }
 }
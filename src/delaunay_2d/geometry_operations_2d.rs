@@ -1,7 +1,11 @@
+use alloc::vec::Vec;
 use robust::{self, Coord};
 
+use crate::compat::{acos, sqrt};
+use crate::rng::SplitMix64;
+
 /// Sorts vertices along 2D Hilbert curve
-pub fn build_hilbert_curve(vertices: &Vec<[f64; 2]>, indices_to_add: &Vec<usize>) -> Vec<usize> {
+pub fn build_hilbert_curve(vertices: &[[f64; 2]], indices_to_add: &[usize]) -> Vec<usize> {
     let mut curve_order = Vec::new();
 
     let mut pt_min = vertices[indices_to_add[0]];
@@ -23,95 +27,94 @@ pub fn build_hilbert_curve(vertices: &Vec<[f64; 2]>, indices_to_add: &Vec<usize>
     }
 
     let mut to_subdiv = Vec::new();
-    let indices: Vec<usize> = indices_to_add.iter().map(|&x| x).collect();
+    let indices: Vec<usize> = indices_to_add.to_vec();
     to_subdiv.push((0, pt_min, pt_max, indices));
 
-    loop {
-        if let Some((rot, pt_min, pt_max, indices_to_add)) = to_subdiv.pop() {
-            if indices_to_add.len() > 1 {
-                let sep_x = (pt_min[0] + pt_max[0]) / 2.0;
-                let sep_y = (pt_min[1] + pt_max[1]) / 2.0;
-
-                let mut ind_a = Vec::new();
-                let mut ind_b = Vec::new();
-                let mut ind_c = Vec::new();
-                let mut ind_d = Vec::new();
-
-                for &ind in indices_to_add.iter() {
-                    let vert = vertices[ind];
-                    if vert[0] < sep_x {
-                        if vert[1] < sep_y {
-                            ind_a.push(ind);
-                        } else {
-                            ind_b.push(ind);
-                        }
+    while let Some((rot, pt_min, pt_max, indices_to_add)) = to_subdiv.pop() {
+        let sep_x = (pt_min[0] + pt_max[0]) / 2.0;
+        let sep_y = (pt_min[1] + pt_max[1]) / 2.0;
+        // cells too small to be split only hold duplicated vertices
+        let splittable =
+            (pt_min[0] < sep_x && sep_x < pt_max[0]) || (pt_min[1] < sep_y && sep_y < pt_max[1]);
+
+        if indices_to_add.len() > 1 && splittable {
+            let mut ind_a = Vec::new();
+            let mut ind_b = Vec::new();
+            let mut ind_c = Vec::new();
+            let mut ind_d = Vec::new();
+
+            for &ind in indices_to_add.iter() {
+                let vert = vertices[ind];
+                if vert[0] < sep_x {
+                    if vert[1] < sep_y {
+                        ind_a.push(ind);
+                    } else {
+                        ind_b.push(ind);
+                    }
+                } else {
+                    if vert[1] < sep_y {
+                        ind_d.push(ind);
                     } else {
-                        if vert[1] < sep_y {
-                            ind_d.push(ind);
-                        } else {
-                            ind_c.push(ind);
-                        }
+                        ind_c.push(ind);
                     }
                 }
+            }
 
-                let pt_a_min = pt_min;
-                let pt_a_max = [sep_x, sep_y];
-
-                let pt_b_min = [pt_min[0], sep_y];
-                let pt_b_max = [sep_x, pt_max[1]];
-
-                let pt_c_min = [sep_x, sep_y];
-                let pt_c_max = pt_max;
-
-                let pt_d_min = [sep_x, pt_min[1]];
-                let pt_d_max = [pt_max[0], sep_y];
-
-                if rot == 0 {
-                    to_subdiv.push((3, pt_a_min, pt_a_max, ind_a));
-                    to_subdiv.push((0, pt_b_min, pt_b_max, ind_b));
-                    to_subdiv.push((0, pt_c_min, pt_c_max, ind_c));
-                    to_subdiv.push((7, pt_d_min, pt_d_max, ind_d));
-                } else if rot == 1 {
-                    to_subdiv.push((6, pt_d_min, pt_d_max, ind_d));
-                    to_subdiv.push((1, pt_c_min, pt_c_max, ind_c));
-                    to_subdiv.push((1, pt_b_min, pt_b_max, ind_b));
-                    to_subdiv.push((2, pt_a_min, pt_a_max, ind_a));
-                } else if rot == 2 {
-                    to_subdiv.push((5, pt_b_min, pt_b_max, ind_b));
-                    to_subdiv.push((2, pt_c_min, pt_c_max, ind_c));
-                    to_subdiv.push((2, pt_d_min, pt_d_max, ind_d));
-                    to_subdiv.push((1, pt_a_min, pt_a_max, ind_a));
-                } else if rot == 3 {
-                    to_subdiv.push((0, pt_a_min, pt_a_max, ind_a));
-                    to_subdiv.push((3, pt_d_min, pt_d_max, ind_d));
-                    to_subdiv.push((3, pt_c_min, pt_c_max, ind_c));
-                    to_subdiv.push((4, pt_b_min, pt_b_max, ind_b));
-                } else if rot == 4 {
-                    to_subdiv.push((7, pt_c_min, pt_c_max, ind_c));
-                    to_subdiv.push((4, pt_d_min, pt_d_max, ind_d));
-                    to_subdiv.push((4, pt_a_min, pt_a_max, ind_a));
-                    to_subdiv.push((3, pt_b_min, pt_b_max, ind_b));
-                } else if rot == 5 {
-                    to_subdiv.push((2, pt_b_min, pt_b_max, ind_b));
-                    to_subdiv.push((5, pt_a_min, pt_a_max, ind_a));
-                    to_subdiv.push((5, pt_d_min, pt_d_max, ind_d));
-                    to_subdiv.push((6, pt_c_min, pt_c_max, ind_c));
-                } else if rot == 6 {
-                    to_subdiv.push((1, pt_d_min, pt_d_max, ind_d));
-                    to_subdiv.push((6, pt_a_min, pt_a_max, ind_a));
-                    to_subdiv.push((6, pt_b_min, pt_b_max, ind_b));
-                    to_subdiv.push((5, pt_c_min, pt_c_max, ind_c));
-                } else if rot == 7 {
-                    to_subdiv.push((4, pt_c_min, pt_c_max, ind_c));
-                    to_subdiv.push((7, pt_b_min, pt_b_max, ind_b));
-                    to_subdiv.push((7, pt_a_min, pt_a_max, ind_a));
-                    to_subdiv.push((0, pt_d_min, pt_d_max, ind_d));
-                }
-            } else if indices_to_add.len() == 1 {
-                curve_order.push(indices_to_add[0]);
+            let pt_a_min = pt_min;
+            let pt_a_max = [sep_x, sep_y];
+
+            let pt_b_min = [pt_min[0], sep_y];
+            let pt_b_max = [sep_x, pt_max[1]];
+
+            let pt_c_min = [sep_x, sep_y];
+            let pt_c_max = pt_max;
+
+            let pt_d_min = [sep_x, pt_min[1]];
+            let pt_d_max = [pt_max[0], sep_y];
+
+            if rot == 0 {
+                to_subdiv.push((3, pt_a_min, pt_a_max, ind_a));
+                to_subdiv.push((0, pt_b_min, pt_b_max, ind_b));
+                to_subdiv.push((0, pt_c_min, pt_c_max, ind_c));
+                to_subdiv.push((7, pt_d_min, pt_d_max, ind_d));
+            } else if rot == 1 {
+                to_subdiv.push((6, pt_d_min, pt_d_max, ind_d));
+                to_subdiv.push((1, pt_c_min, pt_c_max, ind_c));
+                to_subdiv.push((1, pt_b_min, pt_b_max, ind_b));
+                to_subdiv.push((2, pt_a_min, pt_a_max, ind_a));
+            } else if rot == 2 {
+                to_subdiv.push((5, pt_b_min, pt_b_max, ind_b));
+                to_subdiv.push((2, pt_c_min, pt_c_max, ind_c));
+                to_subdiv.push((2, pt_d_min, pt_d_max, ind_d));
+                to_subdiv.push((1, pt_a_min, pt_a_max, ind_a));
+            } else if rot == 3 {
+                to_subdiv.push((0, pt_a_min, pt_a_max, ind_a));
+                to_subdiv.push((3, pt_d_min, pt_d_max, ind_d));
+                to_subdiv.push((3, pt_c_min, pt_c_max, ind_c));
+                to_subdiv.push((4, pt_b_min, pt_b_max, ind_b));
+            } else if rot == 4 {
+                to_subdiv.push((7, pt_c_min, pt_c_max, ind_c));
+                to_subdiv.push((4, pt_d_min, pt_d_max, ind_d));
+                to_subdiv.push((4, pt_a_min, pt_a_max, ind_a));
+                to_subdiv.push((3, pt_b_min, pt_b_max, ind_b));
+            } else if rot == 5 {
+                to_subdiv.push((2, pt_b_min, pt_b_max, ind_b));
+                to_subdiv.push((5, pt_a_min, pt_a_max, ind_a));
+                to_subdiv.push((5, pt_d_min, pt_d_max, ind_d));
+                to_subdiv.push((6, pt_c_min, pt_c_max, ind_c));
+            } else if rot == 6 {
+                to_subdiv.push((1, pt_d_min, pt_d_max, ind_d));
+                to_subdiv.push((6, pt_a_min, pt_a_max, ind_a));
+                to_subdiv.push((6, pt_b_min, pt_b_max, ind_b));
+                to_subdiv.push((5, pt_c_min, pt_c_max, ind_c));
+            } else if rot == 7 {
+                to_subdiv.push((4, pt_c_min, pt_c_max, ind_c));
+                to_subdiv.push((7, pt_b_min, pt_b_max, ind_b));
+                to_subdiv.push((7, pt_a_min, pt_a_max, ind_a));
+                to_subdiv.push((0, pt_d_min, pt_d_max, ind_d));
             }
         } else {
-            break;
+            curve_order.extend(indices_to_add);
         }
     }
 
@@ -151,3 +154,397 @@ pub fn is_convex(pt0: [f64; 2], pt1: [f64; 2], pt2: [f64; 2]) -> i8 {
         }
     }
 }
+
+/// Orientation of triangle (pt0, pt1, pt2): positive if counterclockwise, negative if clockwise,
+/// zero if flat (robust predicate)
+pub fn orient_2d(pt0: [f64; 2], pt1: [f64; 2], pt2: [f64; 2]) -> f64 {
+    robust::orient2d(
+        Coord {
+            x: pt0[0],
+            y: pt0[1],
+        },
+        Coord {
+            x: pt1[0],
+            y: pt1[1],
+        },
+        Coord {
+            x: pt2[0],
+            y: pt2[1],
+        },
+    )
+}
+
+/// Position of pt relative to the circle through pt0, pt1, pt2 (counterclockwise): positive
+/// inside, negative outside, zero on the circle (robust predicate)
+pub fn in_circle(pt0: [f64; 2], pt1: [f64; 2], pt2: [f64; 2], pt: [f64; 2]) -> f64 {
+    robust::incircle(
+        Coord {
+            x: pt0[0],
+            y: pt0[1],
+        },
+        Coord {
+            x: pt1[0],
+            y: pt1[1],
+        },
+        Coord {
+            x: pt2[0],
+            y: pt2[1],
+        },
+        Coord { x: pt[0], y: pt[1] },
+    )
+}
+
+/// Position of pt relative to the circle through pt0, pt1, pt2 (counterclockwise): positive
+/// inside, negative outside, zero on the circle
+///
+/// Plain floating point determinant, without the robust error filter: faster, but the sign may be
+/// wrong for nearly cocircular or nearly flat configurations.
+pub fn in_circle_fast(pt0: [f64; 2], pt1: [f64; 2], pt2: [f64; 2], pt: [f64; 2]) -> f64 {
+    let rel = |p: [f64; 2]| {
+        let d = [p[0] - pt[0], p[1] - pt[1]];
+        [d[0], d[1], d[0] * d[0] + d[1] * d[1]]
+    };
+    let (a, b, c) = (rel(pt0), rel(pt1), rel(pt2));
+    a[2] * (b[0] * c[1] - b[1] * c[0]) - b[2] * (a[0] * c[1] - a[1] * c[0])
+        + c[2] * (a[0] * b[1] - a[1] * b[0])
+}
+
+/// checks if pt is strictly inside the circle of diameter [pt0, pt1]
+pub fn is_in_diametral_circle(pt0: [f64; 2], pt1: [f64; 2], pt: [f64; 2]) -> bool {
+    let ptpt0 = [pt0[0] - pt[0], pt0[1] - pt[1]];
+    let ptpt1 = [pt1[0] - pt[0], pt1[1] - pt[1]];
+
+    ptpt0[0] * ptpt1[0] + ptpt0[1] * ptpt1[1] < 0.
+}
+
+/// Largest absolute value of integer coordinates for which integer predicates do not overflow
+pub const INT_COORD_LIMIT: i32 = 1 << 29;
+
+/// Orientation of triangle (pt0, pt1, pt2) with integer coordinates, exact: positive if
+/// counterclockwise, negative if clockwise, zero if flat
+///
+/// Coordinates should not exceed [`INT_COORD_LIMIT`] in absolute value.
+pub fn orient_2d_int(pt0: [i32; 2], pt1: [i32; 2], pt2: [i32; 2]) -> i128 {
+    let u = [
+        pt1[0] as i128 - pt0[0] as i128,
+        pt1[1] as i128 - pt0[1] as i128,
+    ];
+    let v = [
+        pt2[0] as i128 - pt0[0] as i128,
+        pt2[1] as i128 - pt0[1] as i128,
+    ];
+    u[0] * v[1] - u[1] * v[0]
+}
+
+/// Position of pt relative to the circle through pt0, pt1, pt2 (counterclockwise) with integer
+/// coordinates, exact: positive inside, negative outside, zero on the circle
+///
+/// Coordinates should not exceed [`INT_COORD_LIMIT`] in absolute value.
+pub fn in_circle_int(pt0: [i32; 2], pt1: [i32; 2], pt2: [i32; 2], pt: [i32; 2]) -> i128 {
+    let rel = |p: [i32; 2]| {
+        let d = [p[0] as i128 - pt[0] as i128, p[1] as i128 - pt[1] as i128];
+        [d[0], d[1], d[0] * d[0] + d[1] * d[1]]
+    };
+    let (a, b, c) = (rel(pt0), rel(pt1), rel(pt2));
+    a[2] * (b[0] * c[1] - b[1] * c[0]) - b[2] * (a[0] * c[1] - a[1] * c[0])
+        + c[2] * (a[0] * b[1] - a[1] * b[0])
+}
+
+/// Checks if ang(pt1pt0, pt1pt2) is convex, flat, or concave, with integer coordinates (exact)
+///
+/// Same convention as [`is_convex`].
+pub fn is_convex_int(pt0: [i32; 2], pt1: [i32; 2], pt2: [i32; 2]) -> i8 {
+    let sign = orient_2d_int(pt0, pt1, pt2);
+    if sign > 0 {
+        1
+    } else if sign < 0 {
+        -1
+    } else {
+        let scal = (pt1[0] as i128 - pt0[0] as i128) * (pt1[0] as i128 - pt2[0] as i128)
+            + (pt1[1] as i128 - pt0[1] as i128) * (pt1[1] as i128 - pt2[1] as i128);
+        if scal > 0 {
+            1
+        } else {
+            0
+        }
+    }
+}
+
+/// Computes barycentric coordinates of pt within triangle, None if the triangle is flat
+pub fn barycentric_coordinates(tri: [[f64; 2]; 3], pt: [f64; 2]) -> Option<[f64; 3]> {
+    let area = (tri[1][0] - tri[0][0]) * (tri[2][1] - tri[0][1])
+        - (tri[1][1] - tri[0][1]) * (tri[2][0] - tri[0][0]);
+    if area == 0. {
+        return None;
+    }
+    let sub_area = |pa: [f64; 2], pb: [f64; 2]| {
+        (pb[0] - pa[0]) * (pt[1] - pa[1]) - (pb[1] - pa[1]) * (pt[0] - pa[0])
+    };
+    Some([
+        sub_area(tri[1], tri[2]) / area,
+        sub_area(tri[2], tri[0]) / area,
+        sub_area(tri[0], tri[1]) / area,
+    ])
+}
+
+/// checks if pt is inside a simple polygon (ray casting)
+pub fn is_in_polygon(polygon: &[[f64; 2]], pt: [f64; 2]) -> bool {
+    let mut inside = false;
+    let nb_pts = polygon.len();
+    for i in 0..nb_pts {
+        let pt0 = polygon[i];
+        let pt1 = polygon[(i + 1) % nb_pts];
+        if (pt0[1] > pt[1]) != (pt1[1] > pt[1]) {
+            let x_cross = pt0[0] + (pt[1] - pt0[1]) * (pt1[0] - pt0[0]) / (pt1[1] - pt0[1]);
+            if pt[0] < x_cross {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
+/// Clips a polygon by a counterclockwise triangle (Sutherland-Hodgman algorithm)
+pub fn clip_polygon_by_triangle(polygon: &[[f64; 2]], triangle: &[[f64; 2]; 3]) -> Vec<[f64; 2]> {
+    let mut clipped: Vec<[f64; 2]> = polygon.to_vec();
+    for i in 0..3 {
+        let edg0 = triangle[i];
+        let edg1 = triangle[(i + 1) % 3];
+        let to_clip = clipped;
+        clipped = Vec::new();
+        let nb_pts = to_clip.len();
+        for j in 0..nb_pts {
+            let pt0 = to_clip[j];
+            let pt1 = to_clip[(j + 1) % nb_pts];
+            let side0 = orient_2d(edg0, edg1, pt0);
+            let side1 = orient_2d(edg0, edg1, pt1);
+            if side0 >= 0. {
+                clipped.push(pt0);
+            }
+            if (side0 > 0. && side1 < 0.) || (side0 < 0. && side1 > 0.) {
+                let t = side0 / (side0 - side1);
+                clipped.push([
+                    pt0[0] + t * (pt1[0] - pt0[0]),
+                    pt0[1] + t * (pt1[1] - pt0[1]),
+                ]);
+            }
+        }
+    }
+    clipped
+}
+
+/// Triangulates a simple polygon by ear clipping, returns counterclockwise triangles as indices
+/// within polygon
+pub fn triangulate_polygon(polygon: &[[f64; 2]]) -> Vec<[usize; 3]> {
+    let mut triangles = Vec::new();
+
+    let mut area = 0.;
+    for i in 0..polygon.len() {
+        let pt0 = polygon[i];
+        let pt1 = polygon[(i + 1) % polygon.len()];
+        area += pt0[0] * pt1[1] - pt1[0] * pt0[1];
+    }
+    let mut remaining: Vec<usize> = if area >= 0. {
+        (0..polygon.len()).collect()
+    } else {
+        (0..polygon.len()).rev().collect()
+    };
+
+    while remaining.len() > 3 {
+        let nb_rem = remaining.len();
+        let mut ear_found = false;
+        for i in 0..nb_rem {
+            let ind_prev = remaining[(i + nb_rem - 1) % nb_rem];
+            let ind_cur = remaining[i];
+            let ind_next = remaining[(i + 1) % nb_rem];
+            let (pt_prev, pt_cur, pt_next) =
+                (polygon[ind_prev], polygon[ind_cur], polygon[ind_next]);
+            let sign = orient_2d(pt_prev, pt_cur, pt_next);
+            if sign < 0. {
+                continue;
+            }
+            if sign == 0. {
+                // flat vertex, removed without triangle
+                remaining.remove(i);
+                ear_found = true;
+                break;
+            }
+            let contains_other = remaining.iter().any(|&ind| {
+                if ind == ind_prev || ind == ind_cur || ind == ind_next {
+                    return false;
+                }
+                let pt = polygon[ind];
+                orient_2d(pt_prev, pt_cur, pt) >= 0.
+                    && orient_2d(pt_cur, pt_next, pt) >= 0.
+                    && orient_2d(pt_next, pt_prev, pt) >= 0.
+            });
+            if !contains_other {
+                triangles.push([ind_prev, ind_cur, ind_next]);
+                remaining.remove(i);
+                ear_found = true;
+                break;
+            }
+        }
+        if !ear_found {
+            break;
+        }
+    }
+    if remaining.len() == 3
+        && orient_2d(
+            polygon[remaining[0]],
+            polygon[remaining[1]],
+            polygon[remaining[2]],
+        ) > 0.
+    {
+        triangles.push([remaining[0], remaining[1], remaining[2]]);
+    }
+
+    triangles
+}
+
+/// Checks if a triangle is flat, up to a tolerance relative to its longest edge
+///
+/// The triangle is flat if its area is at most rel_eps times its squared longest edge length.
+/// A zero tolerance gives the exact test (robust orientation equal to zero).
+pub fn is_triangle_flat(pt0: [f64; 2], pt1: [f64; 2], pt2: [f64; 2], rel_eps: f64) -> bool {
+    let orient = orient_2d(pt0, pt1, pt2);
+    if rel_eps == 0. {
+        return orient == 0.;
+    }
+    let sq_longest = [(pt0, pt1), (pt1, pt2), (pt2, pt0)]
+        .iter()
+        .map(|(pa, pb)| (pb[0] - pa[0]) * (pb[0] - pa[0]) + (pb[1] - pa[1]) * (pb[1] - pa[1]))
+        .fold(0., f64::max);
+    0.5 * orient.abs() <= rel_eps * sq_longest
+}
+
+/// Radius ratio of a triangle, 1 for an equilateral triangle and 0 for a flat one
+///
+/// Computed as 2 r / R = (b + c - a)(c + a - b)(a + b - c) / (a b c), with r the inradius, R the
+/// circumradius and a, b, c the edge lengths.
+pub fn triangle_quality(pt0: [f64; 2], pt1: [f64; 2], pt2: [f64; 2]) -> f64 {
+    let len = |pa: [f64; 2], pb: [f64; 2]| {
+        sqrt((pb[0] - pa[0]) * (pb[0] - pa[0]) + (pb[1] - pa[1]) * (pb[1] - pa[1]))
+    };
+    let (a, b, c) = (len(pt1, pt2), len(pt2, pt0), len(pt0, pt1));
+    let product = a * b * c;
+    if product == 0. {
+        return 0.;
+    }
+    let quality = (b + c - a) * (c + a - b) * (a + b - c) / product;
+    quality.clamp(0., 1.)
+}
+
+/// Interior angles of a triangle in radians, angle i being at vertex pti, from the edge lengths
+/// (law of cosines)
+///
+/// Angles are zero at a vertex opposite to a zero length edge, and NaN at its ends.
+pub fn triangle_angles(pt0: [f64; 2], pt1: [f64; 2], pt2: [f64; 2]) -> [f64; 3] {
+    let sq_len = |pa: [f64; 2], pb: [f64; 2]| {
+        (pb[0] - pa[0]) * (pb[0] - pa[0]) + (pb[1] - pa[1]) * (pb[1] - pa[1])
+    };
+    let sq = [sq_len(pt1, pt2), sq_len(pt2, pt0), sq_len(pt0, pt1)];
+    [0, 1, 2].map(|ind| {
+        let (opp, side1, side2) = (sq[ind], sq[(ind + 1) % 3], sq[(ind + 2) % 3]);
+        let cos = (side1 + side2 - opp) / (2. * sqrt(side1 * side2));
+        acos(cos.clamp(-1., 1.))
+    })
+}
+
+/// Computes unit left normal and offset of the line through pt0 and pt1, such that points pt
+/// of the line verify normal . pt = factor, None if both points are equal
+pub fn line_normal_and_factor(pt0: [f64; 2], pt1: [f64; 2]) -> Option<([f64; 2], f64)> {
+    let dir = [pt1[0] - pt0[0], pt1[1] - pt0[1]];
+    let len = sqrt(dir[0] * dir[0] + dir[1] * dir[1]);
+    if len == 0. {
+        return None;
+    }
+    let normal = [-dir[1] / len, dir[0] / len];
+    let factor = normal[0] * pt0[0] + normal[1] * pt0[1];
+    Some((normal, factor))
+}
+
+/// Computes circumcircle center of a triangle, None if the triangle is flat
+pub fn circle_center(pt0: [f64; 2], pt1: [f64; 2], pt2: [f64; 2]) -> Option<[f64; 2]> {
+    circle_center_with_tolerance(pt0, pt1, pt2, 0.)
+}
+
+/// Computes circumcircle center of a triangle, None if the triangle is flat up to the relative
+/// tolerance (see is_triangle_flat)
+pub fn circle_center_with_tolerance(
+    pt0: [f64; 2],
+    pt1: [f64; 2],
+    pt2: [f64; 2],
+    rel_eps: f64,
+) -> Option<[f64; 2]> {
+    let vec1 = [pt1[0] - pt0[0], pt1[1] - pt0[1]];
+    let vec2 = [pt2[0] - pt0[0], pt2[1] - pt0[1]];
+    let det = 2. * (vec1[0] * vec2[1] - vec1[1] * vec2[0]);
+    if det == 0. || is_triangle_flat(pt0, pt1, pt2, rel_eps) {
+        return None;
+    }
+    let sq1 = vec1[0] * vec1[0] + vec1[1] * vec1[1];
+    let sq2 = vec2[0] * vec2[0] + vec2[1] * vec2[1];
+    Some([
+        pt0[0] + (vec2[1] * sq1 - vec1[1] * sq2) / det,
+        pt0[1] + (vec1[0] * sq2 - vec2[0] * sq1) / det,
+    ])
+}
+
+fn distance_2d(pt0: [f64; 2], pt1: [f64; 2]) -> f64 {
+    sqrt((pt1[0] - pt0[0]) * (pt1[0] - pt0[0]) + (pt1[1] - pt0[1]) * (pt1[1] - pt0[1]))
+}
+
+fn diametral_circle(pt0: [f64; 2], pt1: [f64; 2]) -> ([f64; 2], f64) {
+    (
+        [(pt0[0] + pt1[0]) * 0.5, (pt0[1] + pt1[1]) * 0.5],
+        distance_2d(pt0, pt1) * 0.5,
+    )
+}
+
+fn enclosing_circle_3(pt0: [f64; 2], pt1: [f64; 2], pt2: [f64; 2]) -> ([f64; 2], f64) {
+    match circle_center(pt0, pt1, pt2) {
+        Some(ctr) => (ctr, distance_2d(ctr, pt0)),
+        None => {
+            // aligned points: diametral circle of the farthest pair
+            let cand = [(pt0, pt1), (pt1, pt2), (pt2, pt0)];
+            let (pa, pb) = cand
+                .into_iter()
+                .max_by(|(pa1, pb1), (pa2, pb2)| {
+                    distance_2d(*pa1, *pb1).total_cmp(&distance_2d(*pa2, *pb2))
+                })
+                .unwrap();
+            diametral_circle(pa, pb)
+        }
+    }
+}
+
+/// Computes minimal enclosing circle (center, radius) of a set of points (Welzl algorithm, in
+/// its iterative form, on a shuffled copy for expected linear time), None if there is no point
+pub fn minimal_enclosing_circle(points: &[[f64; 2]]) -> Option<([f64; 2], f64)> {
+    let is_outside =
+        |(ctr, rad): ([f64; 2], f64), pt: [f64; 2]| distance_2d(ctr, pt) > rad * (1. + 1e-12);
+
+    let mut points = points.to_vec();
+    SplitMix64::new(0).shuffle(&mut points);
+
+    let mut circle = (*points.first()?, 0.);
+    for i in 1..points.len() {
+        if !is_outside(circle, points[i]) {
+            continue;
+        }
+        circle = (points[i], 0.);
+        for j in 0..i {
+            if !is_outside(circle, points[j]) {
+                continue;
+            }
+            circle = diametral_circle(points[i], points[j]);
+            for k in 0..j {
+                if is_outside(circle, points[k]) {
+                    circle = enclosing_circle_3(points[i], points[j], points[k]);
+                }
+            }
+        }
+    }
+    Some(circle)
+}
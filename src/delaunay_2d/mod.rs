@@ -6,3 +6,11 @@ pub mod geometry_operations_2d;
 
 /// Optimised 2D simplicial structure (no geometry)
 pub mod simplicial_struct_2d;
+
+/// Point trait for user vertex types
+pub mod point_2d;
+
+/// Voronoi diagram as a navigable structure
+pub mod voronoi_2d;
+
+mod spatial_hint_2d;
@@ -0,0 +1,31 @@
+/// 2D point type accepted as input vertex
+pub trait Point2 {
+    /// Gets point coordinates
+    fn xy(&self) -> [f64; 2];
+}
+
+impl Point2 for [f64; 2] {
+    fn xy(&self) -> [f64; 2] {
+        *self
+    }
+}
+
+impl Point2 for (f64, f64) {
+    fn xy(&self) -> [f64; 2] {
+        [self.0, self.1]
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl Point2 for nalgebra::Vector2<f64> {
+    fn xy(&self) -> [f64; 2] {
+        [self.x, self.y]
+    }
+}
+
+#[cfg(feature = "glam")]
+impl Point2 for glam::DVec2 {
+    fn xy(&self) -> [f64; 2] {
+        [self.x, self.y]
+    }
+}
@@ -1,6 +1,12 @@
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
 use anyhow::Result;
+use core::fmt;
 use log;
 
+use crate::error::check_index;
+
 /// Node in the graph, can be at infinity
 #[derive(Copy, Clone)]
 pub enum Node {
@@ -20,26 +26,33 @@ impl Node {
             (_, _) => false,
         }
     }
+}
 
-    /// Node to string
-    pub fn to_string(&self) -> String {
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Node::Infinity => "Node Infinity".to_string(),
-            Node::Value(val) => format!("Node {}", val),
+            Node::Infinity => write!(f, "Node Infinity"),
+            Node::Value(val) => write!(f, "Node {}", val),
         }
     }
+}
 
+#[cfg(feature = "std")]
+impl Node {
     /// Print node string
-    pub fn print(&self) -> () {
-        print!("{}", self.to_string());
+    #[deprecated(note = "use the Display implementation")]
+    pub fn print(&self) {
+        std::print!("{}", self);
     }
 
     /// Println node string
-    pub fn println(&self) -> () {
-        println!("{}", self.to_string());
+    #[deprecated(note = "use the Display implementation")]
+    pub fn println(&self) {
+        std::println!("{}", self);
     }
 }
 
+#[derive(Clone)]
 /// 2D Simplicial structure
 pub struct SimplicialStructure2D {
     // i   : he1 \
@@ -52,7 +65,21 @@ pub struct SimplicialStructure2D {
     halfedge_first_node: Vec<Node>,
     halfedge_opposite: Vec<usize>,
 
+    // for each finite node, index of one halfedge starting from it (usize::MAX if none)
+    node_halfedge: Vec<usize>,
+
     nb_triangles: usize,
+
+    // modification counter, and generation of last modification of each triangle
+    generation: u64,
+    triangle_generation: Vec<u64>,
+
+    // optional label of each triangle, inherited by the triangles replacing it
+    triangle_label: Option<Vec<usize>>,
+
+    // number of triangles of each label, and labels which may have no triangle anymore
+    label_count: Vec<usize>,
+    unused_labels: Vec<usize>,
 }
 
 #[derive(Copy, Clone)]
@@ -69,51 +96,380 @@ pub struct IterTriangle<'a> {
     ind_triangle: usize,
 }
 
+impl Default for SimplicialStructure2D {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl SimplicialStructure2D {
     /// Simplicial structure initialisation
     pub fn new() -> SimplicialStructure2D {
         SimplicialStructure2D {
             halfedge_first_node: Vec::new(),
             halfedge_opposite: Vec::new(),
+            node_halfedge: Vec::new(),
             nb_triangles: 0,
+            generation: 0,
+            triangle_generation: Vec::new(),
+            triangle_label: None,
+            label_count: Vec::new(),
+            unused_labels: Vec::new(),
         }
     }
 
+    /// Reserves capacity for the insertion of nb_nodes more nodes (two more triangles each)
+    pub fn reserve(&mut self, nb_nodes: usize) {
+        self.halfedge_first_node.reserve(6 * nb_nodes);
+        self.halfedge_opposite.reserve(6 * nb_nodes);
+        self.node_halfedge.reserve(nb_nodes);
+        self.triangle_generation.reserve(2 * nb_nodes);
+    }
+
     /// Gets halfedge iterator from index
-    pub fn get_halfedge(&self, ind_halfedge: usize) -> Result<IterHalfEdge> {
-        if ind_halfedge < self.halfedge_first_node.len() {
-            Ok(IterHalfEdge {
+    #[must_use = "query result should be used"]
+    pub fn get_halfedge(&self, ind_halfedge: usize) -> Result<IterHalfEdge<'_>> {
+        check_index(ind_halfedge, self.halfedge_first_node.len())?;
+        Ok(IterHalfEdge {
+            simplicial: self,
+            ind_halfedge,
+        })
+    }
+
+    /// Gets triangle iterator from index
+    #[must_use = "query result should be used"]
+    pub fn get_triangle(&self, ind_triangle: usize) -> Result<IterTriangle<'_>> {
+        check_index(ind_triangle, self.nb_triangles)?;
+        Ok(IterTriangle {
+            simplicial: self,
+            ind_triangle,
+        })
+    }
+
+    /// Gets number of triangles
+    pub fn get_nb_triangles(&self) -> usize {
+        self.nb_triangles
+    }
+
+    /// Gets a halfedge starting from given node, if node is in the structure
+    pub fn get_node_halfedge(&self, node: usize) -> Option<IterHalfEdge<'_>> {
+        match self.node_halfedge.get(node) {
+            Some(&ind_halfedge) if ind_halfedge != usize::MAX => Some(IterHalfEdge {
                 simplicial: self,
                 ind_halfedge,
-            })
-        } else {
-            Err(anyhow::Error::msg("Halfedge value not in simplicial"))
+            }),
+            _ => None,
         }
     }
 
-    /// Gets triangle iterator from index
-    pub fn get_triangle(&self, ind_triangle: usize) -> Result<IterTriangle> {
-        if ind_triangle < self.nb_triangles {
-            Ok(IterTriangle {
-                simplicial: self,
-                ind_triangle,
+    /// Gets halfedges starting from given node, in counterclockwise order
+    pub fn get_outgoing_halfedges(&self, node: usize) -> Vec<IterHalfEdge<'_>> {
+        let mut vec_he = Vec::new();
+        if let Some(he_first) = self.get_node_halfedge(node) {
+            let mut he_cur = he_first;
+            loop {
+                vec_he.push(he_cur);
+                he_cur = he_cur.prev_halfedge().opposite_halfedge();
+                if he_cur.ind() == he_first.ind() {
+                    break;
+                }
+            }
+        }
+        vec_he
+    }
+
+    /// Gets halfedge going from node1 to node2, if it exists
+    pub fn get_halfedge_between(&self, node1: usize, node2: usize) -> Option<IterHalfEdge<'_>> {
+        self.get_outgoing_halfedges(node1)
+            .into_iter()
+            .find(|he| he.last_node().equals(&Node::Value(node2)))
+    }
+
+    /// Gets node of the adjacent triangle across halfedge which is not on the halfedge (apex)
+    #[must_use = "query result should be used"]
+    pub fn opposite_apex(&self, ind_halfedge: usize) -> Result<Node> {
+        Ok(self
+            .get_halfedge(ind_halfedge)?
+            .opposite_halfedge()
+            .prev_halfedge()
+            .first_node())
+    }
+
+    /// Permutes triangle storage, new_order giving for each new triangle index the old one
+    ///
+    /// All triangles are marked as modified.
+    pub fn permute_triangles(&mut self, new_order: &[usize]) -> Result<()> {
+        if new_order.len() != self.nb_triangles {
+            return Err(anyhow::Error::msg(
+                "Permutation size differs from triangle count",
+            ));
+        }
+        let mut old_to_new = vec![usize::MAX; self.nb_triangles];
+        for (ind_new, &ind_old) in new_order.iter().enumerate() {
+            if ind_old >= self.nb_triangles || old_to_new[ind_old] != usize::MAX {
+                return Err(anyhow::Error::msg("Not a permutation of triangles"));
+            }
+            old_to_new[ind_old] = ind_new;
+        }
+        let new_halfedge = |ind_he: usize| 3 * old_to_new[ind_he / 3] + ind_he % 3;
+
+        let mut halfedge_first_node = Vec::with_capacity(3 * self.nb_triangles);
+        let mut halfedge_opposite = Vec::with_capacity(3 * self.nb_triangles);
+        for &ind_old in new_order.iter() {
+            for ind_he in 3 * ind_old..3 * ind_old + 3 {
+                halfedge_first_node.push(self.halfedge_first_node[ind_he]);
+                halfedge_opposite.push(new_halfedge(self.halfedge_opposite[ind_he]));
+            }
+        }
+        self.halfedge_first_node = halfedge_first_node;
+        self.halfedge_opposite = halfedge_opposite;
+        for ind_he in self.node_halfedge.iter_mut() {
+            if *ind_he != usize::MAX {
+                *ind_he = new_halfedge(*ind_he);
+            }
+        }
+        self.generation += 1;
+        self.triangle_generation = vec![self.generation; self.nb_triangles];
+        if let Some(labels) = self.triangle_label.as_mut() {
+            *labels = new_order.iter().map(|&ind_old| labels[ind_old]).collect();
+        }
+        Ok(())
+    }
+
+    /// Marks all triangles as modified by the generation following given one, for a structure
+    /// rebuilt from scratch
+    pub(crate) fn mark_rebuilt(&mut self, previous_generation: u64) {
+        self.generation = self.generation.max(previous_generation) + 1;
+        self.triangle_generation = vec![self.generation; self.nb_triangles];
+    }
+
+    /// Gets current modification generation
+    pub fn current_generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Gets triangles created or modified after given generation
+    pub fn triangles_changed_since(&self, generation: u64) -> Vec<usize> {
+        (0..self.nb_triangles)
+            .filter(|&ind_tri| self.triangle_generation[ind_tri] > generation)
+            .collect()
+    }
+
+    /// Gets label of a triangle, None if labels are disabled or not set
+    pub(crate) fn get_label(&self, ind_triangle: usize) -> Option<usize> {
+        self.triangle_label
+            .as_ref()
+            .and_then(|labels| labels.get(ind_triangle).copied())
+            .filter(|&label| label != usize::MAX)
+    }
+
+    /// Sets label of a triangle, starting labelling (other labels set to usize::MAX) if needed
+    pub(crate) fn set_label(&mut self, ind_triangle: usize, label: usize) -> Result<()> {
+        check_index(ind_triangle, self.nb_triangles)?;
+        if let Some(label_old) = self.get_label(ind_triangle) {
+            self.release_label(label_old);
+        }
+        let nb_triangles = self.nb_triangles;
+        self.triangle_label
+            .get_or_insert_with(|| vec![usize::MAX; nb_triangles])[ind_triangle] = label;
+        self.retain_label(label);
+        Ok(())
+    }
+
+    /// Checks if a label is set on several triangles
+    pub(crate) fn is_label_shared(&self, label: usize) -> bool {
+        self.label_count.get(label).is_some_and(|&count| count > 1)
+    }
+
+    /// Takes a label no triangle has anymore, if any
+    pub(crate) fn take_unused_label(&mut self) -> Option<usize> {
+        while let Some(label) = self.unused_labels.pop() {
+            // labels set again after being released are skipped
+            if self.label_count[label] == 0 {
+                return Some(label);
+            }
+        }
+        None
+    }
+
+    fn retain_label(&mut self, label: usize) {
+        if label != usize::MAX {
+            if label >= self.label_count.len() {
+                self.label_count.resize(label + 1, 0);
+            }
+            self.label_count[label] += 1;
+        }
+    }
+
+    fn release_label(&mut self, label: usize) {
+        self.label_count[label] -= 1;
+        if self.label_count[label] == 0 {
+            self.unused_labels.push(label);
+        }
+    }
+
+    /// Raw halfedge arrays (first nodes, opposites)
+    #[cfg(feature = "std")]
+    pub(crate) fn raw_halfedges(&self) -> (&Vec<Node>, &Vec<usize>) {
+        (&self.halfedge_first_node, &self.halfedge_opposite)
+    }
+
+    /// Builds structure from raw halfedge arrays
+    pub(crate) fn from_raw_halfedges(
+        halfedge_first_node: Vec<Node>,
+        halfedge_opposite: Vec<usize>,
+    ) -> Result<SimplicialStructure2D> {
+        if halfedge_first_node.len() % 3 != 0
+            || halfedge_first_node.len() != halfedge_opposite.len()
+            || halfedge_opposite
+                .iter()
+                .enumerate()
+                .any(|(ind_he, &ind_opp)| {
+                    ind_opp >= halfedge_opposite.len()
+                        || ind_opp == ind_he
+                        || halfedge_opposite[ind_opp] != ind_he
+                })
+        {
+            return Err(anyhow::Error::msg("Inconsistent halfedge arrays"));
+        }
+        let mut simpl = SimplicialStructure2D {
+            nb_triangles: halfedge_first_node.len() / 3,
+            halfedge_first_node,
+            halfedge_opposite,
+            node_halfedge: Vec::new(),
+            generation: 0,
+            triangle_generation: Vec::new(),
+            triangle_label: None,
+            label_count: Vec::new(),
+            unused_labels: Vec::new(),
+        };
+        simpl.triangle_generation = vec![0; simpl.nb_triangles];
+        for ind_he in 0..simpl.halfedge_first_node.len() {
+            simpl.set_node_halfedge(simpl.halfedge_first_node[ind_he], ind_he);
+        }
+        Ok(simpl)
+    }
+
+    /// Builds structure from counterclockwise finite triangles covering a convex domain, adding
+    /// infinite triangles along the boundary
+    pub(crate) fn from_triangles(triangles: &[[usize; 3]]) -> Result<SimplicialStructure2D> {
+        #[cfg(feature = "rayon")]
+        use rayon::prelude::*;
+
+        let nb_nodes = triangles.iter().flatten().max().map_or(0, |&ind| ind + 1);
+        let first_node = |ind_he: usize| triangles[ind_he / 3][ind_he % 3];
+        let last_node = |ind_he: usize| triangles[ind_he / 3][(ind_he + 1) % 3];
+
+        // outgoing halfedges of each node, sorted by node
+        let mut offsets = vec![0; nb_nodes + 1];
+        for &ind_node in triangles.iter().flatten() {
+            offsets[ind_node + 1] += 1;
+        }
+        for ind_node in 0..nb_nodes {
+            offsets[ind_node + 1] += offsets[ind_node];
+        }
+        let mut outgoing = vec![0; offsets[nb_nodes]];
+        let mut filled = offsets.clone();
+        for ind_he in 0..3 * triangles.len() {
+            let ind_node = first_node(ind_he);
+            outgoing[filled[ind_node]] = ind_he;
+            filled[ind_node] += 1;
+        }
+
+        #[cfg(feature = "rayon")]
+        let halfedges = (0..3 * triangles.len()).into_par_iter();
+        #[cfg(not(feature = "rayon"))]
+        let halfedges = 0..3 * triangles.len();
+        let opposites: Vec<Option<usize>> = halfedges
+            .map(|ind_he| {
+                let (ind_a, ind_b) = (first_node(ind_he), last_node(ind_he));
+                let mut matching = outgoing[offsets[ind_b]..offsets[ind_b + 1]]
+                    .iter()
+                    .filter(|&&ind_opp| last_node(ind_opp) == ind_a);
+                match (matching.next(), matching.next()) {
+                    (opp, None) => Ok(opp.copied()),
+                    _ => Err(anyhow::Error::msg("Non manifold triangles")),
+                }
             })
-        } else {
-            Err(anyhow::Error::msg("Triangle value not in simplicial"))
+            .collect::<Result<_>>()?;
+
+        let mut halfedge_first_node: Vec<Node> = triangles
+            .iter()
+            .flatten()
+            .map(|&ind_node| Node::Value(ind_node))
+            .collect();
+        let mut halfedge_opposite = Vec::with_capacity(halfedge_first_node.len());
+
+        // infinite triangle (b, a, infinity) facing each boundary halfedge from a to b
+        let mut to_infinity = vec![None; nb_nodes];
+        let mut from_infinity = vec![None; nb_nodes];
+        let mut infinite_halfedges = Vec::new();
+        let mut boundary = Vec::new();
+        for (ind_he, opp) in opposites.into_iter().enumerate() {
+            if let Some(ind_opp) = opp {
+                halfedge_opposite.push(ind_opp);
+                continue;
+            }
+            let (ind_a, ind_b) = (first_node(ind_he), last_node(ind_he));
+            let ind_first = 3 * triangles.len() + infinite_halfedges.len();
+            infinite_halfedges.extend([Node::Value(ind_b), Node::Value(ind_a), Node::Infinity]);
+            halfedge_opposite.push(ind_first);
+            boundary.push((ind_he, ind_first));
+            if to_infinity[ind_a].replace(ind_first + 1).is_some()
+                || from_infinity[ind_b].replace(ind_first + 2).is_some()
+            {
+                return Err(anyhow::Error::msg("Non manifold boundary"));
+            }
+        }
+        halfedge_first_node.append(&mut infinite_halfedges);
+        halfedge_opposite.resize(halfedge_first_node.len(), 0);
+        for (ind_he, ind_first) in boundary {
+            halfedge_opposite[ind_first] = ind_he;
+        }
+        for ind_node in 0..nb_nodes {
+            match (to_infinity[ind_node], from_infinity[ind_node]) {
+                (Some(ind_he), Some(ind_opp)) => {
+                    halfedge_opposite[ind_he] = ind_opp;
+                    halfedge_opposite[ind_opp] = ind_he;
+                }
+                (None, None) => (),
+                _ => return Err(anyhow::Error::msg("Open boundary")),
+            }
         }
+        SimplicialStructure2D::from_raw_halfedges(halfedge_first_node, halfedge_opposite)
     }
 
-    /// Gets number of triangles
-    pub fn get_nb_triangles(&self) -> usize {
-        self.nb_triangles
+    fn set_node_halfedge(&mut self, node: Node, ind_halfedge: usize) {
+        if let Node::Value(ind_node) = node {
+            if ind_node >= self.node_halfedge.len() {
+                self.node_halfedge.resize(ind_node + 1, usize::MAX);
+            }
+            self.node_halfedge[ind_node] = ind_halfedge;
+        }
     }
 
-    fn insert_triangle(&mut self, nod1: Node, nod2: Node, nod3: Node) -> (usize, usize, usize) {
+    // appends triangle, inheriting the label of the parent triangle
+    fn insert_triangle(
+        &mut self,
+        nod1: Node,
+        nod2: Node,
+        nod3: Node,
+        ind_parent: Option<usize>,
+    ) -> (usize, usize, usize) {
         let ind_first = self.halfedge_first_node.len();
         self.halfedge_first_node.push(nod1);
         self.halfedge_first_node.push(nod2);
         self.halfedge_first_node.push(nod3);
-        self.nb_triangles = self.nb_triangles + 1;
+        self.set_node_halfedge(nod1, ind_first);
+        self.set_node_halfedge(nod2, ind_first + 1);
+        self.set_node_halfedge(nod3, ind_first + 2);
+        self.nb_triangles += 1;
+        self.triangle_generation.push(self.generation);
+        if let Some(labels) = self.triangle_label.as_mut() {
+            let label = ind_parent.map_or(usize::MAX, |ind_parent| labels[ind_parent]);
+            labels.push(label);
+            self.retain_label(label);
+        }
 
         (ind_first, ind_first + 1, ind_first + 2)
     }
@@ -129,12 +485,16 @@ impl SimplicialStructure2D {
         self.halfedge_first_node[ind_first] = nod1;
         self.halfedge_first_node[ind_first + 1] = nod2;
         self.halfedge_first_node[ind_first + 2] = nod3;
+        self.set_node_halfedge(nod1, ind_first);
+        self.set_node_halfedge(nod2, ind_first + 1);
+        self.set_node_halfedge(nod3, ind_first + 2);
+        self.triangle_generation[ind_tri] = self.generation;
 
         (ind_first, ind_first + 1, ind_first + 2)
     }
 
     /// Inserts a first triangle in the structure
-    pub fn first_triangle(&mut self, nodes: [usize; 3]) -> Result<[IterTriangle; 4]> {
+    pub fn first_triangle(&mut self, nodes: [usize; 3]) -> Result<[IterTriangle<'_>; 4]> {
         if self.nb_triangles != 0 {
             return Err(anyhow::Error::msg("Already triangles in simplicial"));
         }
@@ -142,11 +502,12 @@ impl SimplicialStructure2D {
         let n1 = Node::Value(nodes[1]);
         let n2 = Node::Value(nodes[2]);
         let ninf = Node::Infinity;
+        self.generation += 1;
         let first_tri = self.nb_triangles;
-        let (h01, h12, h20) = self.insert_triangle(n0, n1, n2);
-        let (hi2, h21, h1i) = self.insert_triangle(ninf, n2, n1);
-        let (h2i, hi0, h02) = self.insert_triangle(n2, ninf, n0);
-        let (h10, h0i, hi1) = self.insert_triangle(n1, n0, ninf);
+        let (h01, h12, h20) = self.insert_triangle(n0, n1, n2, None);
+        let (hi2, h21, h1i) = self.insert_triangle(ninf, n2, n1, None);
+        let (h2i, hi0, h02) = self.insert_triangle(n2, ninf, n0, None);
+        let (h10, h0i, hi1) = self.insert_triangle(n1, n0, ninf, None);
 
         self.halfedge_opposite.push(h10);
         self.halfedge_opposite.push(h21);
@@ -182,14 +543,15 @@ impl SimplicialStructure2D {
     }
 
     /// Inserts a new node in a triangle
+    ///
+    /// Returns the three triangles around the new node, and the outer halfedges facing them
+    /// (opposite to the edges of the original triangle).
     pub fn insert_node_within_triangle(
         &mut self,
         node: usize,
         ind_tri: usize,
-    ) -> Result<[IterTriangle; 3]> {
-        if ind_tri > self.nb_triangles {
-            return Err(anyhow::Error::msg("Triangle index out of bounds"));
-        }
+    ) -> Result<([usize; 3], [usize; 3])> {
+        check_index(ind_tri, self.nb_triangles)?;
         let h01 = ind_tri * 3;
         let h12 = ind_tri * 3 + 1;
         let h20 = ind_tri * 3 + 2;
@@ -203,9 +565,10 @@ impl SimplicialStructure2D {
         let h21 = self.halfedge_opposite[h12];
         let h02 = self.halfedge_opposite[h20];
 
+        self.generation += 1;
         let (h01, h1n, hn0) = self.replace_triangle(ind_tri, n0, n1, nn);
-        let (h12, h2n, hn1) = self.insert_triangle(n1, n2, nn);
-        let (h20, h0n, hn2) = self.insert_triangle(n2, n0, nn);
+        let (h12, h2n, hn1) = self.insert_triangle(n1, n2, nn, Some(ind_tri));
+        let (h20, h0n, hn2) = self.insert_triangle(n2, n0, nn, Some(ind_tri));
 
         self.halfedge_opposite[h10] = h01;
         self.halfedge_opposite[h21] = h12;
@@ -213,34 +576,90 @@ impl SimplicialStructure2D {
         self.halfedge_opposite[h01] = h10;
         self.halfedge_opposite[h1n] = hn1;
         self.halfedge_opposite[hn0] = h0n;
-        self.halfedge_opposite.push(h21);
-        self.halfedge_opposite.push(hn2);
-        self.halfedge_opposite.push(h1n);
-        self.halfedge_opposite.push(h02);
-        self.halfedge_opposite.push(hn0);
-        self.halfedge_opposite.push(h2n);
+        self.halfedge_opposite
+            .extend_from_slice(&[h21, hn2, h1n, h02, hn0, h2n]);
 
-        Ok([
-            IterTriangle {
-                simplicial: self,
-                ind_triangle: ind_tri,
-            },
-            IterTriangle {
-                simplicial: self,
-                ind_triangle: self.nb_triangles - 2,
-            },
-            IterTriangle {
-                simplicial: self,
-                ind_triangle: self.nb_triangles - 1,
-            },
-        ])
+        Ok((
+            [ind_tri, self.nb_triangles - 2, self.nb_triangles - 1],
+            [h10, h21, h02],
+        ))
+    }
+
+    /// Inserts a new node on an edge, splitting its two adjacent triangles
+    ///
+    /// Returns the four triangles around the new node, and the outer halfedges facing them.
+    pub fn insert_node_on_edge(
+        &mut self,
+        node: usize,
+        ind_he: usize,
+    ) -> Result<([usize; 4], [usize; 4])> {
+        check_index(ind_he, self.halfedge_opposite.len())?;
+        // triangles (a, b, c) and (b, a, d) around halfedge a -> b
+        let ind_he_opp = self.halfedge_opposite[ind_he];
+        let ind_tri1 = ind_he / 3;
+        let ind_tri2 = ind_he_opp / 3;
+        let hbc = Self::next_halfedge_ind(ind_he);
+        let hca = Self::prev_halfedge_ind(ind_he);
+        let had = Self::next_halfedge_ind(ind_he_opp);
+        let hdb = Self::prev_halfedge_ind(ind_he_opp);
+
+        let na = self.halfedge_first_node[ind_he];
+        let nb = self.halfedge_first_node[ind_he_opp];
+        let nc = self.halfedge_first_node[hca];
+        let nd = self.halfedge_first_node[hdb];
+        let nm = Node::Value(node);
+
+        let hcb = self.halfedge_opposite[hbc];
+        let hac = self.halfedge_opposite[hca];
+        let hda = self.halfedge_opposite[had];
+        let hbd = self.halfedge_opposite[hdb];
+
+        self.generation += 1;
+        let (ham, hmc, hca) = self.replace_triangle(ind_tri1, na, nm, nc);
+        let (hbm, hmd, hdb) = self.replace_triangle(ind_tri2, nb, nm, nd);
+        let (hmb, hbc, hcm) = self.insert_triangle(nm, nb, nc, Some(ind_tri1));
+        let (hma, had, hdm) = self.insert_triangle(nm, na, nd, Some(ind_tri2));
+        self.halfedge_opposite
+            .resize(self.halfedge_first_node.len(), usize::MAX);
+
+        for (ind1, ind2) in [
+            (ham, hma),
+            (hmb, hbm),
+            (hmc, hcm),
+            (hmd, hdm),
+            (hca, hac),
+            (hbc, hcb),
+            (hdb, hbd),
+            (had, hda),
+        ] {
+            self.halfedge_opposite[ind1] = ind2;
+            self.halfedge_opposite[ind2] = ind1;
+        }
+
+        Ok((
+            [
+                ind_tri1,
+                ind_tri2,
+                self.nb_triangles - 2,
+                self.nb_triangles - 1,
+            ],
+            [hac, hcb, hbd, hda],
+        ))
     }
 
     /// Flips halfedge
-    pub fn flip_halfedge(&mut self, ind_he: usize) -> () {
+    ///
+    /// The structure is left untouched if the halfedge cannot be flipped.
+    pub fn flip_halfedge(&mut self, ind_he: usize) -> Result<()> {
+        check_index(ind_he, self.halfedge_opposite.len())?;
         let ind_he_opp = self.halfedge_opposite[ind_he];
         let ind_tri1 = ind_he / 3;
         let ind_tri2 = ind_he_opp / 3;
+        if ind_tri1 == ind_tri2 {
+            return Err(anyhow::Error::msg(
+                "Cannot flip halfedge within a single triangle",
+            ));
+        }
 
         let h01 = ind_tri1 * 3;
         let h12 = ind_tri1 * 3 + 1;
@@ -276,6 +695,7 @@ impl SimplicialStructure2D {
         let hdc = self.halfedge_opposite[hcd];
         let had = self.halfedge_opposite[hda];
 
+        self.generation += 1;
         let (hbc, hcd, hdb) = self.replace_triangle(ind_tri1, nb, nc, nd);
         let (hda, hab, hbd) = self.replace_triangle(ind_tri2, nd, na, nb);
 
@@ -291,9 +711,110 @@ impl SimplicialStructure2D {
         self.halfedge_opposite[had] = hda;
         self.halfedge_opposite[hcb] = hbc;
         self.halfedge_opposite[hdc] = hcd;
+
+        Ok(())
+    }
+
+    fn next_halfedge_ind(ind_he: usize) -> usize {
+        (ind_he / 3) * 3 + (ind_he + 1) % 3
+    }
+
+    fn prev_halfedge_ind(ind_he: usize) -> usize {
+        (ind_he / 3) * 3 + (ind_he + 2) % 3
+    }
+
+    // moves last triangle in the slot of the removed one, the removed triangle should not be
+    // referenced by other triangles anymore
+    fn delete_triangle(&mut self, ind_tri: usize) {
+        if let Some(label) = self.get_label(ind_tri) {
+            self.release_label(label);
+        }
+        let ind_last = self.nb_triangles - 1;
+        if ind_tri != ind_last {
+            for sub in 0..3 {
+                let ind_he = ind_tri * 3 + sub;
+                let ind_he_last = ind_last * 3 + sub;
+                let ind_he_opp = self.halfedge_opposite[ind_he_last];
+                self.halfedge_first_node[ind_he] = self.halfedge_first_node[ind_he_last];
+                self.halfedge_opposite[ind_he] = ind_he_opp;
+                self.halfedge_opposite[ind_he_opp] = ind_he;
+                self.set_node_halfedge(self.halfedge_first_node[ind_he], ind_he);
+            }
+            self.triangle_generation[ind_tri] = self.generation;
+            if let Some(labels) = self.triangle_label.as_mut() {
+                labels[ind_tri] = labels[ind_last];
+            }
+        }
+        self.halfedge_first_node.truncate(ind_last * 3);
+        self.halfedge_opposite.truncate(ind_last * 3);
+        self.triangle_generation.truncate(ind_last);
+        if let Some(labels) = self.triangle_label.as_mut() {
+            labels.truncate(ind_last);
+        }
+        self.nb_triangles -= 1;
+    }
+
+    /// Removes the first node of a halfedge by collapsing it onto the last node: the triangles
+    /// around the removed node are replaced by a fan from the last node
+    ///
+    /// Geometric validity of the fan is not checked. Triangle indices are not preserved.
+    pub fn collapse_halfedge(&mut self, ind_he: usize) -> Result<()> {
+        check_index(ind_he, self.halfedge_opposite.len())?;
+        let ind_node = match self.halfedge_first_node[ind_he] {
+            Node::Value(ind_node) => ind_node,
+            Node::Infinity => {
+                return Err(anyhow::Error::msg("Cannot remove node at infinity"));
+            }
+        };
+        let node_target = self.halfedge_first_node[Self::next_halfedge_ind(ind_he)];
+
+        // outgoing halfedges, counterclockwise
+        let mut fan = vec![ind_he];
+        loop {
+            let ind_he_next = self.halfedge_opposite[Self::prev_halfedge_ind(fan[fan.len() - 1])];
+            if ind_he_next == ind_he {
+                break;
+            }
+            fan.push(ind_he_next);
+        }
+        let nb_fan = fan.len();
+        if nb_fan < 3 {
+            return Err(anyhow::Error::msg("Node with less than 3 neighbors"));
+        }
+
+        self.generation += 1;
+        let ind_tri_first = fan[0] / 3;
+        let ind_tri_last = fan[nb_fan - 1] / 3;
+        let he_first_out = self.halfedge_opposite[Self::next_halfedge_ind(fan[0])];
+        let he_last_out = self.halfedge_opposite[Self::next_halfedge_ind(fan[nb_fan - 1])];
+
+        for &ind_he_fan in fan[1..nb_fan - 1].iter() {
+            self.halfedge_first_node[ind_he_fan] = node_target;
+        }
+        let he_first_in = fan[1];
+        let he_last_in = Self::prev_halfedge_ind(fan[nb_fan - 2]);
+        self.halfedge_opposite[he_first_in] = he_first_out;
+        self.halfedge_opposite[he_first_out] = he_first_in;
+        self.halfedge_opposite[he_last_in] = he_last_out;
+        self.halfedge_opposite[he_last_out] = he_last_in;
+
+        self.node_halfedge[ind_node] = usize::MAX;
+        for &ind_he_fan in fan[1..nb_fan - 1].iter() {
+            let ind_tri = ind_he_fan / 3;
+            for ind_he_tri in ind_tri * 3..ind_tri * 3 + 3 {
+                self.set_node_halfedge(self.halfedge_first_node[ind_he_tri], ind_he_tri);
+            }
+            self.triangle_generation[ind_tri] = self.generation;
+        }
+
+        self.delete_triangle(ind_tri_first.max(ind_tri_last));
+        self.delete_triangle(ind_tri_first.min(ind_tri_last));
+
+        Ok(())
     }
 
     /// Checks validity of simplicial graph (unit tests purposes)
+    #[must_use = "query result should be used"]
     pub fn is_valid(&self) -> Result<bool> {
         let mut valid = true;
 
@@ -305,16 +826,65 @@ impl SimplicialStructure2D {
         Ok(valid)
     }
 
-    /// Println each triangle of the graph
-    pub fn println(&self) -> () {
+    /// Checks that the opposite mapping is an involution without fixed point, opposite halfedges
+    /// linking the same nodes in reverse order (cheaper than is_valid)
+    #[must_use = "query result should be used"]
+    pub fn check_opposite_involution(&self) -> Result<bool> {
+        let nb_halfedges = self.halfedge_first_node.len();
+        for ind_he in 0..nb_halfedges {
+            let ind_opp = self.halfedge_opposite[ind_he];
+            if ind_opp >= nb_halfedges
+                || ind_opp == ind_he
+                || self.halfedge_opposite[ind_opp] != ind_he
+            {
+                log::error!("Halfedge {}: opposite link is not an involution", ind_he);
+                return Ok(false);
+            }
+            let he = self.get_halfedge(ind_he)?;
+            let he_opp = he.opposite_halfedge();
+            if !he_opp.first_node().equals(&he.last_node())
+                || !he_opp.last_node().equals(&he.first_node())
+            {
+                log::error!("{}: Wrong opposite halfedge", he);
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Overwrites opposite link of a halfedge, leaving the other links unchanged (corrupts the
+    /// structure, for tests)
+    #[cfg(feature = "testgen")]
+    pub fn corrupt_opposite_halfedge(
+        &mut self,
+        ind_halfedge: usize,
+        ind_opposite: usize,
+    ) -> Result<()> {
+        check_index(ind_halfedge, self.halfedge_opposite.len())?;
+        self.halfedge_opposite[ind_halfedge] = ind_opposite;
+        Ok(())
+    }
+}
+
+impl fmt::Display for SimplicialStructure2D {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         for ind_tri in 0..self.nb_triangles {
             let tri = IterTriangle {
                 simplicial: self,
                 ind_triangle: ind_tri,
             };
-            print!("  ");
-            tri.println();
+            writeln!(f, "  {}", tri)?;
         }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl SimplicialStructure2D {
+    /// Println each triangle of the graph
+    #[deprecated(note = "use the Display implementation")]
+    pub fn println(&self) {
+        std::print!("{}", self);
     }
 }
 
@@ -416,25 +986,32 @@ impl<'a> IterHalfEdge<'a> {
 
         valid
     }
+}
 
-    /// Halfedge to string
-    pub fn to_string(&self) -> String {
-        format!(
+impl fmt::Display for IterHalfEdge<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
             "Edge {}: {} -> {}",
             self.ind(),
-            self.first_node().to_string(),
-            self.last_node().to_string()
+            self.first_node(),
+            self.last_node()
         )
     }
+}
 
+#[cfg(feature = "std")]
+impl IterHalfEdge<'_> {
     /// Print halfedge string
-    pub fn print(&self) -> () {
-        print!("{}", self.to_string());
+    #[deprecated(note = "use the Display implementation")]
+    pub fn print(&self) {
+        std::print!("{}", self);
     }
 
     /// Println halfedge string
-    pub fn println(&self) -> () {
-        println!("{}", self.to_string());
+    #[deprecated(note = "use the Display implementation")]
+    pub fn println(&self) {
+        std::println!("{}", self);
     }
 }
 
@@ -479,28 +1056,28 @@ impl<'a> IterTriangle<'a> {
             self.simplicial.halfedge_first_node[self.ind_triangle * 3 + 2],
         ]
     }
+}
 
-    /// Triangle to string
-    pub fn to_string(&self) -> String {
+impl fmt::Display for IterTriangle<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let nod1 = self.simplicial.halfedge_first_node[self.ind_triangle * 3];
         let nod2 = self.simplicial.halfedge_first_node[self.ind_triangle * 3 + 1];
         let nod3 = self.simplicial.halfedge_first_node[self.ind_triangle * 3 + 2];
-        format!(
-            "Face {}: {} -> {} -> {}",
-            self.ind(),
-            nod1.to_string(),
-            nod2.to_string(),
-            nod3.to_string()
-        )
+        write!(f, "Face {}: {} -> {} -> {}", self.ind(), nod1, nod2, nod3)
     }
+}
 
+#[cfg(feature = "std")]
+impl IterTriangle<'_> {
     /// Print triangle string
-    pub fn print(&self) -> () {
-        print!("{}", self.to_string());
+    #[deprecated(note = "use the Display implementation")]
+    pub fn print(&self) {
+        std::print!("{}", self);
     }
 
     /// Println triangle string
-    pub fn println(&self) -> () {
-        println!("{}", self.to_string());
+    #[deprecated(note = "use the Display implementation")]
+    pub fn println(&self) {
+        std::println!("{}", self);
     }
 }
@@ -0,0 +1,99 @@
+use alloc::collections::VecDeque;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Coarse grid of triangle indices, giving walk starting points for single insertions
+#[derive(Clone)]
+pub(crate) struct SpatialHint {
+    pt_min: [f64; 2],
+    cell_size: [f64; 2],
+    resolution: usize,
+    cells: Vec<usize>,
+}
+
+impl SpatialHint {
+    /// Builds grid with resolution cells per side over a bounding box, from located points
+    ///
+    /// Cells without any located point take the triangle of a neighboring cell.
+    pub(crate) fn new(
+        bbox: ([f64; 2], [f64; 2]),
+        resolution: usize,
+        located: &[([f64; 2], usize)],
+    ) -> SpatialHint {
+        let (pt_min, pt_max) = bbox;
+        let cell_size = [
+            (pt_max[0] - pt_min[0]).max(f64::MIN_POSITIVE) / resolution as f64,
+            (pt_max[1] - pt_min[1]).max(f64::MIN_POSITIVE) / resolution as f64,
+        ];
+        let mut hint = SpatialHint {
+            pt_min,
+            cell_size,
+            resolution,
+            cells: vec![usize::MAX; resolution * resolution],
+        };
+
+        let mut to_spread = VecDeque::new();
+        for &(pt, ind_triangle) in located.iter() {
+            let ind_cell = hint.cell(pt);
+            if hint.cells[ind_cell] == usize::MAX {
+                to_spread.push_back(ind_cell);
+            }
+            hint.cells[ind_cell] = ind_triangle;
+        }
+        while let Some(ind_cell) = to_spread.pop_front() {
+            let (col, row) = (ind_cell % resolution, ind_cell / resolution);
+            let neighbors = [
+                (col > 0).then(|| ind_cell - 1),
+                (col + 1 < resolution).then(|| ind_cell + 1),
+                (row > 0).then(|| ind_cell - resolution),
+                (row + 1 < resolution).then(|| ind_cell + resolution),
+            ];
+            for ind_nei in neighbors.into_iter().flatten() {
+                if hint.cells[ind_nei] == usize::MAX {
+                    hint.cells[ind_nei] = hint.cells[ind_cell];
+                    to_spread.push_back(ind_nei);
+                }
+            }
+        }
+        hint
+    }
+
+    // cell containing point, points outside the grid going to border cells
+    fn cell(&self, pt: [f64; 2]) -> usize {
+        let coord = |ind: usize| {
+            let pos = (pt[ind] - self.pt_min[ind]) / self.cell_size[ind];
+            if pos > 0. {
+                (pos as usize).min(self.resolution - 1)
+            } else {
+                0
+            }
+        };
+        coord(1) * self.resolution + coord(0)
+    }
+
+    /// Gets number of cells per side
+    pub(crate) fn resolution(&self) -> usize {
+        self.resolution
+    }
+
+    /// Gets triangle stored in the cell of point, if any
+    pub(crate) fn get(&self, pt: [f64; 2]) -> Option<usize> {
+        let ind_triangle = self.cells[self.cell(pt)];
+        (ind_triangle != usize::MAX).then_some(ind_triangle)
+    }
+
+    /// Renumbers stored triangles after a permutation of the triangle storage
+    pub(crate) fn remap_triangles(&mut self, old_to_new: &[usize]) {
+        for ind_triangle in self.cells.iter_mut() {
+            if let Some(&ind_new) = old_to_new.get(*ind_triangle) {
+                *ind_triangle = ind_new;
+            }
+        }
+    }
+
+    /// Stores triangle in the cell of point
+    pub(crate) fn update(&mut self, pt: [f64; 2], ind_triangle: usize) {
+        let ind_cell = self.cell(pt);
+        self.cells[ind_cell] = ind_triangle;
+    }
+}
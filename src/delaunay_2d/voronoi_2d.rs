@@ -0,0 +1,263 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::compat::HashMap;
+
+/// Voronoi cell of a site, as a counterclockwise polygon
+pub struct VoronoiCell {
+    /// Site (Delaunay vertex) index
+    pub site: usize,
+    /// Voronoi vertex indices, counterclockwise
+    pub vertices: Vec<usize>,
+    /// Voronoi edge indices, edge i going from vertex i to vertex i+1
+    pub edges: Vec<usize>,
+}
+
+/// Voronoi edge, separating two cells or lying on the bounding box
+pub struct VoronoiEdge {
+    /// Voronoi vertex indices, counterclockwise around the site cell
+    pub vertices: [usize; 2],
+    /// Site of the cell on the left of the edge
+    pub site: usize,
+    /// Site of the cell on the right of the edge, None on the bounding box
+    pub neighbor: Option<usize>,
+}
+
+/// Voronoi diagram clipped to a bounding box, as a navigable structure
+pub struct VoronoiDiagram {
+    vertices: Vec<[f64; 2]>,
+    cells: Vec<VoronoiCell>,
+    edges: Vec<VoronoiEdge>,
+    site_cell: HashMap<usize, usize>,
+}
+
+// site index and coordinates, with its Delaunay neighbors
+pub(crate) type VoronoiSite = (usize, [f64; 2], Vec<(usize, [f64; 2])>);
+
+// polygon corner, with the neighbor site of the edge starting from it (None on bounding box)
+type Corner = ([f64; 2], Option<usize>);
+
+/// Clips a convex polygon by the half plane closer to site than to neighbor
+fn clip_by_bisector(
+    polygon: &[Corner],
+    site: [f64; 2],
+    neighbor_site: [f64; 2],
+    neighbor: usize,
+) -> Vec<Corner> {
+    let dir = [neighbor_site[0] - site[0], neighbor_site[1] - site[1]];
+    let mid = [
+        (neighbor_site[0] + site[0]) * 0.5,
+        (neighbor_site[1] + site[1]) * 0.5,
+    ];
+    let side = |pt: [f64; 2]| (pt[0] - mid[0]) * dir[0] + (pt[1] - mid[1]) * dir[1];
+
+    let mut clipped = Vec::new();
+    let nb_pts = polygon.len();
+    for ind in 0..nb_pts {
+        let (pt0, label) = polygon[ind];
+        let (pt1, _) = polygon[(ind + 1) % nb_pts];
+        let (side0, side1) = (side(pt0), side(pt1));
+        let intersection = || {
+            let t = side0 / (side0 - side1);
+            [
+                pt0[0] + t * (pt1[0] - pt0[0]),
+                pt0[1] + t * (pt1[1] - pt0[1]),
+            ]
+        };
+        if side0 <= 0. {
+            if side1 > 0. {
+                // leaving the half plane, next edge lies on the bisector
+                if side0 == 0. {
+                    clipped.push((pt0, Some(neighbor)));
+                } else {
+                    clipped.push((pt0, label));
+                    clipped.push((intersection(), Some(neighbor)));
+                }
+            } else {
+                clipped.push((pt0, label));
+            }
+        } else if side1 < 0. {
+            // entering the half plane
+            clipped.push((intersection(), label));
+        }
+    }
+    clipped
+}
+
+/// Gets root of union-find set, compressing the path
+pub(crate) fn find_root(parent: &mut [usize], ind: usize) -> usize {
+    let mut root = ind;
+    while parent[root] != root {
+        root = parent[root];
+    }
+    let mut cur = ind;
+    while parent[cur] != root {
+        let next = parent[cur];
+        parent[cur] = root;
+        cur = next;
+    }
+    root
+}
+
+impl VoronoiDiagram {
+    /// Builds diagram from sites and their Delaunay neighbors, clipped to bbox (min and max
+    /// corners)
+    pub(crate) fn build(sites: &[VoronoiSite], bbox: ([f64; 2], [f64; 2])) -> VoronoiDiagram {
+        let (pt_min, pt_max) = bbox;
+        let box_polygon: Vec<Corner> = vec![
+            (pt_min, None),
+            ([pt_max[0], pt_min[1]], None),
+            (pt_max, None),
+            ([pt_min[0], pt_max[1]], None),
+        ];
+
+        // cells with their own corners
+        let mut polygons: Vec<(usize, Vec<Corner>)> = Vec::new();
+        for (site, pt_site, neighbors) in sites.iter() {
+            let mut polygon = box_polygon.clone();
+            for &(neighbor, pt_neighbor) in neighbors.iter() {
+                polygon = clip_by_bisector(&polygon, *pt_site, pt_neighbor, neighbor);
+                if polygon.is_empty() {
+                    break;
+                }
+            }
+            if polygon.len() >= 3 {
+                polygons.push((*site, polygon));
+            }
+        }
+
+        let mut offsets = Vec::with_capacity(polygons.len());
+        let mut nb_corners = 0;
+        for (_, polygon) in polygons.iter() {
+            offsets.push(nb_corners);
+            nb_corners += polygon.len();
+        }
+        let site_cell: HashMap<usize, usize> = polygons
+            .iter()
+            .enumerate()
+            .map(|(ind_cell, (site, _))| (*site, ind_cell))
+            .collect();
+
+        // merges corners of edges shared by two cells, returns matching edge if any
+        let matching_edge = |ind_cell: usize, ind_corner: usize| -> Option<(usize, usize)> {
+            let (site, polygon) = &polygons[ind_cell];
+            let neighbor = polygon[ind_corner].1?;
+            let &ind_cell_opp = site_cell.get(&neighbor)?;
+            let ind_corner_opp = polygons[ind_cell_opp]
+                .1
+                .iter()
+                .position(|(_, label)| *label == Some(*site))?;
+            Some((ind_cell_opp, ind_corner_opp))
+        };
+        let mut parent: Vec<usize> = (0..nb_corners).collect();
+        for (ind_cell, (_, polygon)) in polygons.iter().enumerate() {
+            for ind_corner in 0..polygon.len() {
+                if let Some((ind_cell_opp, ind_corner_opp)) = matching_edge(ind_cell, ind_corner) {
+                    let nb_opp = polygons[ind_cell_opp].1.len();
+                    let pairs = [
+                        (
+                            offsets[ind_cell] + ind_corner,
+                            offsets[ind_cell_opp] + (ind_corner_opp + 1) % nb_opp,
+                        ),
+                        (
+                            offsets[ind_cell] + (ind_corner + 1) % polygon.len(),
+                            offsets[ind_cell_opp] + ind_corner_opp,
+                        ),
+                    ];
+                    for (ind1, ind2) in pairs {
+                        let (root1, root2) =
+                            (find_root(&mut parent, ind1), find_root(&mut parent, ind2));
+                        parent[root1.max(root2)] = root1.min(root2);
+                    }
+                }
+            }
+        }
+
+        let mut vertices = Vec::new();
+        let mut vertex_of_root = HashMap::new();
+        let mut corner_vertex = vec![0; nb_corners];
+        for (ind_cell, (_, polygon)) in polygons.iter().enumerate() {
+            for (ind_corner, (pt, _)) in polygon.iter().enumerate() {
+                let ind = offsets[ind_cell] + ind_corner;
+                let root = find_root(&mut parent, ind);
+                corner_vertex[ind] = *vertex_of_root.entry(root).or_insert_with(|| {
+                    vertices.push(*pt);
+                    vertices.len() - 1
+                });
+            }
+        }
+
+        let mut edges = Vec::new();
+        let mut edge_of_corner: HashMap<usize, usize> = HashMap::new();
+        let mut cells = Vec::with_capacity(polygons.len());
+        for (ind_cell, (site, polygon)) in polygons.iter().enumerate() {
+            let cell_vertices: Vec<usize> = (0..polygon.len())
+                .map(|ind_corner| corner_vertex[offsets[ind_cell] + ind_corner])
+                .collect();
+            let mut cell_edges = Vec::with_capacity(polygon.len());
+            for ind_corner in 0..polygon.len() {
+                let ind = offsets[ind_cell] + ind_corner;
+                let ind_edge = match edge_of_corner.get(&ind) {
+                    Some(&ind_edge) => ind_edge,
+                    None => {
+                        edges.push(VoronoiEdge {
+                            vertices: [
+                                cell_vertices[ind_corner],
+                                cell_vertices[(ind_corner + 1) % polygon.len()],
+                            ],
+                            site: *site,
+                            neighbor: polygon[ind_corner].1,
+                        });
+                        if let Some((ind_cell_opp, ind_corner_opp)) =
+                            matching_edge(ind_cell, ind_corner)
+                        {
+                            edge_of_corner
+                                .insert(offsets[ind_cell_opp] + ind_corner_opp, edges.len() - 1);
+                        }
+                        edges.len() - 1
+                    }
+                };
+                cell_edges.push(ind_edge);
+            }
+            cells.push(VoronoiCell {
+                site: *site,
+                vertices: cell_vertices,
+                edges: cell_edges,
+            });
+        }
+
+        VoronoiDiagram {
+            vertices,
+            cells,
+            edges,
+            site_cell,
+        }
+    }
+
+    /// Gets Voronoi vertices (circumcenters and bounding box points)
+    pub fn get_vertices(&self) -> &[[f64; 2]] {
+        &self.vertices
+    }
+
+    /// Gets cell of a site, None if the site has no cell within the bounding box
+    pub fn get_cell(&self, site: usize) -> Option<&VoronoiCell> {
+        self.site_cell
+            .get(&site)
+            .map(|&ind_cell| &self.cells[ind_cell])
+    }
+
+    /// Gets edge from index
+    pub fn get_edge(&self, ind_edge: usize) -> Option<&VoronoiEdge> {
+        self.edges.get(ind_edge)
+    }
+
+    /// Iterates over cells
+    pub fn cells(&self) -> impl Iterator<Item = &VoronoiCell> {
+        self.cells.iter()
+    }
+
+    /// Iterates over edges, each edge shared by two cells appearing once
+    pub fn edges(&self) -> impl Iterator<Item = &VoronoiEdge> {
+        self.edges.iter()
+    }
+}
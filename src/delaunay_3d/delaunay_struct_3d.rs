@@ -1,10 +1,47 @@
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
 use anyhow::Result;
+use core::cmp::Ordering;
 use robust::{insphere, orient3d, Coord3D};
-use std::time::Instant;
+#[cfg(feature = "std")]
+use std::io::{self, Read, Write};
 
-use super::geometry_operations_3d::build_hilbert_curve_3d;
+#[cfg(feature = "std")]
+use crate::binary_io::{
+    read_f64, read_header, read_u64, write_f64, write_header, write_u64, BINARY_INFINITY,
+};
+use crate::compat::{sqrt, HashMap, HashSet, StepTimer, Timer};
+use crate::error::check_index;
+
+use super::geometry_operations_3d::{
+    build_hilbert_curve_3d, coplanar_in_circle, coplanar_relative_orient, in_sphere_3d,
+    is_tetrahedron_flat, minimal_enclosing_sphere, orient_3d, tetrahedron_quality,
+};
 use super::simplicial_struct_3d::{IterHalfTriangle, Node, SimplicialStructure3D};
 
+#[cfg(feature = "std")]
+const BINARY_MAGIC_3D: &[u8; 4] = b"SDL3";
+
+fn sub(pt0: [f64; 3], pt1: [f64; 3]) -> [f64; 3] {
+    [pt0[0] - pt1[0], pt0[1] - pt1[1], pt0[2] - pt1[2]]
+}
+
+fn dot_product(vec0: [f64; 3], vec1: [f64; 3]) -> f64 {
+    vec0[0] * vec1[0] + vec0[1] * vec1[1] + vec0[2] * vec1[2]
+}
+
+fn cross_product(vec0: [f64; 3], vec1: [f64; 3]) -> [f64; 3] {
+    [
+        vec0[1] * vec1[2] - vec0[2] * vec1[1],
+        vec0[2] * vec1[0] - vec0[0] * vec1[2],
+        vec0[0] * vec1[1] - vec0[1] * vec1[0],
+    ]
+}
+
+/// Vertices and tetrahedra as vertex indices
+pub type IndexedMesh3D = (Vec<[f64; 3]>, Vec<[usize; 4]>);
+
 /// Extended tetrahedron, including point at infinity
 pub enum ExtendedTetrahedron {
     /// Regular tetrahedron
@@ -13,20 +50,33 @@ pub enum ExtendedTetrahedron {
     Triangle([[f64; 3]; 3]),
 }
 
+#[derive(Clone)]
 /// 3D Delaunay structure
 pub struct DelaunayStructure3D {
     simpl_struct: SimplicialStructure3D,
     vertex_coordinates: Vec<[f64; 3]>,
+    symbolic_perturbation: bool,
+    flat_tolerance: f64,
+    nb_walk_fallbacks: usize,
     walk_ns: u128,
     insert_ns: u128,
 }
 
+impl Default for DelaunayStructure3D {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl DelaunayStructure3D {
     /// Delaunay structure initialisation
     pub fn new() -> DelaunayStructure3D {
         DelaunayStructure3D {
             simpl_struct: SimplicialStructure3D::new(),
             vertex_coordinates: Vec::new(),
+            symbolic_perturbation: true,
+            flat_tolerance: 0.,
+            nb_walk_fallbacks: 0,
             walk_ns: 0,
             insert_ns: 0,
         }
@@ -37,12 +87,65 @@ impl DelaunayStructure3D {
         &self.simpl_struct
     }
 
+    /// Gets mutable simplicial structure, to corrupt it in tests
+    #[cfg(feature = "testgen")]
+    pub fn get_simplicial_mut(&mut self) -> &mut SimplicialStructure3D {
+        &mut self.simpl_struct
+    }
+
     /// Gets graph vertices
     pub fn get_vertices(&self) -> &Vec<[f64; 3]> {
         &self.vertex_coordinates
     }
 
+    /// Enables or disables symbolic perturbation (simulation of simplicity, enabled by default)
+    ///
+    /// Cospherical and coplanar ties are broken by vertex index instead of coordinates, avoiding
+    /// flat tetrahedra on degenerate inputs without moving the points.
+    pub fn set_symbolic_perturbation(&mut self, enabled: bool) {
+        self.symbolic_perturbation = enabled;
+    }
+
+    /// Sets relative flatness tolerance (0 by default, exact test)
+    ///
+    /// Tetrahedra whose volume is at most tolerance times their cubed longest edge length are
+    /// considered flat.
+    pub fn set_flat_tolerance(&mut self, rel_eps: f64) {
+        self.flat_tolerance = rel_eps;
+    }
+
+    /// Checks if a tetrahedron is flat up to the flatness tolerance (false for infinite
+    /// tetrahedra)
+    #[must_use = "query result should be used"]
+    pub fn is_tetrahedron_nearly_flat(&self, ind_tetrahedron: usize) -> Result<bool> {
+        let flat = match self.get_extended_tetrahedron(ind_tetrahedron)? {
+            ExtendedTetrahedron::Tetrahedron(tetra) => {
+                is_tetrahedron_flat(tetra, self.flat_tolerance)
+            }
+            ExtendedTetrahedron::Triangle(_) => false,
+        };
+        Ok(flat)
+    }
+
+    /// Gets finite tetrahedra which are flat up to the flatness tolerance
+    #[must_use = "query result should be used"]
+    pub fn degenerate_tetrahedra(&self) -> Result<Vec<usize>> {
+        let mut degenerate = Vec::new();
+        for ind_tetra in 0..self.get_simplicial().get_nb_tetrahedra() {
+            if self.is_tetrahedron_nearly_flat(ind_tetra)? {
+                degenerate.push(ind_tetra);
+            }
+        }
+        Ok(degenerate)
+    }
+
+    /// Number of walks which required an exhaustive search
+    pub fn get_nb_walk_fallbacks(&self) -> usize {
+        self.nb_walk_fallbacks
+    }
+
     /// Gets extended tetrahedron from index
+    #[must_use = "query result should be used"]
     pub fn get_extended_tetrahedron(&self, ind_tetrahedron: usize) -> Result<ExtendedTetrahedron> {
         let [node1, node2, node3, node4] = self
             .get_simplicial()
@@ -94,7 +197,229 @@ impl DelaunayStructure3D {
         Ok(ext_tri)
     }
 
+    /// Gets tetrahedron coordinates from index, None if the tetrahedron contains infinity
+    #[must_use = "query result should be used"]
+    pub fn tetrahedron_coords(&self, ind_tetrahedron: usize) -> Result<Option<[[f64; 3]; 4]>> {
+        if let ExtendedTetrahedron::Tetrahedron(tetra) =
+            self.get_extended_tetrahedron(ind_tetrahedron)?
+        {
+            Ok(Some(tetra))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Gets finite tetrahedra whose quality (volume relative to edge lengths, 1 for a regular
+    /// tetrahedron) is below threshold
+    #[must_use = "query result should be used"]
+    pub fn slivers(&self, volume_threshold: f64) -> Result<Vec<usize>> {
+        let mut vec_slivers = Vec::new();
+        for ind_tetra in 0..self.get_simplicial().get_nb_tetrahedra() {
+            if let Some(tetra) = self.tetrahedron_coords(ind_tetra)? {
+                if tetrahedron_quality(tetra) < volume_threshold {
+                    vec_slivers.push(ind_tetra);
+                }
+            }
+        }
+        Ok(vec_slivers)
+    }
+
+    /// Checks if some finite tetrahedron is flat or negatively oriented
+    #[must_use = "query result should be used"]
+    pub fn has_inverted_elements(&self) -> Result<bool> {
+        for ind_tetra in 0..self.get_simplicial().get_nb_tetrahedra() {
+            if let Some([pt0, pt1, pt2, pt3]) = self.tetrahedron_coords(ind_tetra)? {
+                if orient_3d(pt0, pt1, pt2, pt3) <= 0. {
+                    return Ok(true);
+                }
+            }
+        }
+        Ok(false)
+    }
+
+    /// Calls f on each finite tetrahedron (positively oriented vertex indices), without
+    /// collecting them
+    pub fn for_each_finite_tetrahedron(&self, mut f: impl FnMut([usize; 4])) {
+        for ind_tetra in 0..self.get_simplicial().get_nb_tetrahedra() {
+            let Ok(tetra) = self.get_simplicial().get_tetrahedron(ind_tetra) else {
+                continue;
+            };
+            if let [Node::Value(ind1), Node::Value(ind2), Node::Value(ind3), Node::Value(ind4)] =
+                tetra.nodes()
+            {
+                f([ind1, ind2, ind3, ind4]);
+            }
+        }
+    }
+
+    /// Gets convex hull boundary triangles as vertex indices, counterclockwise seen from outside
+    #[must_use = "query result should be used"]
+    pub fn boundary_triangles(&self) -> Result<Vec<[usize; 3]>> {
+        let mut vec_tri = Vec::new();
+        for ind_tetra in 0..self.get_simplicial().get_nb_tetrahedra() {
+            let nodes = self.get_simplicial().get_tetrahedron(ind_tetra)?.nodes();
+            // reversed hull face of get_extended_tetrahedron
+            let tri = match nodes {
+                [Node::Infinity, Node::Value(ind_v2), Node::Value(ind_v3), Node::Value(ind_v4)] => {
+                    [ind_v2, ind_v3, ind_v4]
+                }
+                [Node::Value(ind_v1), Node::Infinity, Node::Value(ind_v3), Node::Value(ind_v4)] => {
+                    [ind_v1, ind_v4, ind_v3]
+                }
+                [Node::Value(ind_v1), Node::Value(ind_v2), Node::Infinity, Node::Value(ind_v4)] => {
+                    [ind_v1, ind_v2, ind_v4]
+                }
+                [Node::Value(ind_v1), Node::Value(ind_v2), Node::Value(ind_v3), Node::Infinity] => {
+                    [ind_v1, ind_v3, ind_v2]
+                }
+                _ => continue,
+            };
+            vec_tri.push(tri);
+        }
+        Ok(vec_tri)
+    }
+
+    /// Computes convex hull surface area
+    #[must_use = "query result should be used"]
+    pub fn hull_surface_area(&self) -> Result<f64> {
+        let mut area = 0.;
+        for tri in self.boundary_triangles()? {
+            let [pt_a, pt_b, pt_c] = tri.map(|ind| self.vertex_coordinates[ind]);
+            let normal = cross_product(sub(pt_b, pt_a), sub(pt_c, pt_a));
+            area += 0.5 * sqrt(dot_product(normal, normal));
+        }
+        Ok(area)
+    }
+
+    /// Computes convex hull volume, summing signed tetrahedra from the centroid of hull vertices to
+    /// the boundary faces
+    #[must_use = "query result should be used"]
+    pub fn hull_volume(&self) -> Result<f64> {
+        let boundary = self.boundary_triangles()?;
+        let hull_vertices: HashSet<usize> = boundary.iter().flatten().copied().collect();
+        if hull_vertices.is_empty() {
+            return Err(anyhow::Error::msg("No tetrahedron in structure"));
+        }
+        let mut centroid = [0.; 3];
+        for &ind in hull_vertices.iter() {
+            let pt = self.vertex_coordinates[ind];
+            centroid = [
+                centroid[0] + pt[0],
+                centroid[1] + pt[1],
+                centroid[2] + pt[2],
+            ];
+        }
+        let nb_hull = hull_vertices.len() as f64;
+        let centroid = centroid.map(|val| val / nb_hull);
+
+        let mut volume = 0.;
+        for tri in boundary {
+            let [pt_a, pt_b, pt_c] = tri.map(|ind| sub(self.vertex_coordinates[ind], centroid));
+            volume += dot_product(pt_a, cross_product(pt_b, pt_c)) / 6.;
+        }
+        Ok(volume)
+    }
+
+    /// Gets minimal enclosing sphere (center, radius) of the tetrahedralized vertices, computed
+    /// from the convex hull vertices
+    #[must_use = "query result should be used"]
+    pub fn minimal_enclosing_sphere(&self) -> Result<([f64; 3], f64)> {
+        let mut hull_vertices: Vec<usize> = self
+            .boundary_triangles()?
+            .into_iter()
+            .flatten()
+            .collect::<HashSet<usize>>()
+            .into_iter()
+            .collect();
+        hull_vertices.sort_unstable();
+        let hull_pts: Vec<[f64; 3]> = hull_vertices
+            .into_iter()
+            .map(|ind| self.vertex_coordinates[ind])
+            .collect();
+        minimal_enclosing_sphere(&hull_pts)
+            .ok_or_else(|| anyhow::Error::msg("No tetrahedron in structure"))
+    }
+
+    /// Checks that the boundary is a closed oriented 2-manifold with the topology of a sphere
+    ///
+    /// Each boundary edge must be shared by exactly two boundary triangles, with opposite
+    /// directions, and the Euler characteristic must be 2.
+    #[must_use = "query result should be used"]
+    pub fn boundary_is_closed_manifold(&self) -> Result<bool> {
+        let vec_tri = self.boundary_triangles()?;
+
+        let mut directed_edges = HashSet::new();
+        let mut edge_count = HashMap::new();
+        let mut vertices = HashSet::new();
+        for tri in vec_tri.iter() {
+            for i in 0..3 {
+                let (ind_v1, ind_v2) = (tri[i], tri[(i + 1) % 3]);
+                vertices.insert(ind_v1);
+                if !directed_edges.insert((ind_v1, ind_v2)) {
+                    log::error!(
+                        "Boundary edge {} -> {} appears twice with same direction",
+                        ind_v1,
+                        ind_v2
+                    );
+                    return Ok(false);
+                }
+                *edge_count
+                    .entry((ind_v1.min(ind_v2), ind_v1.max(ind_v2)))
+                    .or_insert(0) += 1;
+            }
+        }
+
+        if let Some(((ind_v1, ind_v2), count)) = edge_count.iter().find(|(_, &count)| count != 2) {
+            log::error!(
+                "Boundary edge {} - {} shared by {} triangles",
+                ind_v1,
+                ind_v2,
+                count
+            );
+            return Ok(false);
+        }
+
+        let euler = vertices.len() as i64 - edge_count.len() as i64 + vec_tri.len() as i64;
+        if euler != 2 {
+            log::error!("Boundary Euler characteristic is {} (expected 2)", euler);
+            return Ok(false);
+        }
+        Ok(true)
+    }
+
+    /// Classifies a point relative to the circumsphere of a tetrahedron
+    ///
+    /// Returns Greater if the point is strictly inside, Equal if it lies on the sphere and Less if
+    /// it is outside. For a tetrahedron containing infinity, the sphere is the half-space beyond
+    /// its hull face, bounded by the face circumcircle within the face plane.
+    #[must_use = "query result should be used"]
+    pub fn in_sphere(&self, ind_tetra: usize, point: [f64; 3]) -> Result<Ordering> {
+        let sign = match self.get_extended_tetrahedron(ind_tetra)? {
+            ExtendedTetrahedron::Tetrahedron([pt0, pt1, pt2, pt3]) => {
+                in_sphere_3d(pt0, pt1, pt2, pt3, point)
+            }
+            ExtendedTetrahedron::Triangle([pt0, pt1, pt2]) => {
+                let sign = orient_3d(pt0, pt1, pt2, point);
+                if sign == 0. {
+                    coplanar_in_circle(pt0, pt1, pt2, point)
+                } else {
+                    sign
+                }
+            }
+        };
+        Ok(if sign > 0. {
+            Ordering::Greater
+        } else if sign < 0. {
+            Ordering::Less
+        } else {
+            Ordering::Equal
+        })
+    }
+
     fn is_vertex_in_sphere(&self, ind_vert: usize, ind_tetra: usize) -> Result<bool> {
+        if self.symbolic_perturbation {
+            return self.is_vertex_in_perturbed_sphere(ind_vert, ind_tetra);
+        }
         let vert = self.get_vertices()[ind_vert];
         let ext_tri = self.get_extended_tetrahedron(ind_tetra)?;
 
@@ -152,6 +477,78 @@ impl DelaunayStructure3D {
         Ok(sign >= 0.)
     }
 
+    /// Perturbed insphere test, higher vertex indices having higher perturbations
+    fn is_vertex_in_perturbed_sphere(&self, ind_vert: usize, ind_tetra: usize) -> Result<bool> {
+        let vert = self.get_vertices()[ind_vert];
+        let nodes = self.get_simplicial().get_tetrahedron(ind_tetra)?.nodes();
+
+        let face = match nodes {
+            [Node::Value(ind1), Node::Value(ind2), Node::Value(ind3), Node::Value(ind4)] => {
+                let inds = [ind1, ind2, ind3, ind4];
+                let tetra = inds.map(|ind| self.get_vertices()[ind]);
+                let sign = in_sphere_3d(tetra[0], tetra[1], tetra[2], tetra[3], vert);
+                if sign != 0. {
+                    return Ok(sign > 0.);
+                }
+
+                let mut order = vec![ind_vert, ind1, ind2, ind3, ind4];
+                order.sort_unstable_by(|a, b| b.cmp(a));
+                for ind in order {
+                    if ind == ind_vert {
+                        return Ok(false);
+                    }
+                    let mut tetra_rep = tetra;
+                    tetra_rep[inds.iter().position(|&i| i == ind).unwrap()] = vert;
+                    let sign = orient_3d(tetra_rep[0], tetra_rep[1], tetra_rep[2], tetra_rep[3]);
+                    if sign != 0. {
+                        return Ok(sign > 0.);
+                    }
+                }
+                return Ok(false);
+            }
+            [Node::Infinity, Node::Value(ind2), Node::Value(ind3), Node::Value(ind4)] => {
+                [ind2, ind4, ind3]
+            }
+            [Node::Value(ind1), Node::Infinity, Node::Value(ind3), Node::Value(ind4)] => {
+                [ind1, ind3, ind4]
+            }
+            [Node::Value(ind1), Node::Value(ind2), Node::Infinity, Node::Value(ind4)] => {
+                [ind1, ind4, ind2]
+            }
+            [Node::Value(ind1), Node::Value(ind2), Node::Value(ind3), Node::Infinity] => {
+                [ind1, ind2, ind3]
+            }
+            _ => {
+                return Err(anyhow::Error::msg("Case should not happen"));
+            }
+        };
+
+        let tri = face.map(|ind| self.get_vertices()[ind]);
+        let sign = orient_3d(tri[0], tri[1], tri[2], vert);
+        if sign != 0. {
+            return Ok(sign > 0.);
+        }
+        // coplanar with hull face, checks perturbed circumcircle
+        let sign = coplanar_in_circle(tri[0], tri[1], tri[2], vert);
+        if sign != 0. {
+            return Ok(sign > 0.);
+        }
+        let mut order = vec![ind_vert, face[0], face[1], face[2]];
+        order.sort_unstable_by(|a, b| b.cmp(a));
+        for ind in order {
+            if ind == ind_vert {
+                return Ok(false);
+            }
+            let mut tri_rep = tri;
+            tri_rep[face.iter().position(|&i| i == ind).unwrap()] = vert;
+            let sign = coplanar_relative_orient(tri, tri_rep);
+            if sign != 0. {
+                return Ok(sign > 0.);
+            }
+        }
+        Ok(false)
+    }
+
     fn is_vertex_strict_in_sphere(&self, ind_vert: usize, ind_tetra: usize) -> Result<bool> {
         let vert = self.get_vertices()[ind_vert];
         let ext_tri = self.get_extended_tetrahedron(ind_tetra)?;
@@ -211,34 +608,9 @@ impl DelaunayStructure3D {
     }
 
     fn is_tetrahedron_flat(&self, ind_tri: usize) -> Result<bool> {
-        let ext_tri = self.get_extended_tetrahedron(ind_tri)?;
-
-        let flat = if let ExtendedTetrahedron::Tetrahedron(tri) = ext_tri {
-            let sign = orient3d(
-                Coord3D {
-                    x: tri[0][0],
-                    y: tri[0][1],
-                    z: tri[0][2],
-                },
-                Coord3D {
-                    x: tri[1][0],
-                    y: tri[1][1],
-                    z: tri[1][2],
-                },
-                Coord3D {
-                    x: tri[2][0],
-                    y: tri[2][1],
-                    z: tri[2][2],
-                },
-                Coord3D {
-                    x: tri[3][0],
-                    y: tri[3][1],
-                    z: tri[3][2],
-                },
-            );
-            sign == 0.
-        } else {
-            false
+        let flat = match self.get_extended_tetrahedron(ind_tri)? {
+            ExtendedTetrahedron::Tetrahedron(tetra) => is_tetrahedron_flat(tetra, 0.),
+            ExtendedTetrahedron::Triangle(_) => false,
         };
         Ok(flat)
     }
@@ -308,22 +680,25 @@ impl DelaunayStructure3D {
         let vert = self.get_vertices()[ind_vert];
         let mut ind_tetra_cur = ind_starting_tetrahedron;
         let start_tetra = self.get_simplicial().get_tetrahedron(ind_tetra_cur)?;
-        let mut vec_tri: Vec<IterHalfTriangle> =
-            start_tetra.halftriangles().iter().map(|&tri| tri).collect();
+        let mut vec_tri: Vec<IterHalfTriangle> = start_tetra.halftriangles().to_vec();
         let mut side = 0;
         let mut nb_visited = 0;
-        let th_visited = self.get_simplicial().get_nb_tetrahedra() >> 2;
+        // a walk without cycle visits each tetrahedron at most once. A quarter of them cuts
+        // valid walks short while the structure is small: inserting a 20x20x20 grid or 100k
+        // random points then falls back 1 to 3 times, always below 60 tetrahedra, whereas no
+        // fallback happens with this cap
+        let th_visited = self.get_simplicial().get_nb_tetrahedra();
         loop {
             if nb_visited > th_visited {
                 break Err(anyhow::Error::msg("Could not find sphere containing point"));
             }
             if let Some(tri) = self.choose_tri(&vec_tri, &vert) {
-                nb_visited = nb_visited + 1;
+                nb_visited += 1;
                 let tri_opp = tri.opposite();
                 ind_tetra_cur = tri_opp.tetrahedron().ind();
                 vec_tri.clear();
                 let hes = tri_opp.halfedges();
-                vec_tri.push(hes[(0 + side) % 3].neighbor().triangle());
+                vec_tri.push(hes[side % 3].neighbor().triangle());
                 vec_tri.push(hes[(1 + side) % 3].neighbor().triangle());
                 vec_tri.push(hes[(2 + side) % 3].neighbor().triangle());
                 side = (side + 1) % 3;
@@ -341,15 +716,11 @@ impl DelaunayStructure3D {
     fn insert_bw(&mut self, ind_vert: usize, ind_first_tetra: usize) -> Result<Vec<usize>> {
         self.simpl_struct.bw_start(ind_first_tetra)?;
 
-        loop {
-            if let Some(ind_tetra) = self.simpl_struct.bw_tetra_to_check() {
-                if self.is_vertex_in_sphere(ind_vert, ind_tetra)? {
-                    self.simpl_struct.bw_rem_tetra(ind_tetra);
-                } else {
-                    self.simpl_struct.bw_keep_tetra(ind_tetra)?;
-                }
+        while let Some(ind_tetra) = self.simpl_struct.bw_tetra_to_check() {
+            if self.is_vertex_in_sphere(ind_vert, ind_tetra)? {
+                self.simpl_struct.bw_rem_tetra(ind_tetra);
             } else {
-                break;
+                self.simpl_struct.bw_keep_tetra(ind_tetra)?;
             }
         }
 
@@ -357,148 +728,155 @@ impl DelaunayStructure3D {
         self.simpl_struct.bw_insert_node(nod)
     }
 
-    fn insert_vertex_helper(&mut self, ind_vertex: usize, near_to: usize) -> Result<usize> {
-        let now = Instant::now();
+    fn insert_vertex_helper(&mut self, ind_vertex: usize, near_to: usize) -> Result<Vec<usize>> {
+        let now = StepTimer::start();
         let ind_tetrahedron = if let Ok(ind) = self.walk_by_visibility(ind_vertex, near_to) {
             ind
         } else {
+            self.nb_walk_fallbacks += 1;
             self.simpl_struct.clean_to_rem()?;
             self.walk_check_all(ind_vertex)?
         };
-        let duration = now.elapsed();
-        let nano = duration.as_nanos();
-        self.walk_ns = self.walk_ns + nano;
+        let nano = now.elapsed_ns();
+        self.walk_ns += nano;
 
-        let now = Instant::now();
+        let now = StepTimer::start();
         let added_tetra = self.insert_bw(ind_vertex, ind_tetrahedron)?;
-        let duration = now.elapsed();
-        let nano = duration.as_nanos();
-        self.insert_ns = self.insert_ns + nano;
+        let nano = now.elapsed_ns();
+        self.insert_ns += nano;
 
-        Ok(added_tetra[0])
+        Ok(added_tetra)
     }
 
     fn insert_first_tetrahedron(&mut self, indices_to_insert: &mut Vec<usize>) -> Result<()> {
-        let now = Instant::now();
+        let now = Timer::start();
         // first tetrahedron insertion
         if self.get_vertices().len() == indices_to_insert.len() {
-            let ind1 = indices_to_insert.pop().unwrap();
-            let ind2 = indices_to_insert.pop().unwrap();
-            let pt1 = self.get_vertices()[ind1];
-            let pt2 = self.get_vertices()[ind2];
-
-            let mut aligned = Vec::new();
-            let vec12 = [pt2[0] - pt1[0], pt2[1] - pt1[1], pt2[2] - pt1[2]];
-
-            let i3 = indices_to_insert
-                .iter()
-                .rev()
-                .enumerate()
-                .map(|(e, &ind)| (e, self.get_vertices()[ind]))
-                .map(|(e, pt)| (e, [pt[0] - pt1[0], pt[1] - pt1[1], pt[2] - pt1[2]]))
-                .map(|(e, vec)| (e, vec[0] * vec12[0] + vec[1] * vec12[1] + vec[2] * vec12[2]))
-                .map(|(e, scal)| if scal < 0.0 { (e, -scal) } else { (e, scal) })
-                .max_by(|(_, val1), (_, val2)| val1.partial_cmp(val2).unwrap())
-                .map(|(e, _)| e)
-                .unwrap();
+            let vertices = &self.vertex_coordinates;
+            // position in indices_to_insert of the point maximizing the measure
+            let argmax = |indices: &[usize], measure: &dyn Fn([f64; 3]) -> f64| {
+                indices
+                    .iter()
+                    .map(|&ind| measure(vertices[ind]))
+                    .enumerate()
+                    .max_by(|(_, val1), (_, val2)| val1.total_cmp(val2))
+                    .map(|(e, _)| e)
+                    .unwrap()
+            };
+            let squared_norm = |vec: [f64; 3]| dot_product(vec, vec);
 
-            let ind3 = indices_to_insert.remove(i3);
-            let pt3 = self.get_vertices()[ind3];
-
-            loop {
-                if let Some(ind4) = indices_to_insert.pop() {
-                    let pt4 = self.get_vertices()[ind4];
-
-                    let sign = robust::orient3d(
-                        Coord3D {
-                            x: pt1[0],
-                            y: pt1[1],
-                            z: pt1[2],
-                        },
-                        Coord3D {
-                            x: pt2[0],
-                            y: pt2[1],
-                            z: pt2[2],
-                        },
-                        Coord3D {
-                            x: pt3[0],
-                            y: pt3[1],
-                            z: pt3[2],
-                        },
-                        Coord3D {
-                            x: pt4[0],
-                            y: pt4[1],
-                            z: pt4[2],
-                        },
-                    );
+            // first edge approximates the diameter
+            let pt0 = vertices[indices_to_insert[indices_to_insert.len() - 1]];
+            let i1 = argmax(indices_to_insert, &|pt| squared_norm(sub(pt, pt0)));
+            let ind1 = indices_to_insert.remove(i1);
+            let pt1 = vertices[ind1];
+            let i2 = argmax(indices_to_insert, &|pt| squared_norm(sub(pt, pt1)));
+            let ind2 = indices_to_insert.remove(i2);
+            let pt2 = vertices[ind2];
 
-                    if sign > 0. {
-                        self.simpl_struct
-                            .first_tetrahedron([ind1, ind2, ind3, ind4])?
-                    } else if sign < 0. {
-                        self.simpl_struct
-                            .first_tetrahedron([ind1, ind3, ind2, ind4])?
-                    } else {
-                        aligned.push(ind4);
-                        continue;
-                    };
-                } else {
-                    return Err(anyhow::Error::msg("Could not find four non aligned points"));
-                }
+            // third point maximizes the triangle area, fourth one the tetrahedron volume
+            let i3 = argmax(indices_to_insert, &|pt| {
+                squared_norm(cross_product(sub(pt2, pt1), sub(pt, pt1)))
+            });
+            let ind3 = indices_to_insert.remove(i3);
+            let pt3 = vertices[ind3];
+            let i4 = argmax(indices_to_insert, &|pt| orient_3d(pt1, pt2, pt3, pt).abs());
+            let ind4 = indices_to_insert[i4];
+            let pt4 = vertices[ind4];
 
-                break;
+            let sign = orient_3d(pt1, pt2, pt3, pt4);
+            if sign > 0. {
+                self.simpl_struct
+                    .first_tetrahedron([ind1, ind2, ind3, ind4])?;
+            } else if sign < 0. {
+                self.simpl_struct
+                    .first_tetrahedron([ind1, ind3, ind2, ind4])?;
+            } else {
+                return Err(anyhow::Error::msg("Could not find four non aligned points"));
             }
-            indices_to_insert.append(&mut aligned);
+            indices_to_insert.remove(i4);
         }
-        let duration = now.elapsed();
-        let nano = duration.as_nanos();
-        log::info!("First tetrahedron computed in {}ms", nano as f32 / 1e6);
+        let nano = now.elapsed_ns();
+        log::debug!("First tetrahedron computed in {}ms", nano as f32 / 1e6);
         Ok(())
     }
 
     /// insert a single vertex in the structure
+    ///
+    /// On an empty structure, vertices are stored until four of them are not coplanar, the first
+    /// tetrahedron being then built from all of them.
     pub fn insert_vertex(&mut self, vertex: [f64; 3], near_to: Option<usize>) -> Result<()> {
+        self.insert_vertex_tracked(vertex, near_to)?;
+        Ok(())
+    }
+
+    /// Inserts a single vertex in the structure, returns indices of the created tetrahedra
+    ///
+    /// While the first tetrahedron is not built (see [`Self::insert_vertex`]), returns no index,
+    /// then all tetrahedra once it is.
+    pub fn insert_vertex_tracked(
+        &mut self,
+        vertex: [f64; 3],
+        near_to: Option<usize>,
+    ) -> Result<Vec<usize>> {
         if self.simpl_struct.get_nb_tetrahedra() == 0 {
-            return Err(anyhow::Error::msg(
-                "Needs at least 1 tetrahedron to insert a single point",
-            ));
+            // vertices accumulate until four of them are not coplanar
+            self.vertex_coordinates.push(vertex);
+            if self.vertex_coordinates.len() < 4 {
+                return Ok(Vec::new());
+            }
+            let mut indices_to_insert: Vec<usize> = (0..self.vertex_coordinates.len()).collect();
+            if let Err(err) = self.insert_first_tetrahedron(&mut indices_to_insert) {
+                // coplanar vertices, waiting for more
+                if self.simpl_struct.get_nb_tetrahedra() == 0 {
+                    return Ok(Vec::new());
+                }
+                return Err(err);
+            }
+            let mut last_added = self.simpl_struct.get_nb_tetrahedra() - 1;
+            while let Some(ind_vertex) = indices_to_insert.pop() {
+                last_added = self.insert_vertex_helper(ind_vertex, last_added)?[0];
+            }
+            self.simpl_struct.clean_to_rem()?;
+            return Ok((0..self.simpl_struct.get_nb_tetrahedra()).collect());
         }
+        let near_to = near_to.unwrap_or(self.simpl_struct.get_nb_tetrahedra() - 1);
+        check_index(near_to, self.simpl_struct.get_nb_tetrahedra())?;
         let indices_to_insert = self.vertex_coordinates.len();
         self.vertex_coordinates.push(vertex);
-        self.insert_vertex_helper(
-            indices_to_insert,
-            near_to.unwrap_or(self.simpl_struct.get_nb_tetrahedra() - 1),
-        )?;
-        self.simpl_struct.clean_to_rem()?;
-        log::info!("Walks computed in {}ms", self.walk_ns as f32 / 1e6);
-        log::info!("Insertions computed in {}ms", self.insert_ns as f32 / 1e6);
-        Ok(())
+        let mut added_tetra = self.insert_vertex_helper(indices_to_insert, near_to)?;
+        self.simpl_struct.clean_to_rem_tracking(&mut added_tetra)?;
+        #[cfg(feature = "timing")]
+        log::debug!("Walks computed in {}ms", self.walk_ns as f32 / 1e6);
+        #[cfg(feature = "timing")]
+        log::debug!("Insertions computed in {}ms", self.insert_ns as f32 / 1e6);
+        Ok(added_tetra)
     }
 
     /// Updates delaunay graph, including newly inserted vertices
-    pub fn insert_vertices(
-        &mut self,
-        to_insert: &Vec<[f64; 3]>,
-        reorder_points: bool,
-    ) -> Result<()> {
-        let mut indices_to_insert = Vec::new();
-        for &vert in to_insert.iter() {
-            indices_to_insert.push(self.vertex_coordinates.len());
-            self.vertex_coordinates.push(vert);
-        }
-
-        if self.get_vertices().len() < 4 {
+    ///
+    /// On an empty structure, vertices stored by [`Self::insert_vertex`] are inserted as well.
+    pub fn insert_vertices(&mut self, to_insert: &[[f64; 3]], reorder_points: bool) -> Result<()> {
+        if self.get_vertices().len() + to_insert.len() < 4 {
             return Err(anyhow::Error::msg(
                 "Needs at least 4 vertices to compute Delaunay",
             ));
         }
 
+        // vertices stored by single insertions are still pending on an empty structure
+        let first = if self.simpl_struct.get_nb_tetrahedra() == 0 {
+            0
+        } else {
+            self.vertex_coordinates.len()
+        };
+        self.vertex_coordinates.extend_from_slice(to_insert);
+        let mut indices_to_insert: Vec<usize> = (first..self.vertex_coordinates.len()).collect();
+
         if reorder_points {
-            let now = Instant::now();
+            let now = Timer::start();
             indices_to_insert = build_hilbert_curve_3d(self.get_vertices(), &indices_to_insert);
-            let duration = now.elapsed();
-            let nano = duration.as_nanos();
-            log::info!("Hilbert curve computed in {}ms", nano as f32 / 1e6);
+            let nano = now.elapsed_ns();
+            log::debug!("Hilbert curve computed in {}ms", nano as f32 / 1e6);
         }
 
         if self.simpl_struct.get_nb_tetrahedra() == 0 {
@@ -506,21 +884,120 @@ impl DelaunayStructure3D {
         }
 
         let mut last_added = self.simpl_struct.get_nb_tetrahedra() - 1;
-        loop {
-            if let Some(ind_vertex) = indices_to_insert.pop() {
-                last_added = self.insert_vertex_helper(ind_vertex, last_added)?;
-            } else {
-                break;
-            }
+        while let Some(ind_vertex) = indices_to_insert.pop() {
+            last_added = self.insert_vertex_helper(ind_vertex, last_added)?[0];
         }
         self.simpl_struct.clean_to_rem()?;
-        log::info!("Walks computed in {}ms", self.walk_ns as f32 / 1e6);
-        log::info!("Insertions computed in {}ms", self.insert_ns as f32 / 1e6);
+        #[cfg(feature = "timing")]
+        log::debug!("Walks computed in {}ms", self.walk_ns as f32 / 1e6);
+        #[cfg(feature = "timing")]
+        log::debug!("Insertions computed in {}ms", self.insert_ns as f32 / 1e6);
 
         Ok(())
     }
 
+    /// Inserts the midpoint of every finite edge, returning the range of midpoint indices
+    ///
+    /// The number of vertices grows several times at each call: fails without inserting anything
+    /// if memory for the midpoints cannot be reserved.
+    pub fn refine_uniform(&mut self) -> Result<core::ops::Range<usize>> {
+        // each edge taken once, sorted for a deterministic vertex order
+        let mut edges = Vec::new();
+        self.for_each_finite_tetrahedron(|tetra| {
+            for ind1 in 0..4 {
+                for ind2 in ind1 + 1..4 {
+                    edges.push([tetra[ind1].min(tetra[ind2]), tetra[ind1].max(tetra[ind2])]);
+                }
+            }
+        });
+        edges.sort_unstable();
+        edges.dedup();
+        let mut midpoints = Vec::new();
+        midpoints
+            .try_reserve_exact(edges.len())
+            .and_then(|_| self.vertex_coordinates.try_reserve(edges.len()))
+            .map_err(|_| anyhow::Error::msg("Not enough memory for refinement"))?;
+        for [ind1, ind2] in edges {
+            let (pt1, pt2) = (self.vertex_coordinates[ind1], self.vertex_coordinates[ind2]);
+            midpoints.push([
+                (pt1[0] + pt2[0]) * 0.5,
+                (pt1[1] + pt2[1]) * 0.5,
+                (pt1[2] + pt2[2]) * 0.5,
+            ]);
+        }
+        let first = self.vertex_coordinates.len();
+        if !midpoints.is_empty() {
+            self.insert_vertices(&midpoints, true)?;
+        }
+        Ok(first..self.vertex_coordinates.len())
+    }
+
+    /// Writes structure in binary format (little endian)
+    #[cfg(feature = "std")]
+    pub fn write_bin<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        write_header(w, BINARY_MAGIC_3D)?;
+
+        write_u64(w, self.get_vertices().len() as u64)?;
+        for vert in self.get_vertices().iter() {
+            write_f64(w, vert[0])?;
+            write_f64(w, vert[1])?;
+            write_f64(w, vert[2])?;
+        }
+
+        let (tet_nodes, halftriangle_opposite) = self.get_simplicial().raw_tetrahedra();
+        write_u64(w, tet_nodes.len() as u64)?;
+        for node in tet_nodes.iter() {
+            match node {
+                Node::Infinity => write_u64(w, BINARY_INFINITY)?,
+                Node::Value(ind) => write_u64(w, *ind as u64)?,
+            }
+        }
+        for &ind_opp in halftriangle_opposite.iter() {
+            write_u64(w, ind_opp as u64)?;
+        }
+        Ok(())
+    }
+
+    /// Reads structure from binary format
+    #[cfg(feature = "std")]
+    pub fn read_bin<R: Read>(r: &mut R) -> Result<DelaunayStructure3D> {
+        read_header(r, BINARY_MAGIC_3D)?;
+        let mut del = DelaunayStructure3D::new();
+
+        let nb_vertices = read_u64(r)?;
+        for _ in 0..nb_vertices {
+            let x = read_f64(r)?;
+            let y = read_f64(r)?;
+            let z = read_f64(r)?;
+            del.vertex_coordinates.push([x, y, z]);
+        }
+
+        let nb_nodes = read_u64(r)?;
+        let mut tet_nodes = Vec::new();
+        for _ in 0..nb_nodes {
+            let val = read_u64(r)?;
+            if val == BINARY_INFINITY {
+                tet_nodes.push(Node::Infinity);
+            } else {
+                check_index(val as usize, nb_vertices as usize)?;
+                tet_nodes.push(Node::Value(val as usize));
+            }
+        }
+        let mut halftriangle_opposite = Vec::new();
+        for _ in 0..nb_nodes {
+            halftriangle_opposite.push(read_u64(r)? as usize);
+        }
+        del.simpl_struct =
+            SimplicialStructure3D::from_raw_tetrahedra(tet_nodes, halftriangle_opposite)?;
+        // opposite halftriangles share the same nodes in reverse order
+        if !del.simpl_struct.check_opposite_involution()? {
+            return Err(anyhow::Error::msg("Inconsistent tetrahedra arrays"));
+        }
+        Ok(del)
+    }
+
     /// Checks Delaunay graph validity (unit tests purpose)
+    #[must_use = "query result should be used"]
     pub fn is_valid(&self) -> Result<bool> {
         let mut valid = true;
 
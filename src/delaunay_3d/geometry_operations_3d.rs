@@ -1,5 +1,11 @@
+use alloc::vec::Vec;
+use robust::{self, Coord3D};
+
+use crate::compat::sqrt;
+use crate::rng::SplitMix64;
+
 /// Sorts vertices along 3D Hilbert curve
-pub fn build_hilbert_curve_3d(vertices: &Vec<[f64; 3]>, indices_to_add: &Vec<usize>) -> Vec<usize> {
+pub fn build_hilbert_curve_3d(vertices: &[[f64; 3]], indices_to_add: &[usize]) -> Vec<usize> {
     let mut curve_order = Vec::new();
 
     let mut pt_min = vertices[indices_to_add[0]];
@@ -27,75 +33,342 @@ pub fn build_hilbert_curve_3d(vertices: &Vec<[f64; 3]>, indices_to_add: &Vec<usi
     }
 
     let mut to_subdiv = Vec::new();
-    let indices: Vec<usize> = indices_to_add.iter().map(|&x| x).collect();
+    let indices: Vec<usize> = indices_to_add.to_vec();
     to_subdiv.push(([0, 0, 0], 0, pt_min, pt_max, indices));
 
-    loop {
-        if let Some((start, dir, pt_min, pt_max, indices_to_add)) = to_subdiv.pop() {
-            if indices_to_add.len() > 1 {
-                let sep_x = (pt_min[0] + pt_max[0]) / 2.0;
-                let sep_y = (pt_min[1] + pt_max[1]) / 2.0;
-                let sep_z = (pt_min[2] + pt_max[2]) / 2.0;
-
-                let mut sep_ind = [
-                    [[Vec::new(), Vec::new()], [Vec::new(), Vec::new()]],
-                    [[Vec::new(), Vec::new()], [Vec::new(), Vec::new()]],
-                ];
-
-                for &ind in indices_to_add.iter() {
-                    let vert = vertices[ind];
-                    let xind = if vert[0] < sep_x { 0 } else { 1 } as usize;
-                    let yind = if vert[1] < sep_y { 0 } else { 1 } as usize;
-                    let zind = if vert[2] < sep_z { 0 } else { 1 } as usize;
-                    sep_ind[xind][yind][zind].push(ind);
-                }
+    while let Some((start, dir, pt_min, pt_max, indices_to_add)) = to_subdiv.pop() {
+        let sep_x = (pt_min[0] + pt_max[0]) / 2.0;
+        let sep_y = (pt_min[1] + pt_max[1]) / 2.0;
+        let sep_z = (pt_min[2] + pt_max[2]) / 2.0;
+        // cells too small to be split only hold duplicated vertices
+        let splittable = (pt_min[0] < sep_x && sep_x < pt_max[0])
+            || (pt_min[1] < sep_y && sep_y < pt_max[1])
+            || (pt_min[2] < sep_z && sep_z < pt_max[2]);
 
-                let pt_x = [pt_min[0], sep_x, pt_max[0]];
-                let pt_y = [pt_min[1], sep_y, pt_max[1]];
-                let pt_z = [pt_min[2], sep_z, pt_max[2]];
-
-                let (next_modif, dir) = match (dir, start[dir]) {
-                    (0, 0) => Some(([1, 2, 1, 0, 1, 2, 1, 0], [1, 2, 2, 0, 0, 2, 2, 1])),
-                    (0, 1) => Some(([2, 1, 2, 0, 2, 1, 2, 0], [2, 1, 1, 0, 0, 1, 1, 2])),
-                    (1, 0) => Some(([2, 0, 2, 1, 2, 0, 2, 1], [2, 0, 0, 1, 1, 0, 0, 2])),
-                    (1, 1) => Some(([0, 2, 0, 1, 0, 2, 0, 1], [0, 2, 2, 1, 1, 2, 2, 0])),
-                    (2, 0) => Some(([0, 1, 0, 2, 0, 1, 0, 2], [0, 1, 1, 2, 2, 1, 1, 0])),
-                    (2, 1) => Some(([1, 0, 1, 2, 1, 0, 1, 2], [1, 0, 0, 2, 2, 0, 0, 1])),
-                    (_, _) => None,
-                }
-                .unwrap();
-
-                let mut sep_subind = start;
-                let mut start_ind = start;
-                for i in 0..8 {
-                    let mut vec_inds = Vec::new();
-                    vec_inds.append(&mut sep_ind[sep_subind[0]][sep_subind[1]][sep_subind[2]]);
-                    to_subdiv.push((
-                        start_ind,
-                        dir[i],
-                        [
-                            pt_x[sep_subind[0]],
-                            pt_y[sep_subind[1]],
-                            pt_z[sep_subind[2]],
-                        ],
-                        [
-                            pt_x[sep_subind[0] + 1],
-                            pt_y[sep_subind[1] + 1],
-                            pt_z[sep_subind[2] + 1],
-                        ],
-                        vec_inds,
-                    ));
-                    sep_subind[next_modif[i]] = 1 - sep_subind[next_modif[i]];
-                    start_ind[next_modif[i]] = 1 - start_ind[next_modif[i]];
-                    start_ind[dir[i]] = 1 - start_ind[dir[i]];
-                }
-            } else if indices_to_add.len() == 1 {
-                curve_order.push(indices_to_add[0]);
+        if indices_to_add.len() > 1 && splittable {
+            let mut sep_ind = [
+                [[Vec::new(), Vec::new()], [Vec::new(), Vec::new()]],
+                [[Vec::new(), Vec::new()], [Vec::new(), Vec::new()]],
+            ];
+
+            for &ind in indices_to_add.iter() {
+                let vert = vertices[ind];
+                let xind = if vert[0] < sep_x { 0 } else { 1 } as usize;
+                let yind = if vert[1] < sep_y { 0 } else { 1 } as usize;
+                let zind = if vert[2] < sep_z { 0 } else { 1 } as usize;
+                sep_ind[xind][yind][zind].push(ind);
+            }
+
+            let pt_x = [pt_min[0], sep_x, pt_max[0]];
+            let pt_y = [pt_min[1], sep_y, pt_max[1]];
+            let pt_z = [pt_min[2], sep_z, pt_max[2]];
+
+            let (next_modif, dir) = match (dir, start[dir]) {
+                (0, 0) => Some(([1, 2, 1, 0, 1, 2, 1, 0], [1, 2, 2, 0, 0, 2, 2, 1])),
+                (0, 1) => Some(([2, 1, 2, 0, 2, 1, 2, 0], [2, 1, 1, 0, 0, 1, 1, 2])),
+                (1, 0) => Some(([2, 0, 2, 1, 2, 0, 2, 1], [2, 0, 0, 1, 1, 0, 0, 2])),
+                (1, 1) => Some(([0, 2, 0, 1, 0, 2, 0, 1], [0, 2, 2, 1, 1, 2, 2, 0])),
+                (2, 0) => Some(([0, 1, 0, 2, 0, 1, 0, 2], [0, 1, 1, 2, 2, 1, 1, 0])),
+                (2, 1) => Some(([1, 0, 1, 2, 1, 0, 1, 2], [1, 0, 0, 2, 2, 0, 0, 1])),
+                (_, _) => None,
+            }
+            .unwrap();
+
+            let mut sep_subind = start;
+            let mut start_ind = start;
+            for i in 0..8 {
+                let mut vec_inds = Vec::new();
+                vec_inds.append(&mut sep_ind[sep_subind[0]][sep_subind[1]][sep_subind[2]]);
+                to_subdiv.push((
+                    start_ind,
+                    dir[i],
+                    [
+                        pt_x[sep_subind[0]],
+                        pt_y[sep_subind[1]],
+                        pt_z[sep_subind[2]],
+                    ],
+                    [
+                        pt_x[sep_subind[0] + 1],
+                        pt_y[sep_subind[1] + 1],
+                        pt_z[sep_subind[2] + 1],
+                    ],
+                    vec_inds,
+                ));
+                sep_subind[next_modif[i]] = 1 - sep_subind[next_modif[i]];
+                start_ind[next_modif[i]] = 1 - start_ind[next_modif[i]];
+                start_ind[dir[i]] = 1 - start_ind[dir[i]];
             }
         } else {
-            break;
+            curve_order.extend(indices_to_add);
         }
     }
 
     curve_order
 }
+
+fn to_coord(pt: [f64; 3]) -> Coord3D<f64> {
+    Coord3D {
+        x: pt[0],
+        y: pt[1],
+        z: pt[2],
+    }
+}
+
+/// Robust orientation test, positive if pt3 lies below the plane of (pt0, pt1, pt2)
+pub fn orient_3d(pt0: [f64; 3], pt1: [f64; 3], pt2: [f64; 3], pt3: [f64; 3]) -> f64 {
+    robust::orient3d(to_coord(pt0), to_coord(pt1), to_coord(pt2), to_coord(pt3))
+}
+
+/// Checks if a tetrahedron is flat, up to a tolerance relative to its longest edge
+///
+/// The tetrahedron is flat if its volume is at most rel_eps times its cubed longest edge length.
+/// A zero tolerance gives the exact test (robust orientation equal to zero).
+pub fn is_tetrahedron_flat(tetra: [[f64; 3]; 4], rel_eps: f64) -> bool {
+    let orient = orient_3d(tetra[0], tetra[1], tetra[2], tetra[3]);
+    if rel_eps == 0. {
+        return orient == 0.;
+    }
+    let longest = [(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)]
+        .iter()
+        .map(|&(ind1, ind2)| {
+            let (pa, pb) = (tetra[ind1], tetra[ind2]);
+            sqrt(
+                (pb[0] - pa[0]) * (pb[0] - pa[0])
+                    + (pb[1] - pa[1]) * (pb[1] - pa[1])
+                    + (pb[2] - pa[2]) * (pb[2] - pa[2]),
+            )
+        })
+        .fold(0., f64::max);
+    orient.abs() / 6. <= rel_eps * longest * longest * longest
+}
+
+/// Robust insphere test, positive if pt lies inside the sphere of positively oriented
+/// (pt0, pt1, pt2, pt3)
+pub fn in_sphere_3d(
+    pt0: [f64; 3],
+    pt1: [f64; 3],
+    pt2: [f64; 3],
+    pt3: [f64; 3],
+    pt: [f64; 3],
+) -> f64 {
+    robust::insphere(
+        to_coord(pt0),
+        to_coord(pt1),
+        to_coord(pt2),
+        to_coord(pt3),
+        to_coord(pt),
+    )
+}
+
+/// Orientation of a triangle relative to a non flat reference triangle, all points coplanar
+///
+/// Positive if both triangles have the same orientation within their plane.
+pub fn coplanar_relative_orient(tri_ref: [[f64; 3]; 3], tri: [[f64; 3]; 3]) -> f64 {
+    for (ax0, ax1) in [(0, 1), (1, 2), (2, 0)] {
+        let proj = |pt: [f64; 3]| robust::Coord {
+            x: pt[ax0],
+            y: pt[ax1],
+        };
+        let sign_ref = robust::orient2d(proj(tri_ref[0]), proj(tri_ref[1]), proj(tri_ref[2]));
+        if sign_ref != 0. {
+            let sign = robust::orient2d(proj(tri[0]), proj(tri[1]), proj(tri[2]));
+            return sign * sign_ref;
+        }
+    }
+    0.
+}
+
+/// In circle test for coplanar points, positive if pt lies inside the circumcircle of
+/// non flat (pt0, pt1, pt2)
+pub fn coplanar_in_circle(pt0: [f64; 3], pt1: [f64; 3], pt2: [f64; 3], pt: [f64; 3]) -> f64 {
+    // any sphere through the triangle intersects its plane along the circumcircle, such a
+    // sphere is built with an out of plane point
+    let size = (pt1[0] - pt0[0]).abs()
+        + (pt1[1] - pt0[1]).abs()
+        + (pt1[2] - pt0[2]).abs()
+        + (pt2[0] - pt0[0]).abs()
+        + (pt2[1] - pt0[1]).abs()
+        + (pt2[2] - pt0[2]).abs();
+    let mut pt_out = pt0;
+    let mut sign_out = 0.;
+    for axis in 0..3 {
+        pt_out = pt0;
+        pt_out[axis] += size;
+        sign_out = orient_3d(pt0, pt1, pt2, pt_out);
+        if sign_out != 0. {
+            break;
+        }
+    }
+    if sign_out > 0. {
+        in_sphere_3d(pt0, pt1, pt2, pt_out, pt)
+    } else if sign_out < 0. {
+        -in_sphere_3d(pt0, pt1, pt2, pt_out, pt)
+    } else {
+        0.
+    }
+}
+
+/// Volume to edge length ratio of a tetrahedron, 1 for a regular tetrahedron and 0 for a flat one
+///
+/// Computed as 6 sqrt(2) V / l^3, with V the volume (scalar triple product) and l the root mean
+/// square of the edge lengths.
+pub fn tetrahedron_quality(tetra: [[f64; 3]; 4]) -> f64 {
+    let vec = |ind1: usize, ind2: usize| {
+        [
+            tetra[ind2][0] - tetra[ind1][0],
+            tetra[ind2][1] - tetra[ind1][1],
+            tetra[ind2][2] - tetra[ind1][2],
+        ]
+    };
+    let [v1, v2, v3] = [vec(0, 1), vec(0, 2), vec(0, 3)];
+    let triple = v1[0] * (v2[1] * v3[2] - v2[2] * v3[1]) - v1[1] * (v2[0] * v3[2] - v2[2] * v3[0])
+        + v1[2] * (v2[0] * v3[1] - v2[1] * v3[0]);
+
+    let sq_len_sum: f64 = [(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)]
+        .iter()
+        .map(|&(ind1, ind2)| {
+            let v = vec(ind1, ind2);
+            v[0] * v[0] + v[1] * v[1] + v[2] * v[2]
+        })
+        .sum();
+    let len_rms = sqrt(sq_len_sum / 6.);
+    if len_rms == 0. {
+        return 0.;
+    }
+    core::f64::consts::SQRT_2 * triple.abs() / (len_rms * len_rms * len_rms)
+}
+
+fn sub_3d(pt0: [f64; 3], pt1: [f64; 3]) -> [f64; 3] {
+    [pt0[0] - pt1[0], pt0[1] - pt1[1], pt0[2] - pt1[2]]
+}
+
+fn dot_3d(vec0: [f64; 3], vec1: [f64; 3]) -> f64 {
+    vec0[0] * vec1[0] + vec0[1] * vec1[1] + vec0[2] * vec1[2]
+}
+
+fn cross_3d(vec0: [f64; 3], vec1: [f64; 3]) -> [f64; 3] {
+    [
+        vec0[1] * vec1[2] - vec0[2] * vec1[1],
+        vec0[2] * vec1[0] - vec0[0] * vec1[2],
+        vec0[0] * vec1[1] - vec0[1] * vec1[0],
+    ]
+}
+
+fn distance_3d(pt0: [f64; 3], pt1: [f64; 3]) -> f64 {
+    let vec = sub_3d(pt1, pt0);
+    sqrt(dot_3d(vec, vec))
+}
+
+fn diametral_sphere(pt0: [f64; 3], pt1: [f64; 3]) -> ([f64; 3], f64) {
+    (
+        [
+            (pt0[0] + pt1[0]) * 0.5,
+            (pt0[1] + pt1[1]) * 0.5,
+            (pt0[2] + pt1[2]) * 0.5,
+        ],
+        distance_3d(pt0, pt1) * 0.5,
+    )
+}
+
+/// Smallest sphere through 3 points (circumcircle of the triangle)
+fn enclosing_sphere_3(pt0: [f64; 3], pt1: [f64; 3], pt2: [f64; 3]) -> ([f64; 3], f64) {
+    let vec1 = sub_3d(pt1, pt0);
+    let vec2 = sub_3d(pt2, pt0);
+    let normal = cross_3d(vec1, vec2);
+    let sq_normal = dot_3d(normal, normal);
+    if sq_normal == 0. {
+        // aligned points: diametral sphere of the farthest pair
+        let cand = [(pt0, pt1), (pt1, pt2), (pt2, pt0)];
+        let (pa, pb) = cand
+            .into_iter()
+            .max_by(|(pa1, pb1), (pa2, pb2)| {
+                distance_3d(*pa1, *pb1).total_cmp(&distance_3d(*pa2, *pb2))
+            })
+            .unwrap();
+        return diametral_sphere(pa, pb);
+    }
+    let cr1 = cross_3d(vec2, normal);
+    let cr2 = cross_3d(normal, vec1);
+    let (sq1, sq2) = (dot_3d(vec1, vec1), dot_3d(vec2, vec2));
+    let ctr = [
+        pt0[0] + (sq1 * cr1[0] + sq2 * cr2[0]) / (2. * sq_normal),
+        pt0[1] + (sq1 * cr1[1] + sq2 * cr2[1]) / (2. * sq_normal),
+        pt0[2] + (sq1 * cr1[2] + sq2 * cr2[2]) / (2. * sq_normal),
+    ];
+    (ctr, distance_3d(ctr, pt0))
+}
+
+/// Smallest sphere with 3 points on its surface and enclosing a fourth one
+fn enclosing_sphere_4(
+    pt0: [f64; 3],
+    pt1: [f64; 3],
+    pt2: [f64; 3],
+    pt3: [f64; 3],
+) -> ([f64; 3], f64) {
+    if orient_3d(pt0, pt1, pt2, pt3) == 0. {
+        // coplanar points: smallest sphere through 3 of them which encloses the fourth one
+        let pts = [pt0, pt1, pt2, pt3];
+        return [(0, 1, 2, 3), (0, 1, 3, 2), (0, 2, 3, 1), (1, 2, 3, 0)]
+            .into_iter()
+            .map(|(ind0, ind1, ind2, ind3)| {
+                let sphere = enclosing_sphere_3(pts[ind0], pts[ind1], pts[ind2]);
+                let excess = distance_3d(sphere.0, pts[ind3]) - sphere.1;
+                (sphere, excess)
+            })
+            .min_by(|((_, rad1), exc1), ((_, rad2), exc2)| {
+                (*exc1 > 0.).cmp(&(*exc2 > 0.)).then(rad1.total_cmp(rad2))
+            })
+            .map(|(sphere, _)| sphere)
+            .unwrap();
+    }
+    let vec1 = sub_3d(pt1, pt0);
+    let vec2 = sub_3d(pt2, pt0);
+    let vec3 = sub_3d(pt3, pt0);
+    let det = 2. * dot_3d(vec1, cross_3d(vec2, vec3));
+    let cr1 = cross_3d(vec2, vec3);
+    let cr2 = cross_3d(vec3, vec1);
+    let cr3 = cross_3d(vec1, vec2);
+    let (sq1, sq2, sq3) = (dot_3d(vec1, vec1), dot_3d(vec2, vec2), dot_3d(vec3, vec3));
+    let ctr = [
+        pt0[0] + (sq1 * cr1[0] + sq2 * cr2[0] + sq3 * cr3[0]) / det,
+        pt0[1] + (sq1 * cr1[1] + sq2 * cr2[1] + sq3 * cr3[1]) / det,
+        pt0[2] + (sq1 * cr1[2] + sq2 * cr2[2] + sq3 * cr3[2]) / det,
+    ];
+    (ctr, distance_3d(ctr, pt0))
+}
+
+/// Computes minimal enclosing sphere (center, radius) of a set of points (Welzl algorithm, in
+/// its iterative form, on a shuffled copy for expected linear time), None if there is no point
+pub fn minimal_enclosing_sphere(points: &[[f64; 3]]) -> Option<([f64; 3], f64)> {
+    let is_outside =
+        |(ctr, rad): ([f64; 3], f64), pt: [f64; 3]| distance_3d(ctr, pt) > rad * (1. + 1e-12);
+
+    let mut points = points.to_vec();
+    SplitMix64::new(0).shuffle(&mut points);
+
+    let mut sphere = (*points.first()?, 0.);
+    for i in 1..points.len() {
+        if !is_outside(sphere, points[i]) {
+            continue;
+        }
+        sphere = (points[i], 0.);
+        for j in 0..i {
+            if !is_outside(sphere, points[j]) {
+                continue;
+            }
+            sphere = diametral_sphere(points[i], points[j]);
+            for k in 0..j {
+                if !is_outside(sphere, points[k]) {
+                    continue;
+                }
+                sphere = enclosing_sphere_3(points[i], points[j], points[k]);
+                for l in 0..k {
+                    if is_outside(sphere, points[l]) {
+                        sphere = enclosing_sphere_4(points[i], points[j], points[k], points[l]);
+                    }
+                }
+            }
+        }
+    }
+    Some(sphere)
+}
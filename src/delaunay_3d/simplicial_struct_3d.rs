@@ -1,8 +1,13 @@
-use std::vec;
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
 
 use anyhow::Result;
+use core::fmt;
 use log;
 
+use crate::error::check_index;
+
 /// For each triangle index within tetrahedron, associate list of vertices within tetrahedron
 pub const TRIANGLE_SUBINDICES: [[usize; 3]; 4] = [[1, 3, 2], [0, 2, 3], [0, 3, 1], [0, 1, 2]];
 
@@ -34,26 +39,33 @@ impl Node {
             (_, _) => false,
         }
     }
+}
 
-    /// Node to string
-    pub fn to_string(&self) -> String {
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Node::Infinity => "Node Infinity".to_string(),
-            Node::Value(val) => format!("Node {}", val),
+            Node::Infinity => write!(f, "Node Infinity"),
+            Node::Value(val) => write!(f, "Node {}", val),
         }
     }
+}
 
+#[cfg(feature = "std")]
+impl Node {
     /// Print node string
-    pub fn print(&self) -> () {
-        print!("{}", self.to_string());
+    #[deprecated(note = "use the Display implementation")]
+    pub fn print(&self) {
+        std::print!("{}", self);
     }
 
     /// Println node string
-    pub fn println(&self) -> () {
-        println!("{}", self.to_string());
+    #[deprecated(note = "use the Display implementation")]
+    pub fn println(&self) {
+        std::println!("{}", self);
     }
 }
 
+#[derive(Clone)]
 /// 3D Simplicial structure
 pub struct SimplicialStructure3D {
     // i   : nod0 \
@@ -99,6 +111,12 @@ pub struct IterTetrahedron<'a> {
     ind_tetrahedron: usize,
 }
 
+impl Default for SimplicialStructure3D {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl SimplicialStructure3D {
     /// Simplicial structure initialisation
     pub fn new() -> SimplicialStructure3D {
@@ -122,7 +140,7 @@ impl SimplicialStructure3D {
         }
     }
 
-    fn halftriangle(&self, ind_halftriangle: usize) -> IterHalfTriangle {
+    fn halftriangle(&self, ind_halftriangle: usize) -> IterHalfTriangle<'_> {
         IterHalfTriangle {
             simplicial: self,
             ind_halftriangle,
@@ -130,28 +148,29 @@ impl SimplicialStructure3D {
     }
 
     /// Gets halfedge iterator from index
-    pub fn get_halftriangle(&self, ind_halftriangle: usize) -> Result<IterHalfTriangle> {
-        if ind_halftriangle < self.halftriangle_opposite.len() {
-            Ok(self.halftriangle(ind_halftriangle))
-        } else {
-            Err(anyhow::Error::msg("Halftriangle value not in simplicial"))
-        }
+    #[must_use = "query result should be used"]
+    pub fn get_halftriangle(&self, ind_halftriangle: usize) -> Result<IterHalfTriangle<'_>> {
+        check_index(ind_halftriangle, self.halftriangle_opposite.len())?;
+        Ok(self.halftriangle(ind_halftriangle))
     }
 
-    fn tetrahedron(&self, ind_tetrahedron: usize) -> IterTetrahedron {
+    fn tetrahedron(&self, ind_tetrahedron: usize) -> IterTetrahedron<'_> {
         IterTetrahedron {
             simplicial: self,
             ind_tetrahedron,
         }
     }
 
+    /// Iterates over all halftriangles of the structure
+    pub fn half_triangles(&self) -> impl Iterator<Item = IterHalfTriangle<'_>> {
+        (0..self.halftriangle_opposite.len()).map(move |ind| self.halftriangle(ind))
+    }
+
     /// Gets tetrahedron iterator from index
-    pub fn get_tetrahedron(&self, ind_tetrahedron: usize) -> Result<IterTetrahedron> {
-        if ind_tetrahedron < self.nb_tetrahedra {
-            Ok(self.tetrahedron(ind_tetrahedron))
-        } else {
-            Err(anyhow::Error::msg("Tetrahedron value not in simplicial"))
-        }
+    #[must_use = "query result should be used"]
+    pub fn get_tetrahedron(&self, ind_tetrahedron: usize) -> Result<IterTetrahedron<'_>> {
+        check_index(ind_tetrahedron, self.nb_tetrahedra)?;
+        Ok(self.tetrahedron(ind_tetrahedron))
     }
 
     /// Gets number of triangles
@@ -159,8 +178,46 @@ impl SimplicialStructure3D {
         self.nb_tetrahedra
     }
 
+    /// Raw tetrahedra arrays (nodes, halftriangle opposites)
+    #[cfg(feature = "std")]
+    pub(crate) fn raw_tetrahedra(&self) -> (&Vec<Node>, &Vec<usize>) {
+        (&self.tet_nodes, &self.halftriangle_opposite)
+    }
+
+    /// Builds structure from raw tetrahedra arrays
+    #[cfg(feature = "std")]
+    pub(crate) fn from_raw_tetrahedra(
+        tet_nodes: Vec<Node>,
+        halftriangle_opposite: Vec<usize>,
+    ) -> Result<SimplicialStructure3D> {
+        if tet_nodes.len() % 4 != 0
+            || tet_nodes.len() != halftriangle_opposite.len()
+            || halftriangle_opposite
+                .iter()
+                .enumerate()
+                .any(|(ind_tri, &ind_opp)| {
+                    ind_opp >= halftriangle_opposite.len()
+                        || ind_opp / 4 == ind_tri / 4
+                        || halftriangle_opposite[ind_opp] != ind_tri
+                })
+        {
+            return Err(anyhow::Error::msg("Inconsistent tetrahedra arrays"));
+        }
+        let nb_tetrahedra = tet_nodes.len() / 4;
+        Ok(SimplicialStructure3D {
+            tet_nodes,
+            halftriangle_opposite,
+            nb_tetrahedra,
+            should_rem_tet: vec![false; nb_tetrahedra],
+            should_keep_tet: vec![false; nb_tetrahedra],
+            tet_to_rem: Vec::new(),
+            tet_to_keep: Vec::new(),
+            tet_to_check: Vec::new(),
+        })
+    }
+
     /// Gets halfedges containing a pair of nodes
-    pub fn get_halfedge_containing(&self, node1: &Node, node2: &Node) -> Vec<IterHalfEdge> {
+    pub fn get_halfedge_containing(&self, node1: &Node, node2: &Node) -> Vec<IterHalfEdge<'_>> {
         let mut vec_edg = Vec::new();
         for i in 0..self.nb_tetrahedra {
             let first_nod = i << 2;
@@ -176,14 +233,12 @@ impl SimplicialStructure3D {
             if sub_ind_v1 == 4 || sub_ind_v2 == 4 {
                 continue;
             }
-            for j in 0..4 {
+            for (j, tri_subind) in TRIANGLE_SUBINDICES.iter().enumerate() {
                 if j == sub_ind_v1 || j == sub_ind_v2 {
                     continue;
                 }
                 for k in 0..3 {
-                    if TRIANGLE_SUBINDICES[j][k] == sub_ind_v1
-                        && TRIANGLE_SUBINDICES[j][(k + 1) % 3] == sub_ind_v2
-                    {
+                    if tri_subind[k] == sub_ind_v1 && tri_subind[(k + 1) % 3] == sub_ind_v2 {
                         vec_edg.push(self.halfedge(first_nod + j, k));
                         break;
                     }
@@ -200,7 +255,7 @@ impl SimplicialStructure3D {
         node1: &Node,
         node2: &Node,
         node3: &Node,
-    ) -> Option<IterHalfTriangle> {
+    ) -> Option<IterHalfTriangle<'_>> {
         for i in 0..self.nb_tetrahedra {
             let first_nod = i << 2;
             let mut sub_ind_v1 = 4;
@@ -218,14 +273,14 @@ impl SimplicialStructure3D {
             if sub_ind_v1 == 4 || sub_ind_v2 == 4 || sub_ind_v3 == 4 {
                 continue;
             }
-            for j in 0..4 {
+            for (j, tri_subind) in TRIANGLE_SUBINDICES.iter().enumerate() {
                 if j == sub_ind_v1 || j == sub_ind_v2 || j == sub_ind_v3 {
                     continue;
                 }
                 for k in 0..3 {
-                    if TRIANGLE_SUBINDICES[j][k] == sub_ind_v1
-                        && TRIANGLE_SUBINDICES[j][(k + 1) % 3] == sub_ind_v2
-                        && TRIANGLE_SUBINDICES[j][(k + 2) % 3] == sub_ind_v3
+                    if tri_subind[k] == sub_ind_v1
+                        && tri_subind[(k + 1) % 3] == sub_ind_v2
+                        && tri_subind[(k + 2) % 3] == sub_ind_v3
                     {
                         return Some(self.halftriangle(first_nod + j));
                     }
@@ -238,7 +293,7 @@ impl SimplicialStructure3D {
     }
 
     /// Gets tetrahedra containing a specific node
-    pub fn get_tetrahedra_containing(&self, node: &Node) -> Vec<IterTetrahedron> {
+    pub fn get_tetrahedra_containing(&self, node: &Node) -> Vec<IterTetrahedron<'_>> {
         let mut vec_tet = Vec::new();
         for i in 0..self.nb_tetrahedra {
             let first_nod = i << 2;
@@ -255,7 +310,7 @@ impl SimplicialStructure3D {
 
     /// Starts BW insertion, setting a first tetrahedron to remove
     pub fn bw_start(&mut self, ind_first_tetra: usize) -> Result<()> {
-        if self.tet_to_check.len() != 0 || self.tet_to_keep.len() != 0 {
+        if !self.tet_to_check.is_empty() || !self.tet_to_keep.is_empty() {
             return Err(anyhow::Error::msg(
                 "Bowyer Watson algorithm already started",
             ));
@@ -267,22 +322,16 @@ impl SimplicialStructure3D {
 
     /// Gets next tetrahedron to check
     pub fn bw_tetra_to_check(&mut self) -> Option<usize> {
-        loop {
-            if let Some(ind_tetra) = self.tet_to_check.pop() {
-                if self.should_rem_tet[ind_tetra] == false
-                    && self.should_keep_tet[ind_tetra] == false
-                {
-                    return Some(ind_tetra);
-                }
-            } else {
-                break;
+        while let Some(ind_tetra) = self.tet_to_check.pop() {
+            if !self.should_rem_tet[ind_tetra] && !self.should_keep_tet[ind_tetra] {
+                return Some(ind_tetra);
             }
         }
         None
     }
 
     /// Sets tetrahedron to remove
-    pub fn bw_rem_tetra(&mut self, ind_tetra: usize) -> () {
+    pub fn bw_rem_tetra(&mut self, ind_tetra: usize) {
         let tri0 = ind_tetra << 2;
         let tri1 = tri0 + 1;
         let tri2 = tri0 + 2;
@@ -308,7 +357,7 @@ impl SimplicialStructure3D {
 
     /// BW insertion algorithm
     pub fn bw_insert_node(&mut self, nod: Node) -> Result<Vec<usize>> {
-        if self.tet_to_check.len() != 0 {
+        if !self.tet_to_check.is_empty() {
             return Err(anyhow::Error::msg(
                 "Cannot insert node if all tetrahedra are not checked",
             ));
@@ -339,7 +388,7 @@ impl SimplicialStructure3D {
         let mut ind_cur = 0;
         loop {
             let cur_tri = IterHalfTriangle {
-                simplicial: &self,
+                simplicial: self,
                 ind_halftriangle: vec_tri[ind_cur],
             };
             let he = cur_tri.halfedges();
@@ -368,7 +417,7 @@ impl SimplicialStructure3D {
                     vec_nei[ind_cur2][j2] = Some(ind_cur);
                 }
             }
-            ind_cur = ind_cur + 1;
+            ind_cur += 1;
             if ind_cur >= vec_tri.len() {
                 break;
             }
@@ -376,10 +425,10 @@ impl SimplicialStructure3D {
 
         let mut added_tets = Vec::new();
         // 3 - create tetrahedra
-        for i in 0..vec_tri.len() {
+        for &ind_tri in vec_tri.iter() {
             let cur_tri = IterHalfTriangle {
-                simplicial: &self,
-                ind_halftriangle: vec_tri[i],
+                simplicial: self,
+                ind_halftriangle: ind_tri,
             };
             let [nod0, nod1, nod2] = cur_tri.nodes();
             if let Some(ind_add) = self.tet_to_rem.pop() {
@@ -417,21 +466,21 @@ impl SimplicialStructure3D {
             let ind_tri0_nei = if vec_nei[ind_nei0][0] == Some(i) {
                 ind_tet_nei0 * 4 + 1
             } else if vec_nei[ind_nei0][1] == Some(i) {
-                ind_tet_nei0 * 4 + 0
+                ind_tet_nei0 * 4
             } else {
                 ind_tet_nei0 * 4 + 2
             };
             let ind_tri1_nei = if vec_nei[ind_nei1][0] == Some(i) {
                 ind_tet_nei1 * 4 + 1
             } else if vec_nei[ind_nei1][1] == Some(i) {
-                ind_tet_nei1 * 4 + 0
+                ind_tet_nei1 * 4
             } else {
                 ind_tet_nei1 * 4 + 2
             };
             let ind_tri2_nei = if vec_nei[ind_nei2][0] == Some(i) {
                 ind_tet_nei2 * 4 + 1
             } else if vec_nei[ind_nei2][1] == Some(i) {
-                ind_tet_nei2 * 4 + 0
+                ind_tet_nei2 * 4
             } else {
                 ind_tet_nei2 * 4 + 2
             };
@@ -443,12 +492,8 @@ impl SimplicialStructure3D {
             self.halftriangle_opposite[ind_tri_nei] = tri3;
         }
 
-        loop {
-            if let Some(ind_tetra_keep) = self.tet_to_keep.pop() {
-                self.should_keep_tet[ind_tetra_keep] = false;
-            } else {
-                break;
-            }
+        while let Some(ind_tetra_keep) = self.tet_to_keep.pop() {
+            self.should_keep_tet[ind_tetra_keep] = false;
         }
 
         Ok(added_tets)
@@ -456,13 +501,21 @@ impl SimplicialStructure3D {
 
     /// Clean removed tetraedra
     pub fn clean_to_rem(&mut self) -> Result<()> {
+        self.clean_to_rem_tracking(&mut [])
+    }
+
+    /// Removes tetrahedra still marked as removed, updating tracked tetrahedra indices when
+    /// tetrahedra are moved
+    pub fn clean_to_rem_tracking(&mut self, tracked: &mut [usize]) -> Result<()> {
         self.tet_to_rem.sort();
-        loop {
-            if let Some(ind_tetra_rem) = self.tet_to_rem.pop() {
-                self.should_rem_tet[ind_tetra_rem] = false;
-                self.mov_end_tetrahedron(ind_tetra_rem)?;
-            } else {
-                break;
+        while let Some(ind_tetra_rem) = self.tet_to_rem.pop() {
+            self.should_rem_tet[ind_tetra_rem] = false;
+            let ind_last = self.nb_tetrahedra - 1;
+            self.mov_end_tetrahedron(ind_tetra_rem)?;
+            for ind_tetra in tracked.iter_mut() {
+                if *ind_tetra == ind_last {
+                    *ind_tetra = ind_tetra_rem;
+                }
             }
         }
         Ok(())
@@ -482,7 +535,7 @@ impl SimplicialStructure3D {
         self.tet_nodes.push(nod4);
         self.should_rem_tet.push(false);
         self.should_keep_tet.push(false);
-        self.nb_tetrahedra = self.nb_tetrahedra + 1;
+        self.nb_tetrahedra += 1;
 
         (ind_first, ind_first + 1, ind_first + 2, ind_first + 3)
     }
@@ -541,13 +594,13 @@ impl SimplicialStructure3D {
 
         self.should_rem_tet.pop();
         self.should_keep_tet.pop();
-        self.nb_tetrahedra = self.nb_tetrahedra - 1;
+        self.nb_tetrahedra -= 1;
 
         Ok(())
     }
 
     /// Inserts a first tetrahedron in the structure
-    pub fn first_tetrahedron(&mut self, nodes: [usize; 4]) -> Result<[IterTetrahedron; 4]> {
+    pub fn first_tetrahedron(&mut self, nodes: [usize; 4]) -> Result<[IterTetrahedron<'_>; 4]> {
         if self.nb_tetrahedra != 0 {
             return Err(anyhow::Error::msg("Already tetrahedra in simplicial"));
         }
@@ -605,6 +658,7 @@ impl SimplicialStructure3D {
     }
 
     /// Checks validity of simplicial graph (unit tests purposes)
+    #[must_use = "query result should be used"]
     pub fn is_valid(&self) -> Result<bool> {
         let mut valid = true;
 
@@ -623,13 +677,59 @@ impl SimplicialStructure3D {
         Ok(valid)
     }
 
-    /// Println each triangle of the graph
-    pub fn println(&self) -> () {
+    /// Checks that the opposite mapping is an involution without fixed point, opposite
+    /// halftriangles sharing the same nodes in reverse order (cheaper than is_valid)
+    #[must_use = "query result should be used"]
+    pub fn check_opposite_involution(&self) -> Result<bool> {
+        let nb_halftriangles = self.halftriangle_opposite.len();
+        for ind_tri in 0..nb_halftriangles {
+            let ind_opp = self.halftriangle_opposite[ind_tri];
+            if ind_opp >= nb_halftriangles
+                || ind_opp / 4 == ind_tri / 4
+                || self.halftriangle_opposite[ind_opp] != ind_tri
+            {
+                log::error!(
+                    "Halftriangle {}: opposite link is not an involution",
+                    ind_tri
+                );
+                return Ok(false);
+            }
+            if !self.get_halftriangle(ind_tri)?.is_valid() {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Overwrites opposite link of a halftriangle, leaving the other links unchanged (corrupts the
+    /// structure, for tests)
+    #[cfg(feature = "testgen")]
+    pub fn corrupt_opposite_halftriangle(
+        &mut self,
+        ind_halftriangle: usize,
+        ind_opposite: usize,
+    ) -> Result<()> {
+        check_index(ind_halftriangle, self.halftriangle_opposite.len())?;
+        self.halftriangle_opposite[ind_halftriangle] = ind_opposite;
+        Ok(())
+    }
+}
+
+impl fmt::Display for SimplicialStructure3D {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         for ind_tetra in 0..self.nb_tetrahedra {
-            let tetra = self.tetrahedron(ind_tetra);
-            print!("  ");
-            tetra.println();
+            writeln!(f, "  {}", self.tetrahedron(ind_tetra))?;
         }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl SimplicialStructure3D {
+    /// Println each triangle of the graph
+    #[deprecated(note = "use the Display implementation")]
+    pub fn println(&self) {
+        std::print!("{}", self);
     }
 }
 
@@ -709,6 +809,25 @@ impl<'a> IterHalfEdge<'a> {
         }
     }
 
+    /// Iterates over halftriangles around the edge (one per tetrahedron), rotating with
+    /// neighbor().opposite()
+    pub fn edge_ring(self) -> impl Iterator<Item = IterHalfTriangle<'a>> {
+        let first = self;
+        let mut he_cur = Some(self);
+        core::iter::from_fn(move || {
+            let he = he_cur?;
+            let he_next = he.neighbor().opposite();
+            he_cur = if he_next.ind_halftriangle == first.ind_halftriangle
+                && he_next.ind_halfedge == first.ind_halfedge
+            {
+                None
+            } else {
+                Some(he_next)
+            };
+            Some(he.triangle())
+        })
+    }
+
     /// Checks halfedge validity (unit test purposes)
     pub fn is_valid(&self) -> bool {
         let first_node = self.first_node();
@@ -740,24 +859,26 @@ impl<'a> IterHalfEdge<'a> {
 
         valid
     }
+}
 
-    /// Halfedge to string
-    pub fn to_string(&self) -> String {
-        format!(
-            "Edge: {} -> {}",
-            self.first_node().to_string(),
-            self.last_node().to_string()
-        )
+impl fmt::Display for IterHalfEdge<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Edge: {} -> {}", self.first_node(), self.last_node())
     }
+}
 
+#[cfg(feature = "std")]
+impl IterHalfEdge<'_> {
     /// Print halfedge string
-    pub fn print(&self) -> () {
-        print!("{}", self.to_string());
+    #[deprecated(note = "use the Display implementation")]
+    pub fn print(&self) {
+        std::print!("{}", self);
     }
 
     /// Println halfedge string
-    pub fn println(&self) -> () {
-        println!("{}", self.to_string());
+    #[deprecated(note = "use the Display implementation")]
+    pub fn println(&self) {
+        std::println!("{}", self);
     }
 }
 
@@ -833,41 +954,45 @@ impl<'a> IterHalfTriangle<'a> {
 
         let [nod0o, nod1o, nod2o] = self.opposite().nodes();
 
-        if nod0.equals(&nod0o) && nod1.equals(&nod2o) && nod2.equals(&nod1o) {
-            ()
-        } else if nod0.equals(&nod2o) && nod1.equals(&nod1o) && nod2.equals(&nod0o) {
-            ()
-        } else if nod0.equals(&nod1o) && nod1.equals(&nod0o) && nod2.equals(&nod2o) {
-            ()
-        } else {
-            log::error!("{}: Wrong opposite halftriangle", self.to_string());
-            log::error!("{}", self.opposite().to_string());
+        let matching = (nod0.equals(&nod0o) && nod1.equals(&nod2o) && nod2.equals(&nod1o))
+            || (nod0.equals(&nod2o) && nod1.equals(&nod1o) && nod2.equals(&nod0o))
+            || (nod0.equals(&nod1o) && nod1.equals(&nod0o) && nod2.equals(&nod2o));
+        if !matching {
+            log::error!("{}: Wrong opposite halftriangle", self);
+            log::error!("{}", self.opposite());
             return false;
-        };
+        }
 
         true
     }
+}
 
-    /// Triangle to string
-    pub fn to_string(&self) -> String {
+impl fmt::Display for IterHalfTriangle<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let [nod1, nod2, nod3] = self.nodes();
-        format!(
+        write!(
+            f,
             "Triangle {}: {} -> {} -> {}",
             self.ind(),
-            nod1.to_string(),
-            nod2.to_string(),
-            nod3.to_string()
+            nod1,
+            nod2,
+            nod3
         )
     }
+}
 
+#[cfg(feature = "std")]
+impl IterHalfTriangle<'_> {
     /// Print triangle string
-    pub fn print(&self) -> () {
-        print!("{}", self.to_string());
+    #[deprecated(note = "use the Display implementation")]
+    pub fn print(&self) {
+        std::print!("{}", self);
     }
 
     /// Println triangle string
-    pub fn println(&self) -> () {
-        println!("{}", self.to_string());
+    #[deprecated(note = "use the Display implementation")]
+    pub fn println(&self) {
+        std::println!("{}", self);
     }
 }
 
@@ -888,7 +1013,7 @@ impl<'a> IterTetrahedron<'a> {
     /// Returns true if one of the nodes is infinity
     pub fn contains_infinity(&self) -> bool {
         let ind_first = self.ind_tetrahedron << 2;
-        self.simplicial.tet_nodes[ind_first + 0].equals(&Node::Infinity)
+        self.simplicial.tet_nodes[ind_first].equals(&Node::Infinity)
             || self.simplicial.tet_nodes[ind_first + 1].equals(&Node::Infinity)
             || self.simplicial.tet_nodes[ind_first + 2].equals(&Node::Infinity)
             || self.simplicial.tet_nodes[ind_first + 3].equals(&Node::Infinity)
@@ -950,27 +1075,34 @@ impl<'a> IterTetrahedron<'a> {
             }
         }
     }
+}
 
-    /// Tetrahedron to string
-    pub fn to_string(&self) -> String {
+impl fmt::Display for IterTetrahedron<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let [nod1, nod2, nod3, nod4] = self.nodes();
-        format!(
+        write!(
+            f,
             "Tetrahedron {}: {} -> {} -> {} -> {}",
             self.ind(),
-            nod1.to_string(),
-            nod2.to_string(),
-            nod3.to_string(),
-            nod4.to_string()
+            nod1,
+            nod2,
+            nod3,
+            nod4
         )
     }
+}
 
+#[cfg(feature = "std")]
+impl IterTetrahedron<'_> {
     /// Print tetrahedron string
-    pub fn print(&self) -> () {
-        print!("{}", self.to_string());
+    #[deprecated(note = "use the Display implementation")]
+    pub fn print(&self) {
+        std::print!("{}", self);
     }
 
     /// Println tetrahedron string
-    pub fn println(&self) -> () {
-        println!("{}", self.to_string());
+    #[deprecated(note = "use the Display implementation")]
+    pub fn println(&self) {
+        std::println!("{}", self);
     }
 }
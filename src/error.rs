@@ -0,0 +1,97 @@
+use core::fmt;
+
+/// Index beyond the size of the indexed elements (vertices, halfedges, triangles...)
+///
+/// Carried by the returned `anyhow::Error`, retrievable with `downcast_ref`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct IndexOutOfBounds {
+    /// Given index
+    pub index: usize,
+    /// Number of elements
+    pub len: usize,
+}
+
+impl fmt::Display for IndexOutOfBounds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Index {} out of bounds (length {})",
+            self.index, self.len
+        )
+    }
+}
+
+impl core::error::Error for IndexOutOfBounds {}
+
+/// Input point outside the bounds of a structure built with bounds
+///
+/// Carried by the returned `anyhow::Error`, retrievable with `downcast_ref`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PointOutOfBounds {
+    /// Index of the point in the given points
+    pub index: usize,
+}
+
+impl fmt::Display for PointOutOfBounds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Point {} out of structure bounds", self.index)
+    }
+}
+
+impl core::error::Error for PointOutOfBounds {}
+
+/// Walk towards a point exceeding its maximum number of steps (corrupted structure), carried
+/// by the returned `anyhow::Error`
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct WalkFailed {
+    /// Number of steps done before stopping
+    pub nb_steps: usize,
+}
+
+impl fmt::Display for WalkFailed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Walk stopped after {} steps", self.nb_steps)
+    }
+}
+
+impl core::error::Error for WalkFailed {}
+
+/// Malformed line of a text point file, carried by the returned `anyhow::Error`
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Line number, starting from 1
+    pub line: usize,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Malformed point at line {}", self.line)
+    }
+}
+
+impl core::error::Error for ParseError {}
+
+/// Points to triangulate all lying at the same location, carried by the returned
+/// `anyhow::Error`
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct DegenerateInput {
+    /// Number of points
+    pub nb_points: usize,
+}
+
+impl fmt::Display for DegenerateInput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "All {} points are identical", self.nb_points)
+    }
+}
+
+impl core::error::Error for DegenerateInput {}
+
+/// Checks index against number of elements
+pub(crate) fn check_index(index: usize, len: usize) -> anyhow::Result<()> {
+    if index < len {
+        Ok(())
+    } else {
+        Err(anyhow::Error::msg(IndexOutOfBounds { index, len }))
+    }
+}
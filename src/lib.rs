@@ -1,8 +1,124 @@
+#![no_std]
 #![warn(missing_docs)]
 //! Delaunay graph computation library
+//!
+//! One-shot triangulation of a point set:
+//!
+//! ```
+//! let points = vec![[0., 0.], [1., 0.], [0., 1.], [1., 1.2]];
+//! let (vertices, triangles) = simple_delaunay_lib::triangulate_2d(&points).unwrap();
+//! assert_eq!(vertices.len(), 4);
+//! assert_eq!(triangles.len(), 2);
+//!
+//! let points = vec![[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]];
+//! let (vertices, tetrahedra) = simple_delaunay_lib::triangulate_3d(&points).unwrap();
+//! assert_eq!(vertices.len(), 4);
+//! assert_eq!(tetrahedra.len(), 1);
+//! ```
+//!
+//! The structures of [`delaunay_2d`] and [`delaunay_3d`] give access to incremental operations.
+//!
+//! The default `std` feature enables timings and binary IO. Without it, the crate only depends on
+//! `alloc`. The `timing` feature adds the time spent in walks, insertions and flips to debug logs,
+//! measured around each single insertion.
+
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+use alloc::vec;
+use alloc::vec::Vec;
+use anyhow::Result;
+
+use compat::sin_cos;
+use delaunay_2d::delaunay_struct_2d::{DelaunayStructure2D, IndexedMesh2D};
+use delaunay_3d::delaunay_struct_3d::{DelaunayStructure3D, IndexedMesh3D};
 
 /// 2D delaunay algorithm
 pub mod delaunay_2d;
 
 /// 3D delaunay algorithm
 pub mod delaunay_3d;
+
+/// Typed errors, carried by returned `anyhow::Error`
+pub mod error;
+
+/// Deterministic point generators
+#[cfg(feature = "testgen")]
+pub mod testgen;
+
+#[cfg(feature = "std")]
+mod binary_io;
+
+/// Text point files
+#[cfg(feature = "std")]
+pub mod text_io;
+
+mod compat;
+
+mod rng;
+
+// keeps only vertices used by simplices, in input order
+fn compact_simplices<const D: usize, const N: usize>(
+    vertices: &[[f64; D]],
+    simplices: Vec<[usize; N]>,
+) -> (Vec<[f64; D]>, Vec<[usize; N]>) {
+    let mut new_index = vec![usize::MAX; vertices.len()];
+    for simplex in simplices.iter() {
+        for &ind in simplex.iter() {
+            new_index[ind] = 0;
+        }
+    }
+    let mut new_vertices = Vec::new();
+    for (ind, vert) in vertices.iter().enumerate() {
+        if new_index[ind] == 0 {
+            new_index[ind] = new_vertices.len();
+            new_vertices.push(*vert);
+        }
+    }
+    let new_simplices = simplices
+        .into_iter()
+        .map(|simplex| simplex.map(|ind| new_index[ind]))
+        .collect();
+    (new_vertices, new_simplices)
+}
+
+/// Computes 2D Delaunay triangulation of a point set, returns used vertices and finite triangles
+/// (counterclockwise vertex indices)
+pub fn triangulate_2d(points: &[[f64; 2]]) -> Result<IndexedMesh2D> {
+    let mut del_struct = DelaunayStructure2D::new();
+    del_struct.insert_vertices(points, true)?;
+
+    let triangles = del_struct.finite_triangles()?;
+    Ok(compact_simplices(del_struct.get_vertices(), triangles))
+}
+
+/// Computes 3D Delaunay tetrahedralization of a point set, returns used vertices and finite
+/// tetrahedra (positively oriented vertex indices)
+pub fn triangulate_3d(points: &[[f64; 3]]) -> Result<IndexedMesh3D> {
+    let mut del_struct = DelaunayStructure3D::new();
+    del_struct.insert_vertices(points, true)?;
+
+    let mut tetrahedra = Vec::new();
+    del_struct.for_each_finite_tetrahedron(|tetra| tetrahedra.push(tetra));
+    Ok(compact_simplices(del_struct.get_vertices(), tetrahedra))
+}
+
+/// Computes spherical Delaunay triangulation of points given as longitude and latitude (degrees),
+/// returns triangles oriented outward, indexing input points
+///
+/// The triangulation is the convex hull of the points lifted onto the unit sphere
+pub fn delaunay_on_sphere(points_lonlat: &[[f64; 2]]) -> Result<Vec<[usize; 3]>> {
+    let points: Vec<[f64; 3]> = points_lonlat
+        .iter()
+        .map(|&[lon, lat]| {
+            let (sin_lon, cos_lon) = sin_cos(lon.to_radians());
+            let (sin_lat, cos_lat) = sin_cos(lat.to_radians());
+            [cos_lat * cos_lon, cos_lat * sin_lon, sin_lat]
+        })
+        .collect();
+
+    let mut del_struct = DelaunayStructure3D::new();
+    del_struct.insert_vertices(&points, true)?;
+    del_struct.boundary_triangles()
+}
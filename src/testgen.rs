@@ -0,0 +1,81 @@
+//! Usable for tests and benchmarks, without depending on an external random generator.
+//!
+//! ```
+//! use simple_delaunay_lib::delaunay_2d::delaunay_struct_2d::DelaunayStructure2D;
+//! use simple_delaunay_lib::testgen::grid_points_2d;
+//!
+//! let vec_pts = grid_points_2d(10, 10);
+//! let mut del_struct = DelaunayStructure2D::new();
+//! del_struct.insert_vertices(&vec_pts, true).unwrap();
+//! assert!(del_struct.is_valid().unwrap());
+//! ```
+
+use alloc::vec::Vec;
+use std::f64::consts::PI;
+
+use crate::rng::SplitMix64;
+
+/// Uniformly distributed points in [0, 1)², deterministic given the seed
+pub fn random_points_2d(n: usize, seed: u64) -> Vec<[f64; 2]> {
+    let mut rng = SplitMix64::new(seed);
+    (0..n).map(|_| [rng.next_f64(), rng.next_f64()]).collect()
+}
+
+/// Regular grid of nx * ny points in [0, 1)²
+pub fn grid_points_2d(nx: usize, ny: usize) -> Vec<[f64; 2]> {
+    let mut vec_pts = Vec::new();
+    for j in 0..ny {
+        for i in 0..nx {
+            vec_pts.push([(i as f64) / (nx as f64), (j as f64) / (ny as f64)]);
+        }
+    }
+    vec_pts
+}
+
+/// Points evenly distributed on a circle of radius r centered on origin
+pub fn circle_points_2d(n: usize, r: f64) -> Vec<[f64; 2]> {
+    (0..n)
+        .map(|i| {
+            let angle = 2. * PI * (i as f64) / (n as f64);
+            [r * angle.cos(), r * angle.sin()]
+        })
+        .collect()
+}
+
+/// Uniformly distributed points in [0, 1)³, deterministic given the seed
+pub fn random_points_3d(n: usize, seed: u64) -> Vec<[f64; 3]> {
+    let mut rng = SplitMix64::new(seed);
+    (0..n)
+        .map(|_| [rng.next_f64(), rng.next_f64(), rng.next_f64()])
+        .collect()
+}
+
+/// Regular grid of nx * ny * nz points in [0, 1)³
+pub fn grid_points_3d(nx: usize, ny: usize, nz: usize) -> Vec<[f64; 3]> {
+    let mut vec_pts = Vec::new();
+    for k in 0..nz {
+        for j in 0..ny {
+            for i in 0..nx {
+                vec_pts.push([
+                    (i as f64) / (nx as f64),
+                    (j as f64) / (ny as f64),
+                    (k as f64) / (nz as f64),
+                ]);
+            }
+        }
+    }
+    vec_pts
+}
+
+/// Points evenly distributed on a sphere of radius r centered on origin (Fibonacci sphere)
+pub fn sphere_points_3d(n: usize, r: f64) -> Vec<[f64; 3]> {
+    let golden_angle = PI * (3. - 5f64.sqrt());
+    (0..n)
+        .map(|i| {
+            let z = 1. - 2. * ((i as f64) + 0.5) / (n as f64);
+            let rad = (1. - z * z).sqrt();
+            let angle = golden_angle * (i as f64);
+            [r * rad * angle.cos(), r * rad * angle.sin(), r * z]
+        })
+        .collect()
+}
@@ -0,0 +1,55 @@
+use alloc::vec::Vec;
+use anyhow::Result;
+use std::io::BufRead;
+
+use crate::error::ParseError;
+
+/// Text point format, one point per line, coordinates separated by whitespaces or commas
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PointFormat {
+    /// x y
+    Xy,
+    /// x y z
+    Xyz,
+}
+
+impl PointFormat {
+    fn nb_coordinates(&self) -> usize {
+        match self {
+            PointFormat::Xy => 2,
+            PointFormat::Xyz => 3,
+        }
+    }
+}
+
+/// Reads points line by line, calling f on each one, returns the number of points
+///
+/// Empty lines are skipped, malformed lines and non finite coordinates fail with
+/// [`ParseError`].
+pub(crate) fn for_each_point<R: BufRead>(
+    r: R,
+    format: PointFormat,
+    mut f: impl FnMut([f64; 3]) -> Result<()>,
+) -> Result<usize> {
+    let nb_coordinates = format.nb_coordinates();
+    let mut nb_points = 0;
+    for (ind_line, line) in r.lines().enumerate() {
+        let line = line?;
+        let coordinates: Option<Vec<f64>> = line
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|field| !field.is_empty())
+            .map(|field| field.parse().ok().filter(|val: &f64| val.is_finite()))
+            .collect();
+        let mut pt = [0.; 3];
+        match coordinates {
+            Some(coordinates) if coordinates.is_empty() => continue,
+            Some(coordinates) if coordinates.len() == nb_coordinates => {
+                pt[..nb_coordinates].copy_from_slice(&coordinates);
+            }
+            _ => return Err(anyhow::Error::msg(ParseError { line: ind_line + 1 })),
+        }
+        f(pt)?;
+        nb_points += 1;
+    }
+    Ok(nb_points)
+}
@@ -0,0 +1,52 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use simple_delaunay_lib::delaunay_2d::delaunay_struct_2d;
+
+struct CountingAllocator;
+
+static NB_ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        NB_ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[test]
+fn test_insertion_allocations() -> anyhow::Result<()> {
+    // deterministic jittered grid, inserted one by one row after row
+    let nb = 2000;
+    let vec_pts: Vec<[f64; 2]> = (0..nb)
+        .map(|ind| {
+            let jitter_x = ((ind as f64) * 0.618034).fract() * 1e-3;
+            let jitter_y = ((ind as f64) * 0.414214).fract() * 1e-3;
+            [
+                ((ind % 100) as f64) / 100. + jitter_x,
+                ((ind / 100) as f64) / 100. + jitter_y,
+            ]
+        })
+        .collect();
+    let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+    del_struct.insert_vertices(&vec_pts[..10], false)?;
+
+    let nb_before = NB_ALLOCATIONS.load(Ordering::SeqCst);
+    for &pt in vec_pts[10..].iter() {
+        let near_to = del_struct.get_simplicial().get_nb_triangles() - 1;
+        del_struct.insert_vertex(pt, Some(near_to))?;
+    }
+    let nb_allocations = NB_ALLOCATIONS.load(Ordering::SeqCst) - nb_before;
+
+    // only amortized growth of the structure vectors
+    assert!(nb_allocations < nb / 10);
+    assert!(del_struct.is_valid()?);
+    Ok(())
+}
@@ -1,9 +1,16 @@
 #[cfg(test)]
 mod delaunay_2d_test {
     use anyhow::Result;
-    use env_logger;
     use rand::Rng;
     use simple_delaunay_lib::delaunay_2d::delaunay_struct_2d;
+    use simple_delaunay_lib::delaunay_2d::geometry_operations_2d;
+    use simple_delaunay_lib::delaunay_2d::simplicial_struct_2d;
+    use simple_delaunay_lib::delaunay_2d::voronoi_2d;
+    use simple_delaunay_lib::error::{
+        DegenerateInput, IndexOutOfBounds, ParseError, PointOutOfBounds,
+    };
+    use simple_delaunay_lib::text_io::PointFormat;
+    use std::collections::HashSet;
     use std::time::Instant;
 
     #[ctor::ctor]
@@ -11,6 +18,7 @@ mod delaunay_2d_test {
         env_logger::init();
     }
 
+    #[allow(clippy::ptr_arg, clippy::needless_borrow)]
     fn create_and_check_delaunay(vec_pts: &Vec<[f64; 2]>) -> Result<()> {
         let now = Instant::now();
         let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
@@ -21,6 +29,8 @@ mod delaunay_2d_test {
         log::info!("Delaunay computed in {}ms", milli);
 
         log::info!("Checking delaunay");
+        assert!(del_struct.get_simplicial().check_opposite_involution()?);
+        assert_eq!(del_struct.euler_characteristic()?.3, 2);
         assert!(del_struct.is_valid()?);
         Ok(())
     }
@@ -75,6 +85,8 @@ mod delaunay_2d_test {
         log::info!("Delaunay computed in {}ms", milli);
 
         log::info!("Checking delaunay");
+        assert!(del_struct.get_simplicial().check_opposite_involution()?);
+        assert_eq!(del_struct.euler_characteristic()?.3, 2);
         assert!(del_struct.is_valid()?);
 
         let mut vec_pts: Vec<[f64; 2]> = Vec::new();
@@ -92,7 +104,2595 @@ mod delaunay_2d_test {
         log::info!("Delaunay update computed in {}ms", milli);
 
         log::info!("Checking delaunay");
+        assert!(del_struct.get_simplicial().check_opposite_involution()?);
+        assert_eq!(del_struct.euler_characteristic()?.3, 2);
         assert!(del_struct.is_valid()?);
         Ok(())
     }
+
+    #[test]
+    fn test_encroached_constraints() -> Result<()> {
+        let vec_pts: Vec<[f64; 2]> = vec![
+            [0., 0.],
+            [1., 0.],
+            [1., 1.],
+            [0., 1.],
+            [0.5, 0.1],
+            [0.9, 0.5],
+        ];
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, false)?;
+
+        del_struct.insert_constraint(0, 1)?;
+        del_struct.insert_constraint(2, 3)?;
+        del_struct.insert_constraint(0, 2)?;
+        assert!(del_struct.is_constrained_edge(2, 0));
+        assert!(del_struct
+            .get_simplicial()
+            .get_halfedge_between(0, 2)
+            .is_some());
+
+        let encroached = del_struct.encroached_constraints()?;
+        assert_eq!(encroached, vec![[0, 1], [0, 2]]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_clip_to_polygon() -> Result<()> {
+        let mut vec_pts: Vec<[f64; 2]> = Vec::new();
+        for ind in 0..121 {
+            let x = ((ind % 11) as f64) / 10.;
+            let y = ((ind / 11) as f64) / 10.;
+            vec_pts.push([x, y]);
+        }
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+
+        let square = [[0.25, 0.25], [0.75, 0.25], [0.75, 0.75], [0.25, 0.75]];
+        let (clipped, created) = del_struct.clip_to_polygon(&square, false)?;
+        assert!(created.is_empty());
+        assert!(!clipped.is_empty() && clipped.len() < 200);
+        for [ind1, ind2, ind3] in clipped {
+            let vert = del_struct.get_vertices();
+            let x = (vert[ind1][0] + vert[ind2][0] + vert[ind3][0]) / 3.;
+            let y = (vert[ind1][1] + vert[ind2][1] + vert[ind3][1]) / 3.;
+            assert!(x > 0.25 && x < 0.75 && y > 0.25 && y < 0.75);
+        }
+
+        let (clipped, created) = del_struct.clip_to_polygon(&square, true)?;
+        // intersection vertices shared between adjacent triangles
+        for (ind, pt) in created.iter().enumerate() {
+            assert!(created[ind + 1..]
+                .iter()
+                .all(|other| (other[0] - pt[0]).abs() + (other[1] - pt[1]).abs() > 1e-12));
+        }
+        let mut vert = del_struct.get_vertices().clone();
+        vert.extend(created);
+        let mut area = 0.;
+        for [ind1, ind2, ind3] in clipped {
+            let (pt1, pt2, pt3) = (vert[ind1], vert[ind2], vert[ind3]);
+            let tri_area = 0.5
+                * ((pt2[0] - pt1[0]) * (pt3[1] - pt1[1]) - (pt3[0] - pt1[0]) * (pt2[1] - pt1[1]));
+            assert!(tri_area > 0.);
+            area += tri_area;
+        }
+        assert!((area - 0.25).abs() < 1e-9);
+        Ok(())
+    }
+
+    #[test]
+    fn test_collinear_hull() -> Result<()> {
+        let vec_pts = vec![[0., 0.], [1., 0.], [1., 1.], [0., 1.]];
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, false)?;
+
+        // on hull edges, then collinear with hull edges outside of them
+        for vert in [
+            [0.5, 0.],
+            [0.25, 0.],
+            [1., 0.5],
+            [2., 0.],
+            [-1., 0.],
+            [3., 0.],
+            [-1., 1.],
+            [-2., 1.],
+        ] {
+            for near_to in 0..del_struct.get_simplicial().get_nb_triangles() {
+                let mut del_copy = delaunay_struct_2d::DelaunayStructure2D::new();
+                del_copy.insert_vertices(del_struct.get_vertices(), false)?;
+                del_copy.insert_vertex(vert, Some(near_to))?;
+                assert!(del_copy.is_valid()?);
+            }
+            del_struct.insert_vertex(vert, None)?;
+            assert!(del_struct.is_valid()?);
+        }
+        assert_eq!(del_struct.get_simplicial().get_nb_triangles(), 2 * 12 - 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_triangle_coords() -> Result<()> {
+        let vec_pts = vec![[0., 0.], [1., 0.], [0., 1.]];
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, false)?;
+
+        let mut nb_finite = 0;
+        for ind_tri in 0..del_struct.get_simplicial().get_nb_triangles() {
+            if let Some(tri) = del_struct.triangle_coords(ind_tri)? {
+                nb_finite += 1;
+                let ind_first = tri.iter().position(|&pt| pt == [0., 0.]).unwrap();
+                assert_eq!(tri[(ind_first + 1) % 3], [1., 0.]);
+                assert_eq!(tri[(ind_first + 2) % 3], [0., 1.]);
+            }
+        }
+        assert_eq!(nb_finite, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_flip_criterion() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let mut vec_pts: Vec<[f64; 2]> = Vec::new();
+        for _ in 0..200 {
+            let (x, y): (f64, f64) = rng.gen();
+            vec_pts.push([x, y]);
+        }
+
+        let mut del_default = delaunay_struct_2d::DelaunayStructure2D::new();
+        let mut del_closure = delaunay_struct_2d::DelaunayStructure2D::new();
+        let mut del_hull = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_default.insert_vertices(&vec_pts[0..3], false)?;
+        del_closure.insert_vertices(&vec_pts[0..3], false)?;
+        del_hull.insert_vertices(&vec_pts[0..3], false)?;
+
+        // only flips halfedges touching infinity, keeping the hull convex
+        let hull_criterion =
+            |del: &delaunay_struct_2d::DelaunayStructure2D, ind_he: usize| -> Result<bool> {
+                let he = del.get_simplicial().get_halfedge(ind_he)?;
+                if he.triangle().contains_infinity()
+                    || he.opposite_halfedge().triangle().contains_infinity()
+                {
+                    del.should_flip_halfedge(ind_he)
+                } else {
+                    Ok(false)
+                }
+            };
+
+        for &vert in vec_pts[3..].iter() {
+            del_default.insert_vertex(vert, None)?;
+            del_closure.insert_vertex_with_criterion(vert, None, |del, ind_he| {
+                del.should_flip_halfedge(ind_he)
+            })?;
+            del_hull.insert_vertex_with_criterion(vert, None, hull_criterion)?;
+        }
+
+        for ind_tri in 0..del_default.get_simplicial().get_nb_triangles() {
+            let nodes_default = del_default.get_simplicial().get_triangle(ind_tri)?.nodes();
+            let nodes_closure = del_closure.get_simplicial().get_triangle(ind_tri)?.nodes();
+            for (nod_default, nod_closure) in nodes_default.iter().zip(nodes_closure.iter()) {
+                assert!(nod_default.equals(nod_closure));
+            }
+        }
+        assert!(del_closure.is_valid()?);
+
+        assert!(del_hull.get_simplicial().is_valid()?);
+        assert_eq!(
+            del_hull.get_simplicial().get_nb_triangles(),
+            del_default.get_simplicial().get_nb_triangles()
+        );
+        for ind_tri in 0..del_hull.get_simplicial().get_nb_triangles() {
+            if let Some([pt0, pt1, pt2]) = del_hull.triangle_coords(ind_tri)? {
+                assert!(geometry_operations_2d::orient_2d(pt0, pt1, pt2) > 0.);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_binary_round_trip() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let mut vec_pts: Vec<[f64; 2]> = Vec::new();
+        for _ in 0..10000 {
+            let (x, y): (f64, f64) = rng.gen();
+            vec_pts.push([x, y]);
+        }
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+        del_struct.set_fixed(3, true)?;
+
+        let mut buffer = Vec::new();
+        del_struct.write_bin(&mut buffer)?;
+        let mut del_read =
+            delaunay_struct_2d::DelaunayStructure2D::read_bin(&mut buffer.as_slice())?;
+
+        assert_eq!(del_read.get_vertices(), del_struct.get_vertices());
+        assert!(del_read.is_fixed(3));
+        assert!(!del_read.is_fixed(4));
+        assert_eq!(
+            del_read.get_simplicial().get_nb_triangles(),
+            del_struct.get_simplicial().get_nb_triangles()
+        );
+        for ind in 0..del_struct.get_simplicial().get_nb_triangles() {
+            let nodes = del_struct.get_simplicial().get_triangle(ind)?.nodes();
+            let nodes_read = del_read.get_simplicial().get_triangle(ind)?.nodes();
+            for (nod, nod_read) in nodes.iter().zip(nodes_read.iter()) {
+                assert!(nod.equals(nod_read));
+            }
+        }
+        assert!(del_read.get_simplicial().is_valid()?);
+        del_read.insert_vertex([0.5, 0.5], None)?;
+        assert!(del_read.get_simplicial().is_valid()?);
+
+        // first nodes of two halfedges swapped, opposite halfedges not matching anymore
+        let mut buffer_shuffled = buffer.clone();
+        let first_nodes = 5 + 8 + 16 * vec_pts.len() + 8;
+        let (node0, node1) = buffer_shuffled[first_nodes..first_nodes + 16].split_at_mut(8);
+        node0.swap_with_slice(node1);
+        assert!(
+            delaunay_struct_2d::DelaunayStructure2D::read_bin(&mut buffer_shuffled.as_slice())
+                .is_err()
+        );
+
+        // version 1, without fixed vertices
+        let mut buffer_v1 = Vec::new();
+        delaunay_struct_2d::DelaunayStructure2D::new().write_bin(&mut buffer_v1)?;
+        assert_eq!(buffer_v1[4], 2);
+        buffer_v1[4] = 1;
+        buffer_v1.truncate(buffer_v1.len() - 8);
+        let del_v1 = delaunay_struct_2d::DelaunayStructure2D::read_bin(&mut buffer_v1.as_slice())?;
+        assert!(del_v1.get_vertices().is_empty());
+
+        // version mismatch
+        buffer[4] += 1;
+        assert!(delaunay_struct_2d::DelaunayStructure2D::read_bin(&mut buffer.as_slice()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_triangle_left_of_edge() -> Result<()> {
+        let vec_pts = vec![[0., 0.], [1., 0.], [0., 1.]];
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, false)?;
+
+        let ind_tri = del_struct.triangle_left_of_edge(0, 1)?.unwrap();
+        let tri = del_struct.triangle_coords(ind_tri)?.unwrap();
+        assert!(tri.contains(&[0., 0.]) && tri.contains(&[1., 0.]) && tri.contains(&[0., 1.]));
+        assert_eq!(del_struct.triangle_left_of_edge(1, 2)?, Some(ind_tri));
+        assert!(del_struct.triangle_left_of_edge(1, 0)?.is_none());
+        assert!(del_struct.triangle_left_of_edge(0, 5).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_out_of_bounds() -> Result<()> {
+        let vec_pts = vec![[0., 0.], [1., 0.], [0., 1.]];
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, false)?;
+
+        assert!(del_struct.insert_vertex([0.2, 0.2], Some(1000)).is_err());
+        assert_eq!(del_struct.get_vertices().len(), vec_pts.len());
+        assert!(del_struct.is_valid()?);
+        del_struct.insert_vertex([0.2, 0.2], None)?;
+        assert!(del_struct.is_valid()?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_insert_reporting() -> Result<()> {
+        let vec_pts = vec![
+            [0., 0.],
+            [1., 0.],
+            [0., 1.],
+            [0., 0.],
+            [f64::NAN, 0.5],
+            [0.5, f64::INFINITY],
+            [1., 1.],
+            [-0., 1.],
+        ];
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        let status = del_struct.insert_vertices_reporting(&vec_pts)?;
+        assert_eq!(
+            status,
+            vec![Some(0), Some(1), Some(2), None, None, None, Some(3), None]
+        );
+        assert_eq!(del_struct.get_vertices()[3], [1., 1.]);
+
+        let status = del_struct.insert_vertices_reporting(&[[1., 1.], [0.5, 0.25]])?;
+        assert_eq!(status, vec![None, Some(4)]);
+        assert!(del_struct.is_valid()?);
+
+        // registered vertices are not inserted ones
+        del_struct.register_vertices(&[[0.3, 0.3]])?;
+        let status = del_struct.insert_vertices_reporting(&[[0.3, 0.3], [0.25, 0.5]])?;
+        assert_eq!(status, vec![Some(6), Some(7)]);
+        assert!(del_struct.get_simplicial().get_node_halfedge(6).is_some());
+        assert!(del_struct.is_valid()?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_voronoi_edge() -> Result<()> {
+        let vec_pts = vec![[0., 0.], [2., 0.], [0., 2.], [3., 3.]];
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, false)?;
+
+        let ind_he = del_struct
+            .get_simplicial()
+            .get_halfedge_between(1, 2)
+            .unwrap()
+            .ind();
+        let (ctr1, ctr2) = del_struct.voronoi_edge(ind_he)?.unwrap();
+        assert!((ctr1[0] - 1.).abs() < 1e-12 && (ctr1[1] - 1.).abs() < 1e-12);
+        assert!((ctr2[0] - 1.75).abs() < 1e-12 && (ctr2[1] - 1.75).abs() < 1e-12);
+
+        let ind_he_hull = del_struct
+            .get_simplicial()
+            .get_halfedge_between(0, 1)
+            .unwrap()
+            .ind();
+        assert!(del_struct.voronoi_edge(ind_he_hull)?.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_silent_insertion() -> Result<()> {
+        // child run: inserts vertices with uncaptured stdout between markers
+        if std::env::var_os("SDL_SILENT_CHILD").is_some() {
+            let mut rng = rand::thread_rng();
+            let vec_pts: Vec<[f64; 2]> = (0..1000).map(|_| [rng.gen(), rng.gen()]).collect();
+            println!("SILENT_BEGIN");
+            let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+            del_struct.insert_vertices(&vec_pts, true)?;
+            let valid = del_struct.is_valid()?;
+            println!("SILENT_END");
+            assert!(valid);
+            return Ok(());
+        }
+
+        let output = std::process::Command::new(std::env::current_exe()?)
+            .args([
+                "delaunay_2d_test::test_silent_insertion",
+                "--exact",
+                "--nocapture",
+                "--test-threads=1",
+            ])
+            .env("SDL_SILENT_CHILD", "1")
+            .output()?;
+        assert!(output.status.success());
+
+        let stdout = String::from_utf8(output.stdout)?;
+        let begin = stdout.find("SILENT_BEGIN\n").unwrap() + "SILENT_BEGIN\n".len();
+        let end = stdout.find("SILENT_END").unwrap();
+        assert_eq!(&stdout[begin..end], "");
+        Ok(())
+    }
+
+    #[test]
+    fn test_triangles_changed_since() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let vec_pts: Vec<[f64; 2]> = (0..1000).map(|_| [rng.gen(), rng.gen()]).collect();
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+
+        let generation = del_struct.current_generation();
+        assert!(del_struct.triangles_changed_since(generation).is_empty());
+
+        del_struct.insert_vertex([0.5, 0.5], None)?;
+        let ind_new = vec_pts.len();
+        let mut changed = del_struct.triangles_changed_since(generation);
+        changed.sort();
+
+        // triangles touched by the insertion are exactly those around the new vertex
+        let mut around: Vec<usize> = del_struct
+            .get_simplicial()
+            .get_outgoing_halfedges(ind_new)
+            .iter()
+            .map(|he| he.triangle().ind())
+            .collect();
+        around.sort();
+        assert_eq!(changed, around);
+        assert!(changed.len() < del_struct.get_simplicial().get_nb_triangles());
+        Ok(())
+    }
+
+    #[test]
+    fn test_minimal_enclosing_circle() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let ctr = [1., 2.];
+        let rad = 3.;
+        let mut vec_pts = Vec::new();
+        for ind in 0..3 {
+            let ang = (ind as f64) * 2. * std::f64::consts::PI / 3.;
+            vec_pts.push([ctr[0] + rad * ang.cos(), ctr[1] + rad * ang.sin()]);
+        }
+        for _ in 0..500 {
+            let ang: f64 = rng.gen::<f64>() * 2. * std::f64::consts::PI;
+            let dst: f64 = rng.gen::<f64>() * rad * 0.9;
+            vec_pts.push([ctr[0] + dst * ang.cos(), ctr[1] + dst * ang.sin()]);
+        }
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+
+        let hull = del_struct.convex_hull()?;
+        for ind in 0..hull.len() {
+            let pt0 = vec_pts[hull[ind]];
+            let pt1 = vec_pts[hull[(ind + 1) % hull.len()]];
+            let pt2 = vec_pts[hull[(ind + 2) % hull.len()]];
+            assert!(geometry_operations_2d::orient_2d(pt0, pt1, pt2) >= 0.);
+        }
+
+        let (ctr_mec, rad_mec) = del_struct.minimal_enclosing_circle()?;
+        assert!((ctr_mec[0] - ctr[0]).abs() < 1e-9);
+        assert!((ctr_mec[1] - ctr[1]).abs() < 1e-9);
+        assert!((rad_mec - rad).abs() < 1e-9);
+        Ok(())
+    }
+
+    #[test]
+    fn test_triangles_along_segment() -> Result<()> {
+        let mut vec_pts = Vec::new();
+        for ind in 0..100 {
+            vec_pts.push([((ind % 10) as f64) / 10., ((ind / 10) as f64) / 10.]);
+        }
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+
+        let pt_a = [0.03, 0.54];
+        let pt_b = [0.87, 0.54];
+        let vec_tri = del_struct.triangles_along_segment(pt_a, pt_b)?;
+
+        // each of the 9 crossed cells of the row contributes its two triangles, except possibly
+        // at both ends
+        assert!(vec_tri.len() >= 16 && vec_tri.len() <= 18);
+        let contains = |ind_tri: usize, pt: [f64; 2]| -> Result<bool> {
+            let [pt0, pt1, pt2] = del_struct.triangle_coords(ind_tri)?.unwrap();
+            Ok(geometry_operations_2d::orient_2d(pt0, pt1, pt) >= 0.
+                && geometry_operations_2d::orient_2d(pt1, pt2, pt) >= 0.
+                && geometry_operations_2d::orient_2d(pt2, pt0, pt) >= 0.)
+        };
+        assert!(contains(vec_tri[0], pt_a)?);
+        assert!(contains(*vec_tri.last().unwrap(), pt_b)?);
+        for &ind_tri in vec_tri.iter() {
+            for pt in del_struct.triangle_coords(ind_tri)?.unwrap() {
+                assert!(pt[1] > 0.45 && pt[1] < 0.65);
+            }
+        }
+        for win in vec_tri.windows(2) {
+            let shared = del_struct
+                .get_simplicial()
+                .get_triangle(win[0])?
+                .halfedges();
+            assert!(shared
+                .iter()
+                .any(|he| he.opposite_halfedge().triangle().ind() == win[1]));
+        }
+
+        // segment leaving the convex hull stops at its boundary
+        let vec_tri_out = del_struct.triangles_along_segment(pt_a, [2., 0.54])?;
+        assert_eq!(vec_tri_out[..vec_tri.len()], vec_tri[..]);
+        let last_tri = del_struct
+            .triangle_coords(*vec_tri_out.last().unwrap())?
+            .unwrap();
+        assert_eq!(last_tri.iter().filter(|pt| pt[0] == 0.9).count(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_opposite_apex() -> Result<()> {
+        let vec_pts = vec![[0., 0.], [2., 0.], [0., 2.], [3., 3.]];
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, false)?;
+
+        // interior edge 1 -> 2 between triangles (0, 1, 2) and (2, 1, 3)
+        let simpl = del_struct.get_simplicial();
+        let ind_he = simpl.get_halfedge_between(1, 2).unwrap().ind();
+        assert!(simpl
+            .opposite_apex(ind_he)?
+            .equals(&simplicial_struct_2d::Node::Value(3)));
+        let ind_he_opp = simpl.get_halfedge_between(2, 1).unwrap().ind();
+        assert!(simpl
+            .opposite_apex(ind_he_opp)?
+            .equals(&simplicial_struct_2d::Node::Value(0)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_insert_registered() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let vec_pts: Vec<[f64; 2]> = (0..1000).map(|_| [rng.gen(), rng.gen()]).collect();
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        let range = del_struct.register_vertices(&vec_pts)?;
+        assert_eq!(range, 0..1000);
+        assert_eq!(del_struct.get_simplicial().get_nb_triangles(), 0);
+
+        let first_half: Vec<usize> = range.clone().filter(|ind| ind % 2 == 0).collect();
+        let second_half: Vec<usize> = range.filter(|ind| ind % 2 == 1).collect();
+
+        del_struct.insert_registered(&first_half)?;
+        assert!(del_struct.is_valid()?);
+        for ind in 0..1000 {
+            let inserted = del_struct.get_simplicial().get_node_halfedge(ind).is_some();
+            assert_eq!(inserted, ind % 2 == 0);
+        }
+        assert!(del_struct.insert_registered(&first_half[..1]).is_err());
+
+        del_struct.insert_registered(&second_half)?;
+        assert!(del_struct.is_valid()?);
+        assert_eq!(del_struct.get_simplicial().get_nb_triangles(), 2 * 1000 - 2);
+
+        // bounds checked on registration
+        let mut del_bounded =
+            delaunay_struct_2d::DelaunayStructure2D::with_bounds([0., 0.], [1., 1.]);
+        let err = del_bounded
+            .register_vertices(&[[0.5, 0.5], [2., 0.5]])
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<PointOutOfBounds>(),
+            Some(&PointOutOfBounds { index: 1 })
+        );
+        assert!(del_bounded.get_vertices().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_remove_vertices() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let vec_pts: Vec<[f64; 2]> = (0..1000).map(|_| [rng.gen(), rng.gen()]).collect();
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+
+        // hull vertices and a tenth of the vertices, possibly adjacent
+        let hull = del_struct.convex_hull()?;
+        let mut to_remove: Vec<usize> = (0..1000).filter(|ind| ind % 10 == 0).collect();
+        to_remove.retain(|ind| !hull.contains(ind));
+        to_remove.extend_from_slice(&hull[..3]);
+        del_struct.remove_vertices(&to_remove)?;
+
+        assert!(del_struct.is_valid()?);
+        for &ind in to_remove.iter() {
+            assert!(del_struct.get_simplicial().get_node_halfedge(ind).is_none());
+        }
+        let nb_remaining = 1000 - to_remove.len();
+        assert_eq!(
+            del_struct.get_simplicial().get_nb_triangles(),
+            2 * nb_remaining - 2
+        );
+
+        del_struct.remove_vertex(hull[3])?;
+        assert!(del_struct.is_valid()?);
+        assert!(del_struct.remove_vertex(hull[3]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_remove_vertices_failure() -> Result<()> {
+        let vec_pts = [[0., 0.], [1., 0.], [2., 0.], [1., 1.]];
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, false)?;
+        let nb_triangles = del_struct.get_simplicial().get_nb_triangles();
+
+        // first vertex removable, remaining ones aligned without the second one
+        assert!(del_struct.remove_vertices(&[0, 3]).is_err());
+        for ind in 0..vec_pts.len() {
+            assert!(del_struct.get_simplicial().get_node_halfedge(ind).is_some());
+        }
+        assert_eq!(del_struct.get_simplicial().get_nb_triangles(), nb_triangles);
+        assert!(del_struct.is_valid()?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_circumcircle() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let vec_pts: Vec<[f64; 2]> = (0..100).map(|_| [rng.gen(), rng.gen()]).collect();
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+
+        for ind_tri in 0..del_struct.get_simplicial().get_nb_triangles() {
+            let nodes = del_struct.get_simplicial().get_triangle(ind_tri)?.nodes();
+            let pts: Vec<[f64; 2]> = nodes
+                .iter()
+                .filter_map(|node| match node {
+                    simplicial_struct_2d::Node::Value(ind) => Some(vec_pts[*ind]),
+                    simplicial_struct_2d::Node::Infinity => None,
+                })
+                .collect();
+            match del_struct.get_circumcircle(ind_tri)? {
+                delaunay_struct_2d::ExtendedCircle::Circle { center, radius } => {
+                    assert_eq!(pts.len(), 3);
+                    for pt in pts {
+                        let dist =
+                            ((pt[0] - center[0]).powi(2) + (pt[1] - center[1]).powi(2)).sqrt();
+                        assert!((dist - radius).abs() < 1e-9 * radius.max(1.));
+                    }
+                }
+                delaunay_struct_2d::ExtendedCircle::Line { normal, factor } => {
+                    assert_eq!(pts.len(), 2);
+                    assert!((normal[0].powi(2) + normal[1].powi(2) - 1.).abs() < 1e-12);
+                    for pt in pts {
+                        assert!((normal[0] * pt[0] + normal[1] * pt[1] - factor).abs() < 1e-12);
+                    }
+                    for pt in vec_pts.iter() {
+                        assert!(normal[0] * pt[0] + normal[1] * pt[1] - factor < 1e-12);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_clone() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let vec_pts: Vec<[f64; 2]> = (0..500).map(|_| [rng.gen(), rng.gen()]).collect();
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+
+        let snapshot = del_struct.clone();
+        del_struct.insert_vertex([0.5, 0.5], None)?;
+        del_struct.remove_vertex(0)?;
+
+        assert_eq!(snapshot.get_vertices().len(), 500);
+        assert_eq!(snapshot.get_simplicial().get_nb_triangles(), 2 * 500 - 2);
+        assert!(snapshot.get_simplicial().get_node_halfedge(0).is_some());
+        assert!(snapshot.is_valid()?);
+        assert!(del_struct.is_valid()?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_flat_tolerance() -> Result<()> {
+        // area 1e-15, longest edge 1
+        let vec_pts = [[0., 0.], [1., 0.], [0.5, 2e-15]];
+        let [pt0, pt1, pt2] = vec_pts;
+        assert!(!geometry_operations_2d::is_triangle_flat(pt0, pt1, pt2, 0.));
+        assert!(geometry_operations_2d::is_triangle_flat(
+            pt0, pt1, pt2, 1e-12
+        ));
+        assert!(geometry_operations_2d::circle_center(pt0, pt1, pt2).is_some());
+        assert!(
+            geometry_operations_2d::circle_center_with_tolerance(pt0, pt1, pt2, 1e-12).is_none()
+        );
+
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+        let ind_tri = (0..del_struct.get_simplicial().get_nb_triangles())
+            .find(|&ind| {
+                matches!(
+                    del_struct.get_extended_triangle(ind),
+                    Ok(delaunay_struct_2d::ExtendedTriangle::Triangle(_))
+                )
+            })
+            .unwrap();
+        assert!(!del_struct.is_triangle_nearly_flat(ind_tri)?);
+        assert!(del_struct.get_circumcircle(ind_tri).is_ok());
+
+        del_struct.set_flat_tolerance(1e-12);
+        assert!(del_struct.is_triangle_nearly_flat(ind_tri)?);
+        assert!(del_struct.get_circumcircle(ind_tri).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_triangles_with_neighbors() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let vec_pts: Vec<[f64; 2]> = (0..300).map(|_| [rng.gen(), rng.gen()]).collect();
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+
+        let adjacency: std::collections::HashMap<usize, [Option<usize>; 3]> = del_struct
+            .triangles_with_neighbors()
+            .map(|(ind_tri, _, neighbors)| (ind_tri, neighbors))
+            .collect();
+        let nb_finite = (0..del_struct.get_simplicial().get_nb_triangles())
+            .filter(|&ind| {
+                !del_struct
+                    .get_simplicial()
+                    .get_triangle(ind)
+                    .unwrap()
+                    .contains_infinity()
+            })
+            .count();
+        assert_eq!(adjacency.len(), nb_finite);
+
+        let (ind_start, _, _) = del_struct.triangles_with_neighbors().next().unwrap();
+        let mut reached = std::collections::HashSet::from([ind_start]);
+        let mut to_visit = vec![ind_start];
+        while let Some(ind_tri) = to_visit.pop() {
+            for ind_neigh in adjacency[&ind_tri].iter().flatten() {
+                assert!(adjacency.contains_key(ind_neigh));
+                if reached.insert(*ind_neigh) {
+                    to_visit.push(*ind_neigh);
+                }
+            }
+        }
+        assert_eq!(reached.len(), nb_finite);
+        Ok(())
+    }
+
+    #[test]
+    fn test_has_inverted_elements() -> Result<()> {
+        let vec_pts = [[0., 0.], [1., 0.], [1., 1.], [0., 1.], [0.5, 0.5]];
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, false)?;
+        assert!(!del_struct.has_inverted_elements()?);
+
+        // moving the center vertex inside the square keeps orientation
+        del_struct.move_vertex(4, [0.4, 0.6])?;
+        assert!(!del_struct.has_inverted_elements()?);
+
+        // moving it outside crosses the edge [1, 2]
+        del_struct.move_vertex(4, [1.5, 0.5])?;
+        assert!(del_struct.has_inverted_elements()?);
+        assert!(del_struct.move_vertex(5, [0., 0.]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_insert_tuples() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let vec_pts: Vec<(f64, f64)> = (0..200).map(|_| rng.gen()).collect();
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+
+        assert_eq!(del_struct.get_vertices().len(), vec_pts.len());
+        for (vert, pt) in del_struct.get_vertices().iter().zip(vec_pts.iter()) {
+            assert_eq!(*vert, [pt.0, pt.1]);
+        }
+        assert!(del_struct.is_valid()?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_hull_edge_normals() -> Result<()> {
+        let vec_pts = [[0., 0.], [1., 0.], [1., 1.], [0., 1.], [0.5, 0.4]];
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, false)?;
+
+        let normals = del_struct.hull_edge_normals()?;
+        let expected = [
+            ([0, 1], [0., -1.]),
+            ([1, 2], [1., 0.]),
+            ([2, 3], [0., 1.]),
+            ([3, 0], [-1., 0.]),
+        ];
+        assert_eq!(normals.len(), expected.len());
+        for ((edge, normal), (edge_exp, normal_exp)) in normals.iter().zip(expected.iter()) {
+            assert_eq!(edge, edge_exp);
+            assert!((normal[0] - normal_exp[0]).abs() < 1e-12);
+            assert!((normal[1] - normal_exp[1]).abs() < 1e-12);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_triangles_within_subset() -> Result<()> {
+        let nb = 10;
+        let vec_pts: Vec<[f64; 2]> = (0..(nb * nb))
+            .map(|ind| [(ind % nb) as f64, (ind / nb) as f64])
+            .collect();
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+
+        // lower left quadrant: 5x5 vertices, 4x4 cells of two triangles
+        let subset: std::collections::HashSet<usize> = (0..(nb * nb))
+            .filter(|ind| ind % nb < 5 && ind / nb < 5)
+            .collect();
+        let triangles = del_struct.triangles_within_subset(&subset)?;
+        assert_eq!(triangles.len(), 2 * 4 * 4);
+        for tri in triangles.iter() {
+            assert!(tri.iter().all(|ind| subset.contains(ind)));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_constrained_delaunay() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let mut vec_pts: Vec<[f64; 2]> = vec![[0., 0.5], [1., 0.55]];
+        vec_pts.extend((0..500).map(|_| [rng.gen_range(0.01..0.99), rng.gen()]));
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+        assert!(del_struct.is_constrained_delaunay()?);
+
+        del_struct.insert_constraint(0, 1)?;
+        assert!(!del_struct.get_constraints().is_empty());
+        for &[ind1, ind2] in del_struct.get_constraints().iter() {
+            assert!(del_struct
+                .get_simplicial()
+                .get_halfedge_between(ind1, ind2)
+                .is_some());
+        }
+        assert!(del_struct.is_constrained_delaunay()?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_rasterize_linear() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let mut vec_pts: Vec<[f64; 2]> = vec![[0., 0.], [1., 0.], [1., 1.], [0., 1.]];
+        vec_pts.extend((0..200).map(|_| [rng.gen(), rng.gen()]));
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+
+        let linear = |pt: [f64; 2]| 2. * pt[0] - 3. * pt[1] + 1.;
+        let field: Vec<f64> = del_struct
+            .get_vertices()
+            .iter()
+            .map(|&pt| linear(pt))
+            .collect();
+
+        // grid larger than the unit square hull
+        let (w, h) = (30, 20);
+        let bbox = ([-0.5, -0.5], [1.5, 1.5]);
+        let raster = del_struct.rasterize_linear(&field, bbox, w, h)?;
+        assert_eq!(raster.len(), w * h);
+        let mut nb_inside = 0;
+        for j in 0..h {
+            for i in 0..w {
+                let pt = [
+                    -0.5 + (i as f64 + 0.5) * 2. / (w as f64),
+                    -0.5 + (j as f64 + 0.5) * 2. / (h as f64),
+                ];
+                let inside = (0. ..=1.).contains(&pt[0]) && (0. ..=1.).contains(&pt[1]);
+                match raster[j * w + i] {
+                    Some(value) => {
+                        assert!(inside);
+                        assert!((value - linear(pt)).abs() < 1e-10);
+                        nb_inside += 1;
+                    }
+                    None => assert!(!inside),
+                }
+            }
+        }
+        assert!(nb_inside > 0);
+        assert!(del_struct
+            .rasterize_linear(&field[1..], bbox, w, h)
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_are_adjacent() -> Result<()> {
+        // square with a center vertex, linked to all corners
+        let vec_pts = [[0., 0.], [1., 0.], [1., 1.], [0., 1.], [0.5, 0.5]];
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, false)?;
+
+        assert!(del_struct.are_adjacent(0, 1)?);
+        assert!(del_struct.are_adjacent(4, 2)?);
+        assert!(del_struct.are_adjacent(3, 0)?);
+        assert!(!del_struct.are_adjacent(0, 2)?);
+        assert!(!del_struct.are_adjacent(1, 3)?);
+        assert!(del_struct.are_adjacent(0, 5).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_points() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let first_pts: Vec<[f64; 2]> = (0..500).map(|_| [rng.gen(), rng.gen()]).collect();
+        let second_pts: Vec<[f64; 2]> = (0..500)
+            .map(|_| [rng.gen_range(0.5..1.5), rng.gen()])
+            .collect();
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&first_pts, true)?;
+        del_struct.merge_points(&second_pts)?;
+
+        assert_eq!(del_struct.get_vertices().len(), 1000);
+        assert_eq!(del_struct.get_simplicial().get_nb_triangles(), 2 * 1000 - 2);
+        assert!(del_struct.is_valid()?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_finite_triangles_ccw() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let vec_pts: Vec<[f64; 2]> = (0..500).map(|_| [rng.gen(), rng.gen()]).collect();
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+        del_struct.remove_vertices(&[3, 10, 42])?;
+
+        let (vertices, triangles) = del_struct.to_indexed_mesh()?;
+        assert_eq!(vertices.len(), vec_pts.len());
+        assert_eq!(triangles, del_struct.finite_triangles()?);
+        let mut area = 0.;
+        for [ind1, ind2, ind3] in triangles {
+            let signed =
+                geometry_operations_2d::orient_2d(vertices[ind1], vertices[ind2], vertices[ind3]);
+            assert!(signed > 0.);
+            area += 0.5 * signed;
+        }
+        assert!(area > 0. && area < 1.);
+        Ok(())
+    }
+
+    #[test]
+    fn test_flip_counters() -> Result<()> {
+        let mut max_flips = Vec::new();
+        for nb in [16, 64] {
+            // center lies within the common circumcircle of all triangles
+            let vec_pts: Vec<[f64; 2]> = (0..nb)
+                .map(|ind| {
+                    let ang = 2. * std::f64::consts::PI * (ind as f64) / (nb as f64);
+                    [ang.cos(), ang.sin()]
+                })
+                .collect();
+            let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+            del_struct.insert_vertices(&vec_pts, true)?;
+            let nb_flips_before = del_struct.get_total_flips();
+            del_struct.insert_vertex([0., 0.], None)?;
+
+            assert!(del_struct.get_total_flips() - nb_flips_before >= nb - 3);
+            assert!(del_struct.get_max_flips_single_insert() >= nb - 3);
+            assert!(del_struct.get_max_flips_single_insert() <= del_struct.get_total_flips());
+            assert!(del_struct.is_valid()?);
+            max_flips.push(del_struct.get_max_flips_single_insert());
+        }
+        assert!(max_flips[1] > max_flips[0]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_voronoi_halfedge() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let vec_pts: Vec<[f64; 2]> = (0..200).map(|_| [rng.gen(), rng.gen()]).collect();
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+
+        let voronoi: voronoi_2d::VoronoiDiagram =
+            del_struct.to_voronoi_halfedge(([-0.1, -0.1], [1.1, 1.1]))?;
+        let vertices = voronoi.get_vertices();
+        assert_eq!(voronoi.cells().count(), vec_pts.len());
+
+        let mut total_area = 0.;
+        for (site, &pt_site) in vec_pts.iter().enumerate() {
+            let cell = voronoi.get_cell(site).unwrap();
+            assert_eq!(cell.site, site);
+            let nb = cell.vertices.len();
+            assert!(nb >= 3);
+            assert_eq!(cell.edges.len(), nb);
+            for ind in 0..nb {
+                let pt0 = vertices[cell.vertices[ind]];
+                let pt1 = vertices[cell.vertices[(ind + 1) % nb]];
+                let pt2 = vertices[cell.vertices[(ind + 2) % nb]];
+                // convex polygon containing the site
+                assert!(geometry_operations_2d::orient_2d(pt0, pt1, pt_site) > 0.);
+                assert!(geometry_operations_2d::orient_2d(pt0, pt1, pt2) > -1e-12);
+                total_area += 0.5 * geometry_operations_2d::orient_2d([0., 0.], pt0, pt1);
+
+                let edge = voronoi.get_edge(cell.edges[ind]).unwrap();
+                if edge.site == site {
+                    assert_eq!(
+                        edge.vertices,
+                        [cell.vertices[ind], cell.vertices[(ind + 1) % nb]]
+                    );
+                } else {
+                    assert_eq!(edge.neighbor, Some(site));
+                    assert_eq!(
+                        edge.vertices,
+                        [cell.vertices[(ind + 1) % nb], cell.vertices[ind]]
+                    );
+                }
+            }
+        }
+        assert!((total_area - 1.44).abs() < 1e-9);
+
+        for edge in voronoi.edges() {
+            if let Some(neighbor) = edge.neighbor {
+                assert!(del_struct.are_adjacent(edge.site, neighbor)?);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_insert_on_edge() -> Result<()> {
+        let vec_pts = [[0., 0.], [2., 0.], [2., 1.], [0., 1.2]];
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, false)?;
+        del_struct.insert_constraint(0, 2)?;
+
+        // midpoint of the constrained diagonal, then of a hull edge
+        del_struct.insert_vertex([1., 0.5], None)?;
+        del_struct.insert_vertex([1., 0.], None)?;
+        assert!(del_struct.is_constrained_edge(0, 4));
+        assert!(del_struct.is_constrained_edge(4, 2));
+        assert!(!del_struct.is_constrained_edge(0, 2));
+        assert_eq!(
+            del_struct.get_simplicial().get_outgoing_halfedges(4).len(),
+            5
+        );
+        assert_eq!(
+            del_struct.get_simplicial().get_outgoing_halfedges(5).len(),
+            4
+        );
+        assert_eq!(del_struct.get_simplicial().get_nb_triangles(), 2 * 6 - 2);
+        assert!(!del_struct.has_inverted_elements()?);
+        assert!(del_struct.is_constrained_delaunay()?);
+
+        // coinciding vertex is left out of the graph
+        del_struct.insert_vertex([2., 0.], None)?;
+        assert_eq!(del_struct.get_vertices().len(), 7);
+        assert!(del_struct.get_simplicial().get_node_halfedge(6).is_none());
+        assert_eq!(del_struct.merged_vertex(6), None);
+        assert_eq!(del_struct.get_simplicial().get_nb_triangles(), 2 * 6 - 2);
+        assert!(del_struct.get_simplicial().is_valid()?);
+
+        // or mapped to it
+        del_struct.set_duplicate_policy(delaunay_struct_2d::DuplicatePolicy::Merge);
+        del_struct.insert_vertices(&[[1., 0.5], [0.5, 0.5]], true)?;
+        assert_eq!(del_struct.merged_vertex(7), Some(4));
+        assert_eq!(del_struct.merged_vertex(8), None);
+        assert!(del_struct.get_simplicial().get_node_halfedge(7).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_insert_on_hull_edge_from_infinite_triangle() -> Result<()> {
+        let vec_pts = [[0., 0.], [2., 0.], [2., 1.], [0., 1.]];
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, false)?;
+
+        // walks starting from the infinite triangles along the hull end there
+        let infinite_along =
+            |del_struct: &delaunay_struct_2d::DelaunayStructure2D, ind_a: usize, ind_b: usize| {
+                let he = del_struct
+                    .get_simplicial()
+                    .get_halfedge_between(ind_b, ind_a)
+                    .unwrap();
+                assert!(he.triangle().contains_infinity());
+                he.triangle().ind()
+            };
+        let ind_tri = infinite_along(&del_struct, 0, 1);
+        del_struct.insert_vertex([1., 0.], Some(ind_tri))?;
+        let ind_tri = infinite_along(&del_struct, 1, 2);
+        del_struct.insert_vertex([2., 1.], Some(ind_tri))?;
+
+        assert_eq!(
+            del_struct.get_simplicial().get_outgoing_halfedges(4).len(),
+            5
+        );
+        assert!(del_struct.get_simplicial().get_node_halfedge(5).is_none());
+        assert_eq!(del_struct.get_simplicial().get_nb_triangles(), 2 * 5 - 2);
+        assert!(!del_struct.has_inverted_elements()?);
+        assert!(del_struct.is_valid()?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_boundary_vertex() -> Result<()> {
+        let nb = 5;
+        let vec_pts: Vec<[f64; 2]> = (0..(nb * nb))
+            .map(|ind| [(ind % nb) as f64, (ind / nb) as f64])
+            .collect();
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+
+        // corner, edge and interior vertices
+        assert!(del_struct.is_boundary_vertex(0)?);
+        assert!(del_struct.is_boundary_vertex(2)?);
+        assert!(!del_struct.is_boundary_vertex(12)?);
+        let nb_boundary = (0..(nb * nb))
+            .filter(|&ind| del_struct.is_boundary_vertex(ind).unwrap())
+            .count();
+        assert_eq!(nb_boundary, 4 * (nb - 1));
+        assert!(del_struct.is_boundary_vertex(nb * nb).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_edge_length_stats() -> Result<()> {
+        // unit grid, every cell split by one diagonal
+        let nb = 6;
+        let vec_pts: Vec<[f64; 2]> = (0..(nb * nb))
+            .map(|ind| [(ind % nb) as f64, (ind / nb) as f64])
+            .collect();
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+
+        let nb_edges = del_struct.finite_edges().count();
+        assert_eq!(nb_edges, 2 * nb * (nb - 1) + (nb - 1) * (nb - 1));
+        let (len_min, len_mean, len_max) = del_struct.edge_length_stats()?;
+        assert_eq!(len_min, 1.);
+        assert_eq!(len_max, 2f64.sqrt());
+        assert!(len_min < len_mean && len_mean < len_max);
+
+        let histogram = del_struct.edge_length_histogram(4)?;
+        assert_eq!(histogram.iter().sum::<usize>(), nb_edges);
+        assert_eq!(histogram[0], 2 * nb * (nb - 1));
+        assert_eq!(histogram[3], (nb - 1) * (nb - 1));
+        assert!(del_struct.edge_length_histogram(0).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_locally_delaunay() -> Result<()> {
+        // rhombus, split along its short vertical diagonal
+        let vec_pts = [[-1., 0.], [1., 0.], [0., -0.5], [0., 0.5]];
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, false)?;
+        let nb_halfedges = 3 * del_struct.get_simplicial().get_nb_triangles();
+        for ind_he in 0..nb_halfedges {
+            assert!(del_struct.is_locally_delaunay(ind_he)?);
+        }
+
+        // the horizontal diagonal becomes the shortest one
+        del_struct.move_vertex(0, [-0.2, 0.])?;
+        del_struct.move_vertex(1, [0.2, 0.])?;
+        let ind_he = del_struct
+            .get_simplicial()
+            .get_halfedge_between(2, 3)
+            .unwrap()
+            .ind();
+        assert!(!del_struct.is_locally_delaunay(ind_he)?);
+        assert!(!del_struct.has_inverted_elements()?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_flood_fill_domains() -> Result<()> {
+        use simplicial_struct_2d::Node;
+
+        let vec_pts = [
+            // outer square
+            [0., 0.],
+            [10., 0.],
+            [10., 10.],
+            [0., 10.],
+            // inner square (hole)
+            [4., 4.],
+            [6., 4.],
+            [6., 6.],
+            [4., 6.],
+            // ring and hole points
+            [2., 5.],
+            [8., 5.],
+            [5., 2.],
+            [5., 8.],
+            [5., 5.],
+        ];
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+        for ind in 0..4 {
+            del_struct.insert_constraint(ind, (ind + 1) % 4)?;
+            del_struct.insert_constraint(4 + ind, 4 + (ind + 1) % 4)?;
+        }
+
+        // 0 for exterior, 1 for ring, 2 for hole
+        let region = |ind_triangle: usize| -> usize {
+            match del_struct
+                .get_simplicial()
+                .get_triangle(ind_triangle)
+                .unwrap()
+                .nodes()
+            {
+                [Node::Value(ind1), Node::Value(ind2), Node::Value(ind3)] => {
+                    let center = [ind1, ind2, ind3].iter().fold([0., 0.], |acc, &ind| {
+                        [acc[0] + vec_pts[ind][0] / 3., acc[1] + vec_pts[ind][1] / 3.]
+                    });
+                    let in_hole = (4. ..6.).contains(&center[0]) && (4. ..6.).contains(&center[1]);
+                    if in_hole {
+                        2
+                    } else {
+                        1
+                    }
+                }
+                _ => 0,
+            }
+        };
+
+        let nb_triangles = del_struct.get_simplicial().get_nb_triangles();
+        for seed_region in 0..3 {
+            let seed = (0..nb_triangles)
+                .find(|&ind_triangle| region(ind_triangle) == seed_region)
+                .unwrap();
+            let reached = del_struct.flood_fill_domains(seed)?;
+            for (ind_triangle, &is_reached) in reached.iter().enumerate() {
+                assert_eq!(is_reached, region(ind_triangle) == seed_region);
+            }
+        }
+
+        assert!(del_struct.flood_fill_domains(nb_triangles).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_spatial_hint() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let vec_pts: Vec<[f64; 2]> = (0..1000).map(|_| rng.gen()).collect();
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        assert!(del_struct.set_spatial_hint(Some(8)).is_err());
+        del_struct.insert_vertices(&vec_pts, true)?;
+        assert!(del_struct.set_spatial_hint(Some(0)).is_err());
+        del_struct.set_spatial_hint(Some(32))?;
+
+        // scattered single insertions, some outside the hint grid
+        for _ in 0..200 {
+            let (x, y): (f64, f64) = rng.gen();
+            del_struct.insert_vertex([1.2 * x - 0.1, 1.2 * y - 0.1], None)?;
+        }
+        assert!(del_struct.is_valid()?);
+        assert_eq!(del_struct.get_vertices().len(), 1200);
+
+        del_struct.set_spatial_hint(None)?;
+        del_struct.insert_vertex([0.5, 0.5], None)?;
+        assert!(del_struct.is_valid()?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_medial_axis() -> Result<()> {
+        // rectangle boundary sampled counterclockwise
+        let mut vec_pts = Vec::new();
+        for ind in 0..16 {
+            vec_pts.push([ind as f64 * 0.25, 0.]);
+        }
+        for ind in 0..4 {
+            vec_pts.push([4., ind as f64 * 0.25]);
+        }
+        for ind in 0..16 {
+            vec_pts.push([4. - ind as f64 * 0.25, 1.]);
+        }
+        for ind in 0..4 {
+            vec_pts.push([0., 1. - ind as f64 * 0.25]);
+        }
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+        let boundary: Vec<[usize; 2]> = (0..vec_pts.len())
+            .map(|ind| [ind, (ind + 1) % vec_pts.len()])
+            .collect();
+        for &[ind_a, ind_b] in boundary.iter() {
+            del_struct.insert_constraint(ind_a, ind_b)?;
+        }
+
+        let axis = del_struct.medial_axis(&boundary)?;
+        assert!(!axis.is_empty());
+        let eps = 1e-9;
+        for (pt0, pt1) in axis.iter() {
+            for pt in [pt0, pt1] {
+                assert!(pt[0] > -eps && pt[0] < 4. + eps);
+                assert!(pt[1] > -eps && pt[1] < 1. + eps);
+            }
+        }
+
+        // central spine along the rectangle
+        let spine_x: Vec<f64> = axis
+            .iter()
+            .flat_map(|(pt0, pt1)| [pt0, pt1])
+            .filter(|pt| (pt[1] - 0.5).abs() < eps)
+            .map(|pt| pt[0])
+            .collect();
+        assert!(spine_x.iter().any(|&x| x < 1.));
+        assert!(spine_x.iter().any(|&x| x > 3.));
+
+        assert!(del_struct.medial_axis(&[[0, 20]]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_spatial_reorder() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let vec_pts: Vec<[f64; 2]> = (0..2000).map(|_| rng.gen()).collect();
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, false)?;
+
+        let value = |node: simplicial_struct_2d::Node| match node {
+            simplicial_struct_2d::Node::Value(ind) => Some(ind),
+            simplicial_struct_2d::Node::Infinity => None,
+        };
+        let nodes_before: Vec<_> = (0..del_struct.get_simplicial().get_nb_triangles())
+            .map(|ind_tri| {
+                del_struct
+                    .get_simplicial()
+                    .get_triangle(ind_tri)
+                    .unwrap()
+                    .nodes()
+                    .map(value)
+            })
+            .collect();
+
+        let mut permutation = Vec::new();
+        del_struct.spatial_reorder(&mut permutation)?;
+        assert!(del_struct.is_valid()?);
+
+        let mut sorted = permutation.clone();
+        sorted.sort();
+        assert_eq!(sorted, (0..nodes_before.len()).collect::<Vec<usize>>());
+        for (ind_new, &ind_old) in permutation.iter().enumerate() {
+            let nodes = del_struct
+                .get_simplicial()
+                .get_triangle(ind_new)?
+                .nodes()
+                .map(value);
+            assert_eq!(nodes, nodes_before[ind_old]);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_insert_vertices_int() -> Result<()> {
+        let lim = geometry_operations_2d::INT_COORD_LIMIT;
+
+        // exact predicates at the limit of the coordinate range
+        assert_eq!(
+            geometry_operations_2d::orient_2d_int([-lim, -lim], [lim, lim], [lim - 1, lim]),
+            2 * lim as i128
+        );
+        assert_eq!(
+            geometry_operations_2d::orient_2d_int([-lim, -lim], [0, 0], [lim, lim]),
+            0
+        );
+        assert_eq!(
+            geometry_operations_2d::in_circle_int(
+                [-lim, -lim],
+                [lim, -lim],
+                [lim, lim],
+                [-lim, lim]
+            ),
+            0
+        );
+
+        // many collinear points with large coordinates, and one point on each side
+        let mut vec_pts: Vec<[i32; 2]> = (-50..50)
+            .map(|ind: i32| [ind * 5_000_003, ind * 3_000_001])
+            .collect();
+        vec_pts.push([1, -lim]);
+        vec_pts.push([-1, lim]);
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices_int(&vec_pts)?;
+        assert!(del_struct.has_integer_coordinates());
+        assert!(del_struct.is_valid()?);
+
+        let nb_hull = (0..vec_pts.len())
+            .filter(|&ind| del_struct.is_boundary_vertex(ind).unwrap())
+            .count();
+        assert_eq!(nb_hull, 4);
+        assert_eq!(
+            del_struct.finite_triangles()?.len(),
+            2 * vec_pts.len() - 2 - nb_hull
+        );
+
+        assert!(del_struct.insert_vertices_int(&[[lim + 1, 0]]).is_err());
+
+        // floating vertex falls back to floating predicates
+        del_struct.insert_vertex([0.5, 0.25], None)?;
+        assert!(!del_struct.has_integer_coordinates());
+        assert!(del_struct.is_valid()?);
+        assert!(del_struct.insert_vertices_int(&[[3, 4]]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_components_under_length() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        // two clusters of radius 1, ten units apart
+        let vec_pts: Vec<[f64; 2]> = (0..200)
+            .map(|ind| {
+                let (x, y): (f64, f64) = rng.gen();
+                let shift = if ind % 2 == 0 { 0. } else { 10. };
+                [shift + x, y]
+            })
+            .collect();
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+
+        let (nb_components, component) = del_struct.components_under_length(3.)?;
+        assert_eq!(nb_components, 2);
+        for (ind, &comp) in component.iter().enumerate() {
+            assert_eq!(comp, ind % 2);
+        }
+
+        let (nb_components, _) = del_struct.components_under_length(20.)?;
+        assert_eq!(nb_components, 1);
+        let (nb_components, _) = del_struct.components_under_length(0.)?;
+        assert_eq!(nb_components, vec_pts.len());
+        assert!(del_struct.components_under_length(f64::NAN).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_index_out_of_bounds() -> Result<()> {
+        let mut simpl = simplicial_struct_2d::SimplicialStructure2D::new();
+        simpl.first_triangle([0, 1, 2])?;
+        let nb_triangles = simpl.get_nb_triangles();
+
+        let err = simpl
+            .insert_node_within_triangle(3, nb_triangles)
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<IndexOutOfBounds>(),
+            Some(&IndexOutOfBounds {
+                index: nb_triangles,
+                len: nb_triangles
+            })
+        );
+        assert_eq!(simpl.get_nb_triangles(), nb_triangles);
+        assert!(simpl.is_valid()?);
+
+        let err = simpl.get_halfedge(3 * nb_triangles).err().unwrap();
+        assert_eq!(
+            err.downcast_ref::<IndexOutOfBounds>(),
+            Some(&IndexOutOfBounds {
+                index: 3 * nb_triangles,
+                len: 3 * nb_triangles
+            })
+        );
+
+        // constraint read from binary format, ending with its second index and no fixed vertex
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&[[0., 0.], [1., 0.], [0., 1.]], false)?;
+        del_struct.insert_constraint(0, 1)?;
+        let mut buffer = Vec::new();
+        del_struct.write_bin(&mut buffer)?;
+        let len = buffer.len();
+
+        // stored in reverse order, read back sorted
+        let mut buffer_reversed = buffer.clone();
+        buffer_reversed[len - 24..len - 16].copy_from_slice(&1u64.to_le_bytes());
+        buffer_reversed[len - 16..len - 8].copy_from_slice(&0u64.to_le_bytes());
+        let del_read =
+            delaunay_struct_2d::DelaunayStructure2D::read_bin(&mut buffer_reversed.as_slice())?;
+        assert!(del_read.is_constrained_edge(0, 1));
+        assert!(del_read.get_constraints().contains(&[0, 1]));
+
+        buffer[len - 16..len - 8].copy_from_slice(&7u64.to_le_bytes());
+        let err = delaunay_struct_2d::DelaunayStructure2D::read_bin(&mut buffer.as_slice())
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.downcast_ref::<IndexOutOfBounds>(),
+            Some(&IndexOutOfBounds { index: 7, len: 3 })
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_is_valid_parallel() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let vec_pts: Vec<[f64; 2]> = (0..2000).map(|_| rng.gen()).collect();
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+        assert!(del_struct.is_valid()?);
+        assert!(del_struct.is_valid_parallel()?);
+
+        // quad whose diagonal is no longer Delaunay once a vertex is moved
+        let vec_pts = [[0., 0.], [1., 0.], [0., 1.], [1., 1.2]];
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, false)?;
+        assert!(del_struct.is_valid_parallel()?);
+        del_struct.move_vertex(3, [1., 0.8])?;
+        assert!(!del_struct.has_inverted_elements()?);
+        assert!(!del_struct.is_valid()?);
+        assert!(!del_struct.is_valid_parallel()?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_hull_measures() -> Result<()> {
+        let vec_pts = [
+            [0., 0.],
+            [1., 0.],
+            [1., 1.],
+            [0., 1.],
+            [0.5, 0.5],
+            [0.2, 0.7],
+        ];
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+        assert!((del_struct.hull_area()? - 1.).abs() < 1e-12);
+        assert!((del_struct.hull_perimeter()? - 4.).abs() < 1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn test_for_each_finite_triangle() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let vec_pts: Vec<[f64; 2]> = (0..500).map(|_| rng.gen()).collect();
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+
+        let mut nb_calls = 0;
+        del_struct.for_each_finite_triangle(|tri| {
+            let [pt0, pt1, pt2] = tri.map(|ind| vec_pts[ind]);
+            assert!(geometry_operations_2d::orient_2d(pt0, pt1, pt2) > 0.);
+            nb_calls += 1;
+        });
+        assert_eq!(nb_calls, del_struct.finite_triangles()?.len());
+        Ok(())
+    }
+
+    #[test]
+    fn test_vertex_normals_from_heights() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let vec_pts: Vec<[f64; 2]> = (0..300).map(|_| rng.gen()).collect();
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+
+        // tilted plane z = 0.5 x - 2 y + 1, of normal (-0.5, 2, 1)
+        let heights: Vec<f64> = vec_pts
+            .iter()
+            .map(|pt| 0.5 * pt[0] - 2. * pt[1] + 1.)
+            .collect();
+        let norm = (0.25f64 + 4. + 1.).sqrt();
+        let expected = [-0.5 / norm, 2. / norm, 1. / norm];
+        for normal in del_struct.vertex_normals_from_heights(&heights)? {
+            for k in 0..3 {
+                assert!((normal[k] - expected[k]).abs() < 1e-9);
+            }
+        }
+
+        assert!(del_struct
+            .vertex_normals_from_heights(&heights[1..])
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_voronoi_cell_if_inserted() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let vec_pts: Vec<[f64; 2]> = (0..100).map(|_| [rng.gen(), rng.gen()]).collect();
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+
+        let cell_areas =
+            |del_struct: &delaunay_struct_2d::DelaunayStructure2D| -> Result<Vec<f64>> {
+                let voronoi = del_struct.to_voronoi_halfedge(([-1., -1.], [2., 2.]))?;
+                let vertices = voronoi.get_vertices();
+                Ok((0..del_struct.get_vertices().len())
+                    .map(|site| {
+                        let cell = voronoi.get_cell(site).unwrap();
+                        let nb = cell.vertices.len();
+                        (0..nb)
+                            .map(|ind| {
+                                let pt0 = vertices[cell.vertices[ind]];
+                                let pt1 = vertices[cell.vertices[(ind + 1) % nb]];
+                                0.5 * geometry_operations_2d::orient_2d([0., 0.], pt0, pt1)
+                            })
+                            .sum()
+                    })
+                    .collect())
+            };
+
+        let point = [0.4 + 0.2 * rng.gen::<f64>(), 0.4 + 0.2 * rng.gen::<f64>()];
+        let neighbors = del_struct.voronoi_cell_if_inserted(point)?;
+        assert!(neighbors.len() >= 3);
+        assert_eq!(del_struct.get_vertices().len(), vec_pts.len());
+
+        // insert/remove method, comparing cell areas before and after insertion
+        let areas_before = cell_areas(&del_struct)?;
+        let mut inserted = del_struct.clone();
+        inserted.insert_vertex(point, None)?;
+        let areas_after = cell_areas(&inserted)?;
+        let total: f64 = neighbors.iter().map(|(_, area)| area).sum();
+        assert!((total - areas_after[vec_pts.len()]).abs() < 1e-9);
+        for &(ind, area) in neighbors.iter() {
+            assert!(inserted.are_adjacent(ind, vec_pts.len())?);
+            assert!((areas_before[ind] - areas_after[ind] - area).abs() < 1e-9);
+        }
+        let nb_stolen = (0..vec_pts.len())
+            .filter(|&ind| areas_before[ind] - areas_after[ind] > 1e-12)
+            .count();
+        assert_eq!(nb_stolen, neighbors.len());
+
+        assert!(del_struct.voronoi_cell_if_inserted(vec_pts[0]).is_err());
+        assert!(del_struct.voronoi_cell_if_inserted([2., 2.]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_triangle_quality() -> Result<()> {
+        let mut equilateral = delaunay_struct_2d::DelaunayStructure2D::new();
+        equilateral.insert_vertices(&[[0., 0.], [1., 0.], [0.5, 0.75_f64.sqrt()]], false)?;
+        assert!((equilateral.min_quality()? - 1.).abs() < 1e-12);
+        assert!((equilateral.mean_quality()? - 1.).abs() < 1e-12);
+
+        let mut sliver = delaunay_struct_2d::DelaunayStructure2D::new();
+        sliver.insert_vertices(&[[0., 0.], [1., 0.], [0.5, 1e-6]], false)?;
+        let mut nb_finite = 0;
+        for ind_triangle in 0..sliver.get_simplicial().get_nb_triangles() {
+            if sliver
+                .get_simplicial()
+                .get_triangle(ind_triangle)?
+                .contains_infinity()
+            {
+                assert!(sliver.triangle_quality(ind_triangle).is_err());
+            } else {
+                assert!(sliver.triangle_quality(ind_triangle)? < 1e-5);
+                nb_finite += 1;
+            }
+        }
+        assert_eq!(nb_finite, 1);
+
+        let mut square = delaunay_struct_2d::DelaunayStructure2D::new();
+        square.insert_vertices(&[[0., 0.], [1., 0.], [1., 1.], [0., 1.]], false)?;
+        // right isosceles triangles, 2 r / R = 2 (sqrt(2) - 1)
+        let expected = 2. * (2_f64.sqrt() - 1.);
+        assert!((square.min_quality()? - expected).abs() < 1e-12);
+        assert!((square.mean_quality()? - expected).abs() < 1e-12);
+        Ok(())
+    }
+
+    #[cfg(feature = "testgen")]
+    #[test]
+    fn test_check_opposite_involution() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let vec_pts: Vec<[f64; 2]> = (0..100).map(|_| [rng.gen(), rng.gen()]).collect();
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+        assert!(del_struct.get_simplicial().check_opposite_involution()?);
+
+        // first halfedge linked to the second one, which keeps its own opposite
+        del_struct
+            .get_simplicial_mut()
+            .corrupt_opposite_halfedge(0, 1)?;
+        assert!(!del_struct.get_simplicial().check_opposite_involution()?);
+
+        // rejected when read back
+        let mut buffer = Vec::new();
+        del_struct.write_bin(&mut buffer)?;
+        assert!(delaunay_struct_2d::DelaunayStructure2D::read_bin(&mut buffer.as_slice()).is_err());
+        Ok(())
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_insert_vertices_parallel() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let sorted_triangles = |del_struct: &delaunay_struct_2d::DelaunayStructure2D| {
+            let mut triangles: Vec<[usize; 3]> = del_struct
+                .finite_triangles()
+                .unwrap()
+                .into_iter()
+                .map(|tri| {
+                    let ind_min = (0..3).min_by_key(|&ind| tri[ind]).unwrap();
+                    [tri[ind_min], tri[(ind_min + 1) % 3], tri[(ind_min + 2) % 3]]
+                })
+                .collect();
+            triangles.sort();
+            triangles
+        };
+
+        // full validity check being quadratic, only local one on the large point set
+        for nb_vert in [2000, 100000] {
+            let vec_pts: Vec<[f64; 2]> = (0..nb_vert).map(|_| rng.gen()).collect();
+            let mut del_serial = delaunay_struct_2d::DelaunayStructure2D::new();
+            del_serial.insert_vertices(&vec_pts, true)?;
+            let serial_triangles = sorted_triangles(&del_serial);
+
+            // more threads than cores, splitting into more tiles
+            for nb_threads in [1, 8] {
+                let pool = rayon::ThreadPoolBuilder::new()
+                    .num_threads(nb_threads)
+                    .build()?;
+                let mut del_parallel = delaunay_struct_2d::DelaunayStructure2D::new();
+                pool.install(|| del_parallel.insert_vertices_parallel(&vec_pts))?;
+                if nb_vert <= 2000 {
+                    assert!(del_parallel.is_valid()?);
+                } else {
+                    assert!(del_parallel.get_simplicial().check_opposite_involution()?);
+                    assert!(del_parallel.is_valid_parallel()?);
+                }
+                assert_eq!(sorted_triangles(&del_parallel), serial_triangles);
+                assert_eq!(
+                    del_parallel.get_simplicial().get_nb_triangles(),
+                    del_serial.get_simplicial().get_nb_triangles()
+                );
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_same_triangulation() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let vec_pts: Vec<[f64; 2]> = (0..500).map(|_| rng.gen()).collect();
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+
+        let mut reordered = del_struct.clone();
+        let mut permutation = Vec::new();
+        reordered.spatial_reorder(&mut permutation)?;
+        assert!(del_struct.same_triangulation(&reordered));
+        assert!(reordered.same_triangulation(&del_struct));
+
+        let mut moved = del_struct.clone();
+        moved.move_vertex(0, [0.5, 0.5])?;
+        assert!(!del_struct.same_triangulation(&moved));
+        Ok(())
+    }
+
+    #[test]
+    fn test_valence_histogram() -> Result<()> {
+        // hexagonal patch of a lattice with acute triangles, with exact coordinates
+        let nb: i32 = 6;
+        let mut vec_pts = Vec::new();
+        for i in -nb..=nb {
+            for j in -nb..=nb {
+                if (i + j).abs() <= nb {
+                    vec_pts.push([(2 * i + j) as f64, (2 * j) as f64]);
+                }
+            }
+        }
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+
+        let histogram = del_struct.valence_histogram()?;
+        let nb_interior = 3 * (nb - 1) * (nb - 1) + 3 * (nb - 1) + 1;
+        assert_eq!(histogram.len(), 1);
+        assert_eq!(histogram.get(&6), Some(&(nb_interior as usize)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_neighbor_apices() -> Result<()> {
+        // lattice with acute triangles, with exact coordinates
+        let mut vec_pts = Vec::new();
+        for i in -3..=3 {
+            for j in -3..=3 {
+                vec_pts.push([(2 * i + j) as f64, (2 * j) as f64]);
+            }
+        }
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+        let index_of = |pt: [f64; 2]| vec_pts.iter().position(|&vert| vert == pt).unwrap();
+
+        let (ind_a, ind_b, ind_c) = (index_of([0., 0.]), index_of([2., 0.]), index_of([1., 2.]));
+        let ind_tri = del_struct.triangle_left_of_edge(ind_a, ind_b)?.unwrap();
+        let nodes = del_struct.get_simplicial().get_triangle(ind_tri)?.nodes();
+        let apices = del_struct.neighbor_apices(ind_tri)?;
+        for ind in 0..3 {
+            // apex across edge opposite to vertex ind + 2
+            let expected = match nodes[(ind + 2) % 3] {
+                simplicial_struct_2d::Node::Value(ind_v) if ind_v == ind_a => [3., 2.],
+                simplicial_struct_2d::Node::Value(ind_v) if ind_v == ind_b => [-1., 2.],
+                simplicial_struct_2d::Node::Value(ind_v) if ind_v == ind_c => [1., -2.],
+                _ => panic!("Wrong triangle"),
+            };
+            assert_eq!(apices[ind], Some(index_of(expected)));
+        }
+
+        // triangle along the bottom hull edge
+        let ind_hull = del_struct
+            .triangle_left_of_edge(index_of([-7., -6.]), index_of([-5., -6.]))?
+            .unwrap();
+        let apices_hull = del_struct.neighbor_apices(ind_hull)?;
+        assert_eq!(apices_hull.iter().filter(|apex| apex.is_none()).count(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_snap_to_vertex() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let vec_pts: Vec<[f64; 2]> = (0..200).map(|_| rng.gen()).collect();
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+
+        let tolerance = 1e-6;
+        for (ind, pt) in vec_pts.iter().enumerate() {
+            let click = [pt[0] + 0.6 * tolerance, pt[1] - 0.6 * tolerance];
+            assert_eq!(del_struct.snap_to_vertex(click, tolerance)?, Some(ind));
+            let far_click = [pt[0] + 2. * tolerance, pt[1]];
+            assert_eq!(del_struct.snap_to_vertex(far_click, tolerance)?, None);
+        }
+        assert!(del_struct.snap_to_vertex([0.5, 0.5], -1.).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_undo_last_insert() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let vec_pts: Vec<[f64; 2]> = (0..100).map(|_| rng.gen()).collect();
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+        assert!(del_struct.undo_last_insert().is_err());
+
+        del_struct.set_undo_depth(2);
+        let original = del_struct.clone();
+        del_struct.insert_vertex([0.5, 0.5], None)?;
+        let snapshot = del_struct.clone();
+        del_struct.insert_vertex([1.5, 1.2], None)?;
+        del_struct.undo_last_insert()?;
+        assert!(del_struct.is_valid()?);
+        assert!(del_struct.same_triangulation(&snapshot));
+        del_struct.undo_last_insert()?;
+        assert!(del_struct.is_valid()?);
+        assert!(del_struct.same_triangulation(&original));
+        assert!(del_struct.undo_last_insert().is_err());
+
+        // insertion on an edge
+        let mut del_grid = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_grid.insert_vertices(&[[0., 0.], [2., 0.], [2., 2.], [0., 2.], [1., 1.]], true)?;
+        del_grid.set_undo_depth(1);
+        let snapshot = del_grid.clone();
+        del_grid.insert_vertex([1., 0.], None)?;
+        del_grid.undo_last_insert()?;
+        assert!(del_grid.is_valid()?);
+        assert!(del_grid.same_triangulation(&snapshot));
+        Ok(())
+    }
+
+    #[test]
+    fn test_euler_characteristic() -> Result<()> {
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&[[0., 0.], [1., 0.], [1., 1.], [0., 1.], [0.5, 0.4]], true)?;
+        assert_eq!(del_struct.euler_characteristic()?, (5, 8, 4, 2));
+        Ok(())
+    }
+
+    #[test]
+    fn test_refine_uniform() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let vec_pts: Vec<[f64; 2]> = (0..300).map(|_| rng.gen()).collect();
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+
+        let (nb_vertices, nb_edges, nb_triangles, _) = del_struct.euler_characteristic()?;
+        let midpoints = del_struct.refine_uniform()?;
+        assert_eq!(midpoints, nb_vertices..nb_vertices + nb_edges);
+        assert!(del_struct.is_valid()?);
+
+        let (_, _, nb_triangles_refined, characteristic) = del_struct.euler_characteristic()?;
+        assert_eq!(characteristic, 2);
+        assert!(
+            nb_triangles_refined >= 3 * nb_triangles && nb_triangles_refined <= 5 * nb_triangles
+        );
+
+        // exact midpoints, the two triangles split into four
+        let mut del_square = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_square.insert_vertices(&[[0., 0.], [2., 0.], [2., 2.], [0., 2.5]], true)?;
+        assert_eq!(del_square.refine_uniform()?, 4..9);
+        assert_eq!(del_square.finite_triangles()?.len(), 8);
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_vertex_out_of_bounds() -> Result<()> {
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&[[0., 0.], [1., 0.], [0., 1.], [1., 1.2]], true)?;
+        assert_eq!(del_struct.get_vertex(3)?, [1., 1.2]);
+
+        let expected = Some(IndexOutOfBounds { index: 4, len: 4 });
+        let err = del_struct.get_vertex(4).unwrap_err();
+        assert_eq!(err.downcast_ref::<IndexOutOfBounds>(), expected.as_ref());
+        let err = del_struct.triangle_left_of_edge(0, 4).unwrap_err();
+        assert_eq!(err.downcast_ref::<IndexOutOfBounds>(), expected.as_ref());
+        let err = del_struct.remove_vertex(4).unwrap_err();
+        assert_eq!(err.downcast_ref::<IndexOutOfBounds>(), expected.as_ref());
+        assert!(del_struct.is_valid()?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_farthest_point_triangulation() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let vec_pts: Vec<[f64; 2]> = (0..500).map(|_| rng.gen()).collect();
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+
+        let nb_hull = del_struct.convex_hull()?.len();
+        let triangles = del_struct.farthest_point_triangulation()?;
+        assert_eq!(triangles.len(), nb_hull - 2);
+        let coord = |ind: usize| robust::Coord {
+            x: vec_pts[ind][0],
+            y: vec_pts[ind][1],
+        };
+        for tri in triangles.iter() {
+            let [pt0, pt1, pt2] = tri.map(coord);
+            assert!(robust::orient2d(pt0, pt1, pt2) > 0.);
+            // circumcircle contains every vertex, the apices being the farthest ones
+            for ind in 0..vec_pts.len() {
+                assert!(robust::incircle(pt0, pt1, pt2, coord(ind)) >= 0.);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_degenerate_triangles() -> Result<()> {
+        let vec_pts = [[0., 0.], [1., 0.], [0.5, 2e-15], [0.5, 1.]];
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+        assert!(del_struct.degenerate_triangles()?.is_empty());
+
+        // triangle of the three near-collinear points
+        del_struct.set_flat_tolerance(1e-12);
+        let degenerate = del_struct.degenerate_triangles()?;
+        assert_eq!(degenerate.len(), 1);
+        let mut nodes = del_struct.triangle_coords(degenerate[0])?.unwrap();
+        nodes.sort_by(|pt1, pt2| pt1[0].total_cmp(&pt2[0]));
+        assert_eq!(nodes, [vec_pts[0], vec_pts[2], vec_pts[1]]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_with_bounds() -> Result<()> {
+        let mut del_struct =
+            delaunay_struct_2d::DelaunayStructure2D::with_bounds([0., 0.], [1., 1.]);
+        let vec_pts = [[0., 0.], [1., 0.], [2., 0.5], [0., 1.]];
+        let err = del_struct.insert_vertices(&vec_pts, true).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<PointOutOfBounds>(),
+            Some(&PointOutOfBounds { index: 2 })
+        );
+        assert!(del_struct.get_vertices().is_empty());
+
+        del_struct.insert_vertices(&[[0., 0.], [1., 0.], [0., 1.]], true)?;
+        assert!(del_struct.insert_vertex([2., 0.5], None).is_err());
+        del_struct.insert_vertex([1., 1.], None)?;
+        assert_eq!(del_struct.get_vertices().len(), 4);
+        assert!(del_struct.is_valid()?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_triangle_attributes() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let vec_pts: Vec<[f64; 2]> = (0..300).map(|_| rng.gen()).collect();
+        let mut del_struct =
+            delaunay_struct_2d::DelaunayStructure2D::<[usize; 3]>::new_with_attributes();
+        del_struct.insert_vertices(&vec_pts, true)?;
+
+        // sorted vertices of a finite triangle
+        let sorted_nodes = |del: &delaunay_struct_2d::DelaunayStructure2D<[usize; 3]>,
+                            ind_tri: usize| {
+            let nodes = del.get_simplicial().get_triangle(ind_tri).ok()?.nodes();
+            let mut tri = [0; 3];
+            for (ind, node) in nodes.iter().enumerate() {
+                match node {
+                    simplicial_struct_2d::Node::Value(ind_v) => tri[ind] = *ind_v,
+                    simplicial_struct_2d::Node::Infinity => return None,
+                }
+            }
+            tri.sort_unstable();
+            Some(tri)
+        };
+        let mut original = HashSet::new();
+        for ind_tri in 0..del_struct.get_simplicial().get_nb_triangles() {
+            if let Some(tri) = sorted_nodes(&del_struct, ind_tri) {
+                del_struct.set_attr(ind_tri, tri)?;
+                original.insert(tri);
+            }
+        }
+        // untouched triangles keep their attribute, new ones inherit one
+        let check = |del: &delaunay_struct_2d::DelaunayStructure2D<[usize; 3]>| {
+            for ind_tri in 0..del.get_simplicial().get_nb_triangles() {
+                if let Some(tri) = sorted_nodes(del, ind_tri) {
+                    let attr = del.get_attr(ind_tri).copied();
+                    assert!(attr.is_some());
+                    if original.contains(&tri) {
+                        assert_eq!(attr, Some(tri));
+                    }
+                }
+            }
+        };
+
+        let nb_flips = del_struct.get_total_flips();
+        for _ in 0..20 {
+            let (x, y): (f64, f64) = rng.gen();
+            del_struct.insert_vertex([0.1 + 0.8 * x, 0.1 + 0.8 * y], None)?;
+        }
+        assert!(del_struct.get_total_flips() > nb_flips);
+        check(&del_struct);
+
+        // removals move triangles
+        let mut interior = Vec::new();
+        for ind_vertex in 0..vec_pts.len() {
+            if interior.len() < 3 && !del_struct.is_boundary_vertex(ind_vertex)? {
+                interior.push(ind_vertex);
+            }
+        }
+        del_struct.remove_vertices(&interior)?;
+        check(&del_struct);
+        assert!(del_struct.is_valid()?);
+
+        // overwriting an inherited attribute leaves the other triangles sharing it unchanged
+        let (ind_tri, tri) = (0..del_struct.get_simplicial().get_nb_triangles())
+            .find_map(|ind_tri| Some((ind_tri, sorted_nodes(&del_struct, ind_tri)?)))
+            .unwrap();
+        del_struct.insert_vertex(
+            {
+                let pts = del_struct.get_vertices();
+                [
+                    (pts[tri[0]][0] + pts[tri[1]][0] + pts[tri[2]][0]) / 3.,
+                    (pts[tri[0]][1] + pts[tri[1]][1] + pts[tri[2]][1]) / 3.,
+                ]
+            },
+            Some(ind_tri),
+        )?;
+        let ind_new = del_struct.get_vertices().len() - 1;
+        let fan: Vec<usize> = del_struct
+            .get_simplicial()
+            .get_outgoing_halfedges(ind_new)
+            .iter()
+            .map(|he| he.triangle().ind())
+            .collect();
+        for _ in 0..3 {
+            del_struct.set_attr(fan[0], [0, 0, 0])?;
+        }
+        assert_eq!(del_struct.get_attr(fan[0]), Some(&[0, 0, 0]));
+        for &ind_fan in fan[1..].iter() {
+            assert_ne!(del_struct.get_attr(ind_fan), Some(&[0, 0, 0]));
+        }
+
+        // attributes are not stored in binary format
+        let mut buffer = Vec::new();
+        del_struct.write_bin(&mut buffer)?;
+        let del_read =
+            delaunay_struct_2d::DelaunayStructure2D::<[usize; 3]>::read_bin_with_attributes(
+                &mut buffer.as_slice(),
+            )?;
+        assert!(del_read.get_attr(0).is_none());
+        assert!(del_read.is_valid()?);
+        Ok(())
+    }
+
+    #[cfg(feature = "testgen")]
+    #[test]
+    fn test_walk_failed() -> Result<()> {
+        let vec_pts = [[0., 0.], [1., 0.], [0., 1.]];
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+        assert_eq!(del_struct.get_nb_walk_fallbacks(), 0);
+
+        // point on the right of the first halfedge
+        let he = del_struct.get_simplicial().get_halfedge(0)?;
+        let (pt_a, pt_b) = match (he.first_node(), he.last_node()) {
+            (
+                simplicial_struct_2d::Node::Value(ind_a),
+                simplicial_struct_2d::Node::Value(ind_b),
+            ) => (vec_pts[ind_a], vec_pts[ind_b]),
+            _ => panic!("First triangle should be finite"),
+        };
+        let point = [
+            (pt_a[0] + pt_b[0]) * 0.5 + (pt_b[1] - pt_a[1]),
+            (pt_a[1] + pt_b[1]) * 0.5 - (pt_b[0] - pt_a[0]),
+        ];
+        assert!(del_struct
+            .triangles_along_segment(point, [0.2, 0.2])
+            .is_ok());
+
+        // first halfedge linked to the second one, walks across it stay in the first triangle
+        del_struct
+            .get_simplicial_mut()
+            .corrupt_opposite_halfedge(0, 1)?;
+        // queries fall back to checking all triangles as well
+        assert!(del_struct
+            .triangles_along_segment(point, [0.2, 0.2])
+            .is_ok());
+        assert!(del_struct.snap_to_vertex(point, 10.)?.is_some());
+
+        // insertion falls back to checking all triangles
+        del_struct.insert_vertex(point, Some(0))?;
+        assert!(del_struct.get_nb_walk_fallbacks() > 0);
+        assert_eq!(del_struct.get_vertices().len(), 4);
+        Ok(())
+    }
+
+    #[test]
+    fn test_last_insertion_order() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let vec_pts: Vec<[f64; 2]> = (0..200).map(|_| rng.gen()).collect();
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts[..150], true)?;
+        let mut order = del_struct.last_insertion_order().to_vec();
+        order.sort_unstable();
+        assert_eq!(order, (0..150).collect::<Vec<usize>>());
+
+        del_struct.insert_vertices(&vec_pts[150..], true)?;
+        let mut order = del_struct.last_insertion_order().to_vec();
+        order.sort_unstable();
+        assert_eq!(order, (150..200).collect::<Vec<usize>>());
+        Ok(())
+    }
+
+    #[test]
+    fn test_insert_from_reader() -> Result<()> {
+        let rows = "0 0 1.5\n1.0, 0.0, 2\n\n0 1 0.5\n1\t1.2  3\n";
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        let nb_points = del_struct.insert_from_reader(rows.as_bytes(), PointFormat::Xyz)?;
+        assert_eq!(nb_points, 4);
+        assert_eq!(
+            del_struct.get_vertices(),
+            &vec![[0., 0.], [1., 0.], [0., 1.], [1., 1.2]]
+        );
+        assert!(del_struct.is_valid()?);
+
+        let malformed = "0.5 0.5 1\n0.5 x 1\n";
+        let err = del_struct
+            .insert_from_reader(malformed.as_bytes(), PointFormat::Xyz)
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<ParseError>(),
+            Some(&ParseError { line: 2 })
+        );
+        let err = del_struct
+            .insert_from_reader("0.5 0.5 1\n".as_bytes(), PointFormat::Xy)
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<ParseError>(),
+            Some(&ParseError { line: 1 })
+        );
+        let non_finite = "0 0\n1 0\n0 1\n1 1\nnan 0.5\n0.5 0.5\n";
+        let err = del_struct
+            .insert_from_reader(non_finite.as_bytes(), PointFormat::Xy)
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<ParseError>(),
+            Some(&ParseError { line: 5 })
+        );
+        let err = del_struct
+            .insert_from_reader("inf 0.5\n".as_bytes(), PointFormat::Xy)
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<ParseError>(),
+            Some(&ParseError { line: 1 })
+        );
+        assert!(del_struct.is_valid()?);
+
+        // short files stored until a triangle can be built, duplicates not counted
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        assert_eq!(
+            del_struct.insert_from_reader("0 0\n".as_bytes(), PointFormat::Xy)?,
+            0
+        );
+        assert_eq!(
+            del_struct.insert_from_reader("1 0\n0 0\n".as_bytes(), PointFormat::Xy)?,
+            0
+        );
+        assert_eq!(
+            del_struct.insert_from_reader("0 1\n1 0\n2 2\n".as_bytes(), PointFormat::Xy)?,
+            4
+        );
+        assert_eq!(del_struct.get_vertices().len(), 6);
+        assert!(del_struct.is_valid()?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_lower_hull_consistency() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let vec_pts: Vec<[f64; 2]> = (0..1000).map(|_| rng.gen()).collect();
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+        let lifted = del_struct.lifted_vertices();
+        assert_eq!(lifted.len(), vec_pts.len());
+        assert_eq!(
+            lifted[0][2],
+            vec_pts[0][0] * vec_pts[0][0] + vec_pts[0][1] * vec_pts[0][1]
+        );
+        assert!(del_struct.is_lower_hull_consistent()?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fast_predicate_mode() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let vec_pts: Vec<[f64; 2]> = (0..1000).map(|_| rng.gen()).collect();
+
+        let mut del_robust = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_robust.insert_vertices(&vec_pts, true)?;
+        let mut del_fast = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_fast.set_predicate_mode(delaunay_struct_2d::PredicateMode::Fast);
+        del_fast.insert_vertices(&vec_pts, true)?;
+
+        // well separated random points, both predicates agree
+        assert!(del_fast.is_valid()?);
+        assert!(del_fast.same_triangulation(&del_robust));
+        Ok(())
+    }
+
+    #[test]
+    fn test_stitch() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let left: Vec<[f64; 2]> = (0..1000)
+            .map(|_| [rng.gen::<f64>() * 0.5, rng.gen()])
+            .collect();
+        let right: Vec<[f64; 2]> = (0..1000)
+            .map(|_| [0.5 + rng.gen::<f64>() * 0.5, rng.gen()])
+            .collect();
+
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&left, true)?;
+        let mut del_right = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_right.insert_vertices(&right, true)?;
+        del_struct.set_spatial_hint(Some(8))?;
+        let old_gen = del_struct.get_simplicial().current_generation();
+        del_struct.stitch(del_right)?;
+        assert!(del_struct.is_valid()?);
+        let simpl = del_struct.get_simplicial();
+        assert!(simpl.current_generation() > old_gen);
+        assert_eq!(
+            simpl.triangles_changed_since(old_gen).len(),
+            simpl.get_nb_triangles()
+        );
+
+        let mut del_ref = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_ref.insert_vertices(&left, false)?;
+        del_ref.insert_vertices(&right, false)?;
+        assert!(del_struct.same_triangulation(&del_ref));
+
+        // spatial hint rebuilt on the stitched triangles
+        del_struct.insert_vertex([0.5, 0.5], None)?;
+        assert!(del_struct.is_valid()?);
+
+        // overlapping point sets, inserted one by one
+        let mut del_overlap = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_overlap.insert_vertices(&right, true)?;
+        let mut del_both = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_both.insert_vertices(&[[0.4, 0.], [0.9, 0.5], [0.4, 1.]], true)?;
+        del_overlap.stitch(del_both)?;
+        assert_eq!(del_overlap.get_vertices().len(), right.len() + 3);
+        assert!(del_overlap.is_valid()?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_triangle_strips() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let vec_pts: Vec<[f64; 2]> = (0..1000).map(|_| rng.gen()).collect();
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+
+        // counterclockwise triangle starting from its smallest index
+        let normalize = |tri: [usize; 3]| {
+            let ind_min = (0..3).min_by_key(|&ind| tri[ind]).unwrap();
+            [0, 1, 2].map(|ind| tri[(ind_min + ind) % 3])
+        };
+        let mut strip_triangles = Vec::new();
+        let strips = del_struct.to_triangle_strips()?;
+        for strip in strips.iter() {
+            assert!(strip.len() >= 3);
+            for ind in 0..strip.len() - 2 {
+                let [v0, v1, v2] = [strip[ind], strip[ind + 1], strip[ind + 2]].map(|v| v as usize);
+                let tri = if ind % 2 == 0 {
+                    [v0, v1, v2]
+                } else {
+                    [v1, v0, v2]
+                };
+                strip_triangles.push(normalize(tri));
+            }
+        }
+        let nb_strip_triangles = strip_triangles.len();
+        let strip_triangles: HashSet<[usize; 3]> = strip_triangles.into_iter().collect();
+        let finite_triangles: HashSet<[usize; 3]> = del_struct
+            .finite_triangles()?
+            .into_iter()
+            .map(normalize)
+            .collect();
+        assert_eq!(nb_strip_triangles, finite_triangles.len());
+        assert_eq!(strip_triangles, finite_triangles);
+        assert!(strips.len() < finite_triangles.len() / 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_insertion_cavity() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let vec_pts: Vec<[f64; 2]> = (0..1000).map(|_| rng.gen()).collect();
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+        let nb_triangles = del_struct.get_simplicial().get_nb_triangles();
+
+        let point = [0.25 + 0.5 * rng.gen::<f64>(), 0.25 + 0.5 * rng.gen::<f64>()];
+        let cavity = del_struct.insertion_cavity(point)?;
+        assert!(!cavity.is_empty());
+        assert_eq!(del_struct.get_simplicial().get_nb_triangles(), nb_triangles);
+
+        // exactly the finite triangles whose circumcircle contains the point
+        let in_cavity: HashSet<usize> = cavity.iter().copied().collect();
+        assert_eq!(in_cavity.len(), cavity.len());
+        for ind_tri in 0..nb_triangles {
+            if let delaunay_struct_2d::ExtendedCircle::Circle { center, radius } =
+                del_struct.get_circumcircle(ind_tri)?
+            {
+                let dist = ((point[0] - center[0]).powi(2) + (point[1] - center[1]).powi(2)).sqrt();
+                assert_eq!(dist < radius, in_cavity.contains(&ind_tri));
+            }
+        }
+
+        // edge connected
+        let mut reached = HashSet::from([cavity[0]]);
+        let mut to_visit = vec![cavity[0]];
+        while let Some(ind_tri) = to_visit.pop() {
+            for he in del_struct
+                .get_simplicial()
+                .get_triangle(ind_tri)?
+                .halfedges()
+            {
+                let ind_opp = he.opposite_halfedge().triangle().ind();
+                if in_cavity.contains(&ind_opp) && reached.insert(ind_opp) {
+                    to_visit.push(ind_opp);
+                }
+            }
+        }
+        assert_eq!(reached, in_cavity);
+        Ok(())
+    }
+
+    #[test]
+    fn test_insert_vertex_from_empty() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+
+        // duplicated and aligned vertices before the first triangle
+        for vert in [[0., 0.], [0., 0.], [1., 1.], [2., 2.]] {
+            del_struct.insert_vertex(vert, None)?;
+            assert_eq!(del_struct.get_simplicial().get_nb_triangles(), 0);
+        }
+        del_struct.insert_vertex([2., 0.], None)?;
+        assert!(del_struct.get_simplicial().get_nb_triangles() > 0);
+        assert!(del_struct.is_valid()?);
+
+        for _ in 0..100 {
+            del_struct.insert_vertex([2. * rng.gen::<f64>(), 2. * rng.gen::<f64>()], None)?;
+        }
+        assert_eq!(del_struct.get_vertices().len(), 105);
+        assert!(del_struct.is_valid()?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_mixed_insertion_from_empty() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let all_inserted = |del_struct: &delaunay_struct_2d::DelaunayStructure2D| {
+            (0..del_struct.get_vertices().len())
+                .all(|ind| del_struct.get_simplicial().get_node_halfedge(ind).is_some())
+        };
+
+        // pending vertices completed by a bulk insertion
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertex([0., 0.], None)?;
+        del_struct.insert_vertex([1., 0.], None)?;
+        del_struct.insert_vertices(&[[0., 1.]], true)?;
+        assert!(all_inserted(&del_struct));
+        assert!(del_struct.is_valid()?);
+
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertex([0.5, 0.5], None)?;
+        del_struct.insert_vertices(&[[0., 0.], [1., 0.], [0., 1.]], true)?;
+        assert!(all_inserted(&del_struct));
+        assert!(del_struct.is_valid()?);
+
+        let vec_pts: Vec<[f64; 2]> = (0..100).map(|_| rng.gen()).collect();
+        del_struct.insert_vertices(&vec_pts, true)?;
+        assert_eq!(del_struct.get_vertices().len(), 104);
+        assert!(all_inserted(&del_struct));
+        assert!(del_struct.is_valid()?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_shortest_path() -> Result<()> {
+        let mut vec_pts = Vec::new();
+        for i in 0..10 {
+            for j in 0..10 {
+                vec_pts.push([i as f64, j as f64]);
+            }
+        }
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+        let path_length = |path: &[usize]| -> Result<f64> {
+            let mut length = 0.;
+            for ind in 1..path.len() {
+                assert!(del_struct.are_adjacent(path[ind - 1], path[ind])?);
+                let (pt0, pt1) = (vec_pts[path[ind - 1]], vec_pts[path[ind]]);
+                length += ((pt1[0] - pt0[0]).powi(2) + (pt1[1] - pt0[1]).powi(2)).sqrt();
+            }
+            Ok(length)
+        };
+
+        // straight along the grid
+        let path = del_struct.shortest_path(0, 90)?.unwrap();
+        assert_eq!((path[0], *path.last().unwrap()), (0, 90));
+        assert!((path_length(&path)? - 9.).abs() < 1e-9);
+
+        // grid steps and cell diagonals, between the straight line and Manhattan distances
+        let path = del_struct.shortest_path(0, 95)?.unwrap();
+        assert_eq!((path[0], *path.last().unwrap()), (0, 95));
+        let length = path_length(&path)?;
+        assert!(length >= (81f64 + 25.).sqrt() && length <= 14. + 1e-9);
+
+        assert_eq!(del_struct.shortest_path(42, 42)?, Some(vec![42]));
+        assert!(del_struct.shortest_path(0, 100).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_angle_bounds() -> Result<()> {
+        use std::f64::consts::{FRAC_PI_2, FRAC_PI_4};
+
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&[[0., 0.], [1., 0.], [0., 1.]], false)?;
+        let (min, max) = del_struct.angle_bounds()?;
+        assert!((min - FRAC_PI_4).abs() < 1e-12);
+        assert!((max - FRAC_PI_2).abs() < 1e-12);
+
+        let ind_tri = (0..del_struct.get_simplicial().get_nb_triangles())
+            .find(|&ind_tri| del_struct.triangle_coords(ind_tri).unwrap().is_some())
+            .unwrap();
+        let angles = del_struct.triangle_angles(ind_tri)?;
+        assert!((angles.iter().sum::<f64>() - std::f64::consts::PI).abs() < 1e-12);
+        assert!(del_struct.triangle_angles((ind_tri + 1) % 4).is_err());
+
+        let empty = delaunay_struct_2d::DelaunayStructure2D::new();
+        assert!(empty.angle_bounds().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_fixed_vertices() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let vec_pts: Vec<[f64; 2]> = (0..200).map(|_| rng.gen()).collect();
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+        let sensors = [[0.3, 0.3], [0.7, 0.6]];
+        del_struct.add_fixed_vertices(&sensors)?;
+        let nb_vertices = del_struct.get_vertices().len();
+        assert!(del_struct.is_fixed(nb_vertices - 1) && !del_struct.is_fixed(0));
+
+        // Lloyd-like smoothing of interior vertices, moving each toward its neighbors centroid
+        for _ in 0..5 {
+            for ind_v in 0..nb_vertices {
+                if del_struct.is_boundary_vertex(ind_v)? {
+                    continue;
+                }
+                let mut centroid = [0., 0.];
+                let outgoing = del_struct
+                    .get_simplicial()
+                    .get_outgoing_halfedges(ind_v)
+                    .iter()
+                    .map(|he| match he.last_node() {
+                        simplicial_struct_2d::Node::Value(ind) => ind,
+                        simplicial_struct_2d::Node::Infinity => unreachable!(),
+                    })
+                    .collect::<Vec<_>>();
+                for &ind in outgoing.iter() {
+                    let pt = del_struct.get_vertices()[ind];
+                    centroid = [centroid[0] + pt[0], centroid[1] + pt[1]];
+                }
+                let nb = outgoing.len() as f64;
+                let moved = del_struct.move_vertex(ind_v, [centroid[0] / nb, centroid[1] / nb]);
+                assert_eq!(moved.is_err(), del_struct.is_fixed(ind_v));
+            }
+        }
+        assert_eq!(del_struct.get_vertices()[nb_vertices - 2], sensors[0]);
+        assert_eq!(del_struct.get_vertices()[nb_vertices - 1], sensors[1]);
+
+        assert!(del_struct.remove_vertex(nb_vertices - 1).is_err());
+        del_struct.set_fixed(nb_vertices - 1, false)?;
+        del_struct.remove_vertex(nb_vertices - 1)?;
+        assert!(del_struct.set_fixed(nb_vertices, true).is_err());
+        Ok(())
+    }
+
+    #[cfg(feature = "petgraph")]
+    #[test]
+    fn test_to_petgraph() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let mut vec_pts: Vec<[f64; 2]> = (0..500).map(|_| rng.gen()).collect();
+        // duplicated vertex, outside the triangulation
+        vec_pts.push(vec_pts[0]);
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, false)?;
+
+        let graph = del_struct.to_petgraph();
+        let (nb_vertices, nb_edges, _, _) = del_struct.euler_characteristic()?;
+        assert_eq!(graph.node_count(), nb_vertices);
+        assert_eq!(nb_vertices, vec_pts.len() - 1);
+        assert_eq!(graph.edge_count(), nb_edges);
+        let edge = graph.edge_indices().next().unwrap();
+        let (node_a, node_b) = graph.edge_endpoints(edge).unwrap();
+        assert!(del_struct.are_adjacent(graph[node_a], graph[node_b])?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_region_centroid() -> Result<()> {
+        // L-shaped point set, triangulated region being its convex hull: the 2x2 square minus the
+        // corner triangle (2, 1), (2, 2), (1, 2)
+        let vec_pts = [[0., 0.], [2., 0.], [2., 1.], [1., 1.], [1., 2.], [0., 2.]];
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+
+        let centroid = del_struct.region_centroid()?;
+        let expected = 19. / 21.;
+        assert!((centroid[0] - expected).abs() < 1e-12);
+        assert!((centroid[1] - expected).abs() < 1e-12);
+        // bounding box and vertex centers are both (1, 1)
+        assert!(centroid[0] < 0.95 && centroid[1] < 0.95);
+
+        let empty = delaunay_struct_2d::DelaunayStructure2D::new();
+        assert!(empty.region_centroid().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_in_general_position() -> Result<()> {
+        let grid: Vec<[f64; 2]> = (0..10)
+            .flat_map(|i| (0..10).map(move |j| [i as f64, j as f64]))
+            .collect();
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&grid, true)?;
+        assert!(!del_struct.is_in_general_position()?);
+
+        let mut rng = rand::thread_rng();
+        let jittered: Vec<[f64; 2]> = grid
+            .iter()
+            .map(|pt| {
+                [
+                    pt[0] + rng.gen_range(-0.2..0.2),
+                    pt[1] + rng.gen_range(-0.2..0.2),
+                ]
+            })
+            .collect();
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&jittered, true)?;
+        assert!(del_struct.is_in_general_position()?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_unbounded_cell_rays() -> Result<()> {
+        let vec_pts = [[0., 0.], [4., 0.], [1., 3.]];
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, false)?;
+
+        let rays = del_struct.unbounded_cell_rays()?;
+        assert_eq!(rays.len(), 3);
+        for (origin, dir) in rays.iter() {
+            assert!((origin[0] - 2.).abs() < 1e-12 && (origin[1] - 1.).abs() < 1e-12);
+            assert!((dir[0] * dir[0] + dir[1] * dir[1] - 1.).abs() < 1e-12);
+            // outward normal of a hull edge: two vertices farthest along it, the third behind
+            let mut heights: Vec<f64> = vec_pts
+                .iter()
+                .map(|pt| pt[0] * dir[0] + pt[1] * dir[1])
+                .collect();
+            heights.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            assert!((heights[2] - heights[1]).abs() < 1e-12);
+            assert!(heights[0] < heights[1] - 1e-6);
+        }
+        assert!(rays
+            .iter()
+            .any(|(_, dir)| dir[0].abs() < 1e-12 && (dir[1] + 1.).abs() < 1e-12));
+        Ok(())
+    }
+
+    #[test]
+    fn test_identical_points() {
+        let vec_pts = vec![[0.3, -1.2]; 1000];
+        for reorder in [false, true] {
+            let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+            let start = Instant::now();
+            let err = del_struct.insert_vertices(&vec_pts, reorder).unwrap_err();
+            assert!(start.elapsed().as_secs() < 1);
+            assert_eq!(
+                err.downcast_ref::<DegenerateInput>(),
+                Some(&DegenerateInput { nb_points: 1000 })
+            );
+            assert!(del_struct.get_vertices().is_empty());
+        }
+    }
+
+    #[test]
+    fn test_boundary_polygon() -> Result<()> {
+        let vec_pts = [[0., 0.], [1., 0.], [1., 1.], [0., 1.], [0.4, 0.6]];
+        let mut del_struct = delaunay_struct_2d::DelaunayStructure2D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+
+        let polygon = del_struct.boundary_polygon()?;
+        assert_eq!(polygon.len(), 4);
+        // counterclockwise, starting anywhere
+        let start = polygon.iter().position(|&pt| pt == [0., 0.]).unwrap();
+        let rotated: Vec<[f64; 2]> = (0..4).map(|ind| polygon[(start + ind) % 4]).collect();
+        assert_eq!(rotated, vec![[0., 0.], [1., 0.], [1., 1.], [0., 1.]]);
+
+        let closed = del_struct.boundary_polygon_closed()?;
+        assert_eq!(closed.len(), 5);
+        assert_eq!(closed[..4], polygon[..]);
+        assert_eq!(closed[4], closed[0]);
+        Ok(())
+    }
 }
@@ -1,17 +1,20 @@
 #[cfg(test)]
 mod delaunay_3d_test {
     use anyhow::Result;
-    use env_logger;
     use rand::Rng;
+    use std::cmp::Ordering;
     use std::time::Instant;
 
     use simple_delaunay_lib::delaunay_3d::delaunay_struct_3d;
+    use simple_delaunay_lib::delaunay_3d::geometry_operations_3d;
+    use simple_delaunay_lib::delaunay_3d::simplicial_struct_3d;
 
     #[ctor::ctor]
     fn init() {
         env_logger::init();
     }
 
+    #[allow(clippy::ptr_arg, clippy::needless_borrow)]
     fn create_and_check_delaunay(vec_pts: &Vec<[f64; 3]>) -> Result<()> {
         let now = Instant::now();
         let mut del_struct = delaunay_struct_3d::DelaunayStructure3D::new();
@@ -22,7 +25,10 @@ mod delaunay_3d_test {
         log::info!("Delaunay computed in {}ms", milli);
 
         log::info!("Checking delaunay");
+        assert!(del_struct.get_simplicial().check_opposite_involution()?);
         assert!(del_struct.is_valid()?);
+        assert!(del_struct.boundary_is_closed_manifold()?);
+        assert!(!del_struct.has_inverted_elements()?);
         Ok(())
     }
 
@@ -79,6 +85,7 @@ mod delaunay_3d_test {
         log::info!("Delaunay computed in {}ms", milli);
 
         log::info!("Checking delaunay");
+        assert!(del_struct.get_simplicial().check_opposite_involution()?);
         assert!(del_struct.is_valid()?);
 
         let mut vec_pts: Vec<[f64; 3]> = Vec::new();
@@ -96,6 +103,594 @@ mod delaunay_3d_test {
         log::info!("Delaunay update computed in {}ms", milli);
 
         log::info!("Checking delaunay");
+        assert!(del_struct.get_simplicial().check_opposite_involution()?);
+        assert!(del_struct.is_valid()?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_iterators() -> Result<()> {
+        let mut rng = rand::thread_rng();
+
+        let mut vec_pts: Vec<[f64; 3]> = Vec::new();
+        for _ in 0..100 {
+            let (x, y, z): (f64, f64, f64) = rng.gen();
+            vec_pts.push([x, y, z]);
+        }
+        let mut del_struct = delaunay_struct_3d::DelaunayStructure3D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+        let simplicial = del_struct.get_simplicial();
+
+        assert_eq!(
+            simplicial.half_triangles().count(),
+            4 * simplicial.get_nb_tetrahedra()
+        );
+
+        for tri in simplicial.get_tetrahedron(0)?.halftriangles() {
+            for he in tri.halfedges() {
+                let nb_tetra = simplicial
+                    .get_halfedge_containing(&he.first_node(), &he.last_node())
+                    .len();
+                assert_eq!(he.edge_ring().count(), nb_tetra);
+                assert!(he
+                    .edge_ring()
+                    .all(|tri_ring| tri_ring.nodes().iter().any(|n| n.equals(&he.first_node()))));
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_tetrahedron_coords() -> Result<()> {
+        let vec_pts = vec![[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]];
+        let mut del_struct = delaunay_struct_3d::DelaunayStructure3D::new();
+        del_struct.insert_vertices(&vec_pts, false)?;
+
+        let mut nb_finite = 0;
+        for ind_tetra in 0..del_struct.get_simplicial().get_nb_tetrahedra() {
+            if let Some(tetra) = del_struct.tetrahedron_coords(ind_tetra)? {
+                nb_finite += 1;
+                for pt in vec_pts.iter() {
+                    assert!(tetra.contains(pt));
+                }
+            }
+        }
+        assert_eq!(nb_finite, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_regular_perturbation() -> Result<()> {
+        let mut vec_pts: Vec<[f64; 3]> = Vec::new();
+        let nb = 10;
+        for ind in 0..(nb * nb * nb) {
+            let x = ((ind % nb) as f64) / (nb as f64);
+            let y = (((ind / nb) % nb) as f64) / (nb as f64);
+            let z = ((ind / (nb * nb)) as f64) / (nb as f64);
+            vec_pts.push([x, y, z]);
+        }
+        let mut del_struct = delaunay_struct_3d::DelaunayStructure3D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+
+        assert_eq!(del_struct.get_nb_walk_fallbacks(), 0);
+        for ind_tetra in 0..del_struct.get_simplicial().get_nb_tetrahedra() {
+            if let Some([pt0, pt1, pt2, pt3]) = del_struct.tetrahedron_coords(ind_tetra)? {
+                assert!(geometry_operations_3d::orient_3d(pt0, pt1, pt2, pt3) > 0.);
+            }
+        }
+        assert!(del_struct.is_valid()?);
+
+        // ties broken by coordinates
+        let mut del_struct = delaunay_struct_3d::DelaunayStructure3D::new();
+        del_struct.set_symbolic_perturbation(false);
+        del_struct.insert_vertices(&vec_pts, true)?;
+        assert!(del_struct.is_valid()?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_binary_round_trip() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let mut vec_pts: Vec<[f64; 3]> = Vec::new();
+        for _ in 0..10000 {
+            let (x, y, z): (f64, f64, f64) = rng.gen();
+            vec_pts.push([x, y, z]);
+        }
+        let mut del_struct = delaunay_struct_3d::DelaunayStructure3D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+
+        let mut buffer = Vec::new();
+        del_struct.write_bin(&mut buffer)?;
+        let mut del_read =
+            delaunay_struct_3d::DelaunayStructure3D::read_bin(&mut buffer.as_slice())?;
+
+        assert_eq!(del_read.get_vertices(), del_struct.get_vertices());
+        assert_eq!(
+            del_read.get_simplicial().get_nb_tetrahedra(),
+            del_struct.get_simplicial().get_nb_tetrahedra()
+        );
+        for ind in 0..del_struct.get_simplicial().get_nb_tetrahedra() {
+            let nodes = del_struct.get_simplicial().get_tetrahedron(ind)?.nodes();
+            let nodes_read = del_read.get_simplicial().get_tetrahedron(ind)?.nodes();
+            for (nod, nod_read) in nodes.iter().zip(nodes_read.iter()) {
+                assert!(nod.equals(nod_read));
+            }
+        }
+        assert!(del_read.get_simplicial().is_valid()?);
+        del_read.insert_vertex([0.5, 0.5, 0.5], None)?;
+        assert!(del_read.get_simplicial().is_valid()?);
+
+        // two nodes of the first tetrahedron swapped, opposite halftriangles not matching anymore
+        let mut buffer_shuffled = buffer.clone();
+        let nodes = 5 + 8 + 24 * vec_pts.len() + 8;
+        let (node0, node1) = buffer_shuffled[nodes..nodes + 16].split_at_mut(8);
+        node0.swap_with_slice(node1);
+        assert!(
+            delaunay_struct_3d::DelaunayStructure3D::read_bin(&mut buffer_shuffled.as_slice())
+                .is_err()
+        );
+
+        // version mismatch
+        buffer[4] += 1;
+        assert!(delaunay_struct_3d::DelaunayStructure3D::read_bin(&mut buffer.as_slice()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_out_of_bounds() -> Result<()> {
+        let vec_pts = vec![[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]];
+        let mut del_struct = delaunay_struct_3d::DelaunayStructure3D::new();
+        del_struct.insert_vertices(&vec_pts, false)?;
+
+        assert!(del_struct
+            .insert_vertex([0.2, 0.2, 0.2], Some(1000))
+            .is_err());
+        assert_eq!(del_struct.get_vertices().len(), vec_pts.len());
+        assert!(del_struct.is_valid()?);
+        del_struct.insert_vertex([0.2, 0.2, 0.2], None)?;
+        assert!(del_struct.is_valid()?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_in_sphere() -> Result<()> {
+        let vec_pts = vec![[1., 1., 1.], [1., -1., -1.], [-1., 1., -1.], [-1., -1., 1.]];
+        let mut del_struct = delaunay_struct_3d::DelaunayStructure3D::new();
+        del_struct.insert_vertices(&vec_pts, false)?;
+
+        for ind_tetra in 0..del_struct.get_simplicial().get_nb_tetrahedra() {
+            if del_struct.tetrahedron_coords(ind_tetra)?.is_some() {
+                for &pt in vec_pts.iter() {
+                    assert_eq!(del_struct.in_sphere(ind_tetra, pt)?, Ordering::Equal);
+                }
+                assert_eq!(
+                    del_struct.in_sphere(ind_tetra, [0., 0., 0.])?,
+                    Ordering::Greater
+                );
+                assert_eq!(
+                    del_struct.in_sphere(ind_tetra, [2., 0., 0.])?,
+                    Ordering::Less
+                );
+            } else {
+                // beyond hull face, opposite to the point at infinity
+                assert_eq!(
+                    del_struct.in_sphere(ind_tetra, [0., 0., 0.])?,
+                    Ordering::Less
+                );
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_boundary_triangles() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let vec_pts: Vec<[f64; 3]> = (0..500)
+            .map(|_| {
+                let (x, y, z): (f64, f64, f64) = rng.gen();
+                [x, y, z]
+            })
+            .collect();
+        let mut del_struct = delaunay_struct_3d::DelaunayStructure3D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+
+        // every vertex lies on the inner side of outward oriented boundary triangles
+        for [ind_v1, ind_v2, ind_v3] in del_struct.boundary_triangles()? {
+            for &pt in vec_pts.iter() {
+                assert!(
+                    geometry_operations_3d::orient_3d(
+                        vec_pts[ind_v1],
+                        vec_pts[ind_v2],
+                        vec_pts[ind_v3],
+                        pt
+                    ) >= 0.
+                );
+            }
+        }
+        assert!(del_struct.boundary_is_closed_manifold()?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_slivers() -> Result<()> {
+        let regular = [[1., 1., 1.], [1., -1., -1.], [-1., 1., -1.], [-1., -1., 1.]];
+        let quality = geometry_operations_3d::tetrahedron_quality(regular);
+        assert!((quality - 1.).abs() < 1e-12);
+
+        let mut del_struct = delaunay_struct_3d::DelaunayStructure3D::new();
+        del_struct.insert_vertices(&regular, false)?;
+        assert!(del_struct.slivers(0.5)?.is_empty());
+
+        let flat = vec![[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0.3, 0.3, 1e-3]];
+        let mut del_struct = delaunay_struct_3d::DelaunayStructure3D::new();
+        del_struct.insert_vertices(&flat, false)?;
+        let slivers = del_struct.slivers(0.1)?;
+        assert_eq!(slivers.len(), 1);
+        assert!(del_struct.tetrahedron_coords(slivers[0])?.is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn test_clone() -> Result<()> {
+        use rand::SeedableRng;
+
+        // seeded: on some point sets, an insertion creates as many tetrahedra as it removes
+        let mut rng = rand::rngs::StdRng::seed_from_u64(368);
+        let vec_pts: Vec<[f64; 3]> = (0..200)
+            .map(|_| {
+                let (x, y, z): (f64, f64, f64) = rng.gen();
+                [x, y, z]
+            })
+            .collect();
+        let mut del_struct = delaunay_struct_3d::DelaunayStructure3D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+
+        let snapshot = del_struct.clone();
+        let nb_tetra = snapshot.get_simplicial().get_nb_tetrahedra();
+        del_struct.insert_vertex([0.5, 0.5, 0.5], None)?;
+
+        assert_eq!(snapshot.get_vertices().len(), 200);
+        assert_eq!(snapshot.get_simplicial().get_nb_tetrahedra(), nb_tetra);
+        assert_eq!(del_struct.get_vertices().len(), 201);
+        assert_ne!(del_struct.get_simplicial().get_nb_tetrahedra(), nb_tetra);
+        assert!(snapshot.is_valid()?);
+        assert!(del_struct.is_valid()?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_insert_vertex_tracked() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let vec_pts: Vec<[f64; 3]> = (0..300)
+            .map(|_| {
+                let (x, y, z): (f64, f64, f64) = rng.gen();
+                [x, y, z]
+            })
+            .collect();
+        let mut del_struct = delaunay_struct_3d::DelaunayStructure3D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+
+        for (ind, vertex) in [[0.5, 0.5, 0.5], [0.1, 0.9, 0.3], [2., 2., 2.]]
+            .into_iter()
+            .enumerate()
+        {
+            let ind_vertex = vec_pts.len() + ind;
+            let mut added = del_struct.insert_vertex_tracked(vertex, None)?;
+            added.sort();
+            added.dedup();
+
+            // created tetrahedra are exactly those around the new vertex
+            let around: Vec<usize> = (0..del_struct.get_simplicial().get_nb_tetrahedra())
+                .filter(|&ind_tetra| {
+                    del_struct
+                        .get_simplicial()
+                        .get_tetrahedron(ind_tetra)
+                        .unwrap()
+                        .nodes()
+                        .iter()
+                        .any(|node| {
+                            matches!(node, simplicial_struct_3d::Node::Value(ind) if *ind == ind_vertex)
+                        })
+                })
+                .collect();
+            assert!(!added.is_empty());
+            assert_eq!(added, around);
+        }
+        assert!(del_struct.is_valid()?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_delaunay_on_sphere() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let nb_pts = 500;
+        let points_lonlat: Vec<[f64; 2]> = (0..nb_pts)
+            .map(|_| {
+                let lon: f64 = rng.gen_range(-180.0..180.0);
+                let lat = rng.gen_range(-1.0f64..1.0).asin().to_degrees();
+                [lon, lat]
+            })
+            .collect();
+
+        let triangles = simple_delaunay_lib::delaunay_on_sphere(&points_lonlat)?;
+
+        // closed triangulated sphere: V - E + F = 2 with 3F = 2E
+        assert_eq!(triangles.len(), 2 * nb_pts - 4);
+
+        let to_3d = |[lon, lat]: [f64; 2]| {
+            let (lon, lat) = (lon.to_radians(), lat.to_radians());
+            [lat.cos() * lon.cos(), lat.cos() * lon.sin(), lat.sin()]
+        };
+        for tri in triangles.iter() {
+            let [pa, pb, pc] = tri.map(|ind| to_3d(points_lonlat[ind]));
+            let (u, v) = (
+                [pb[0] - pa[0], pb[1] - pa[1], pb[2] - pa[2]],
+                [pc[0] - pa[0], pc[1] - pa[1], pc[2] - pa[2]],
+            );
+            let normal = [
+                u[1] * v[2] - u[2] * v[1],
+                u[2] * v[0] - u[0] * v[2],
+                u[0] * v[1] - u[1] * v[0],
+            ];
+            // outward orientation
+            assert!(normal[0] * pa[0] + normal[1] * pa[1] + normal[2] * pa[2] > 0.);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_hull_measures() -> Result<()> {
+        let mut vec_pts = vec![[0.5, 0.5, 0.5], [0.2, 0.3, 0.7]];
+        for ind in 0..8 {
+            vec_pts.push([(ind % 2) as f64, ((ind / 2) % 2) as f64, (ind / 4) as f64]);
+        }
+        let mut del_struct = delaunay_struct_3d::DelaunayStructure3D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+        assert!((del_struct.hull_surface_area()? - 6.).abs() < 1e-12);
+        assert!((del_struct.hull_volume()? - 1.).abs() < 1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn test_minimal_enclosing_sphere() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let ctr = [1., 2., 3.];
+        let rad = 3.;
+        // regular tetrahedron inscribed in the sphere
+        let sq = rad / 3f64.sqrt();
+        let mut vec_pts = vec![
+            [ctr[0] + sq, ctr[1] + sq, ctr[2] + sq],
+            [ctr[0] + sq, ctr[1] - sq, ctr[2] - sq],
+            [ctr[0] - sq, ctr[1] + sq, ctr[2] - sq],
+            [ctr[0] - sq, ctr[1] - sq, ctr[2] + sq],
+        ];
+        while vec_pts.len() < 500 {
+            let dir = [
+                rng.gen::<f64>() * 2. - 1.,
+                rng.gen::<f64>() * 2. - 1.,
+                rng.gen::<f64>() * 2. - 1.,
+            ];
+            if dir[0] * dir[0] + dir[1] * dir[1] + dir[2] * dir[2] < 0.81 {
+                vec_pts.push([
+                    ctr[0] + rad * dir[0],
+                    ctr[1] + rad * dir[1],
+                    ctr[2] + rad * dir[2],
+                ]);
+            }
+        }
+        let mut del_struct = delaunay_struct_3d::DelaunayStructure3D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+
+        let (ctr_mes, rad_mes) = del_struct.minimal_enclosing_sphere()?;
+        assert!((ctr_mes[0] - ctr[0]).abs() < 1e-9);
+        assert!((ctr_mes[1] - ctr[1]).abs() < 1e-9);
+        assert!((ctr_mes[2] - ctr[2]).abs() < 1e-9);
+        assert!((rad_mes - rad).abs() < 1e-9);
+
+        // coplanar and aligned points
+        let square = [
+            [0., 0., 1.],
+            [2., 0., 1.],
+            [2., 2., 1.],
+            [0., 2., 1.],
+            [1., 1., 1.],
+        ];
+        let (ctr_sq, rad_sq) = geometry_operations_3d::minimal_enclosing_sphere(&square).unwrap();
+        assert!((ctr_sq[0] - 1.).abs() + (ctr_sq[1] - 1.).abs() + (ctr_sq[2] - 1.).abs() < 1e-12);
+        assert!((rad_sq - 2f64.sqrt()).abs() < 1e-12);
+        let line = [[0., 0., 0.], [1., 1., 1.], [3., 3., 3.], [2., 2., 2.]];
+        let (ctr_line, rad_line) = geometry_operations_3d::minimal_enclosing_sphere(&line).unwrap();
+        assert!((ctr_line[0] - 1.5).abs() < 1e-12);
+        assert!((rad_line - 1.5 * 3f64.sqrt()).abs() < 1e-12);
+        assert!(geometry_operations_3d::minimal_enclosing_sphere(&[]).is_none());
+        assert!(delaunay_struct_3d::DelaunayStructure3D::new()
+            .minimal_enclosing_sphere()
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_for_each_finite_tetrahedron() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let vec_pts: Vec<[f64; 3]> = (0..300).map(|_| rng.gen()).collect();
+        let mut del_struct = delaunay_struct_3d::DelaunayStructure3D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+
+        let nb_finite = (0..del_struct.get_simplicial().get_nb_tetrahedra())
+            .filter(|&ind_tetra| {
+                !del_struct
+                    .get_simplicial()
+                    .get_tetrahedron(ind_tetra)
+                    .unwrap()
+                    .contains_infinity()
+            })
+            .count();
+        let mut nb_calls = 0;
+        del_struct.for_each_finite_tetrahedron(|_| nb_calls += 1);
+        assert_eq!(nb_calls, nb_finite);
+        Ok(())
+    }
+
+    #[test]
+    fn test_nearly_coplanar_first_points() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let mut vec_pts: Vec<[f64; 3]> = (0..200).map(|_| rng.gen::<[f64; 3]>()).collect();
+        // points inserted first, nearly coplanar and clustered
+        for _ in 0..10 {
+            let (x, y): (f64, f64) = rng.gen();
+            vec_pts.push([
+                0.5 + 1e-3 * x,
+                0.5 + 1e-3 * y,
+                0.5 + 1e-12 * rng.gen::<f64>(),
+            ]);
+        }
+        let mut del_struct = delaunay_struct_3d::DelaunayStructure3D::new();
+        del_struct.insert_vertices(&vec_pts, false)?;
+        assert!(del_struct.is_valid()?);
+        assert!(del_struct.boundary_is_closed_manifold()?);
+        assert!(!del_struct.has_inverted_elements()?);
+
+        // only the nearly coplanar points, exactly coplanar ones being rejected
+        let mut flat_struct = delaunay_struct_3d::DelaunayStructure3D::new();
+        flat_struct.insert_vertices(&vec_pts[200..], false)?;
+        assert!(flat_struct.is_valid()?);
+
+        let mut coplanar_struct = delaunay_struct_3d::DelaunayStructure3D::new();
+        let coplanar: Vec<[f64; 3]> = vec_pts[200..]
+            .iter()
+            .map(|pt| [pt[0], pt[1], 0.5])
+            .collect();
+        assert!(coplanar_struct.insert_vertices(&coplanar, false).is_err());
+        Ok(())
+    }
+
+    #[cfg(feature = "testgen")]
+    #[test]
+    fn test_check_opposite_involution() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let vec_pts: Vec<[f64; 3]> = (0..100).map(|_| rng.gen()).collect();
+        let mut del_struct = delaunay_struct_3d::DelaunayStructure3D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+        assert!(del_struct.get_simplicial().check_opposite_involution()?);
+
+        // first halftriangle linked to the second one, which keeps its own opposite
+        del_struct
+            .get_simplicial_mut()
+            .corrupt_opposite_halftriangle(0, 1)?;
+        assert!(!del_struct.get_simplicial().check_opposite_involution()?);
+
+        // rejected when read back
+        let mut buffer = Vec::new();
+        del_struct.write_bin(&mut buffer)?;
+        assert!(delaunay_struct_3d::DelaunayStructure3D::read_bin(&mut buffer.as_slice()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_degenerate_tetrahedra() -> Result<()> {
+        let vec_pts = [
+            [0., 0., 0.],
+            [1., 0., 0.],
+            [0., 1., 0.],
+            [0.3, 0.3, 1e-14],
+            [0.3, 0.3, 1.],
+        ];
+        let mut del_struct = delaunay_struct_3d::DelaunayStructure3D::new();
+        del_struct.insert_vertices(&vec_pts, false)?;
+        assert!(del_struct.degenerate_tetrahedra()?.is_empty());
+
+        // tetrahedron of the four near-coplanar points
+        del_struct.set_flat_tolerance(1e-12);
+        let degenerate = del_struct.degenerate_tetrahedra()?;
+        assert_eq!(degenerate.len(), 1);
+        let mut nodes = del_struct
+            .get_simplicial()
+            .get_tetrahedron(degenerate[0])?
+            .nodes()
+            .map(|node| match node {
+                simplicial_struct_3d::Node::Value(ind) => ind,
+                simplicial_struct_3d::Node::Infinity => usize::MAX,
+            });
+        nodes.sort();
+        assert_eq!(nodes, [0, 1, 2, 3]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_insert_vertex_from_empty() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let mut del_struct = delaunay_struct_3d::DelaunayStructure3D::new();
+
+        // coplanar vertices before the first tetrahedron
+        for vert in [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [1., 1., 0.]] {
+            assert!(del_struct.insert_vertex_tracked(vert, None)?.is_empty());
+        }
+        let created = del_struct.insert_vertex_tracked([0., 0., 1.], None)?;
+        assert_eq!(
+            created.len(),
+            del_struct.get_simplicial().get_nb_tetrahedra()
+        );
+        assert!(!created.is_empty());
+        assert!(del_struct.is_valid()?);
+
+        for _ in 0..100 {
+            del_struct.insert_vertex(rng.gen(), None)?;
+        }
+        assert_eq!(del_struct.get_vertices().len(), 105);
+        assert!(del_struct.is_valid()?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_mixed_insertion_from_empty() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let all_inserted = |del_struct: &delaunay_struct_3d::DelaunayStructure3D| {
+            let mut inserted = vec![false; del_struct.get_vertices().len()];
+            for ind_tetra in 0..del_struct.get_simplicial().get_nb_tetrahedra() {
+                let tetra = del_struct
+                    .get_simplicial()
+                    .get_tetrahedron(ind_tetra)
+                    .unwrap();
+                for node in tetra.nodes() {
+                    if let simplicial_struct_3d::Node::Value(ind) = node {
+                        inserted[ind] = true;
+                    }
+                }
+            }
+            inserted.into_iter().all(|ins| ins)
+        };
+
+        // pending vertex completed by a bulk insertion
+        let mut del_struct = delaunay_struct_3d::DelaunayStructure3D::new();
+        del_struct.insert_vertex([0.2, 0.2, 0.2], None)?;
+        del_struct.insert_vertices(
+            &[[0., 0., 0.], [1., 0., 0.], [0., 1., 0.], [0., 0., 1.]],
+            true,
+        )?;
+        assert!(all_inserted(&del_struct));
+        assert!(del_struct.is_valid()?);
+
+        let vec_pts: Vec<[f64; 3]> = (0..100).map(|_| rng.gen()).collect();
+        del_struct.insert_vertices(&vec_pts, true)?;
+        assert_eq!(del_struct.get_vertices().len(), 105);
+        assert!(all_inserted(&del_struct));
+        assert!(del_struct.is_valid()?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_refine_uniform() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let vec_pts: Vec<[f64; 3]> = (0..50).map(|_| rng.gen()).collect();
+        let mut del_struct = delaunay_struct_3d::DelaunayStructure3D::new();
+        del_struct.insert_vertices(&vec_pts, true)?;
+        let nb_tetrahedra = del_struct.get_simplicial().get_nb_tetrahedra();
+
+        let added = del_struct.refine_uniform()?;
+        assert_eq!(added.start, vec_pts.len());
+        assert_eq!(added.end, del_struct.get_vertices().len());
+        assert!(added.len() > vec_pts.len());
+        assert!(del_struct.get_simplicial().get_nb_tetrahedra() > nb_tetrahedra);
         assert!(del_struct.is_valid()?);
         Ok(())
     }